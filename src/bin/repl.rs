@@ -30,7 +30,7 @@ fn main() -> Result<(), Box<dyn Error>> {
             line => match parser.parse(line) {
                 Ok(ast) => {
                     println!("Interpreted as: {ast}");
-                    println!("{ast:#?}");
+                    println!("AST: {}", ast.to_sexpr());
                     println!("Variables: {:?}", ast.vars);
                     let variables = ask_for_variables(&ast.vars);
                     println!(
@@ -0,0 +1,363 @@
+/* Built-in imports */
+use core::{iter, str};
+use std::collections::{HashMap, HashSet};
+/* Crate imports */
+use crate::{
+    context::Context,
+    element::{BinOp, Element, FunctionCall, UnOp},
+    token::Operator,
+    utils::hidden_macros::yeet,
+    xprs::Xprs,
+};
+
+/// Magic bytes starting an encoded expression.
+const MAGIC: &[u8; 4] = b"XPRS";
+/// Version of the encoding, bumped on any change of the layout or of the opcodes.
+pub const VERSION: u8 = 1;
+
+/// Maximum depth of a decoded (or imported) tree, deeper trees are rejected instead of overflowing the stack.
+pub const MAX_DEPTH: usize = 256;
+
+/* Opcodes */
+/// A number, followed by its 8 bytes (little-endian).
+const NUMBER: u8 = 0x00;
+/// A variable, followed by the index of its name.
+const VARIABLE: u8 = 0x01;
+/// A function call, followed by the index of its name, its number of arguments and its arguments.
+const FUNCTION: u8 = 0x02;
+/// An integer too large for a number, followed by its 8 bytes (little-endian).
+const INTEGER: u8 = 0x03;
+/// A binary operation (`0x10 | operator`), followed by its operands.
+const BINOP: u8 = 0x10;
+/// A unary operation (`0x20 | operator`), followed by its operand.
+const UNOP: u8 = 0x20;
+
+/// Encoder of an expression tree, interning the names.
+#[derive(Default)]
+struct Encoder<'el> {
+    /// The indices of the interned names.
+    indices: HashMap<&'el str, usize>,
+    /// The interned names, in order of appearance.
+    strings: Vec<&'el str>,
+    /// The encoded tree.
+    tree: Vec<u8>,
+}
+
+impl<'el> Encoder<'el> {
+    /// Encodes an element in pre-order.
+    fn element(&mut self, element: &'el Element) {
+        match *element {
+            Element::Number(num) => {
+                self.tree.push(NUMBER);
+                // the format is little-endian on every platform
+                #[allow(clippy::little_endian_bytes)]
+                self.tree.extend_from_slice(&num.to_le_bytes());
+            },
+            Element::Integer(int) => {
+                self.tree.push(INTEGER);
+                #[allow(clippy::little_endian_bytes)]
+                self.tree.extend_from_slice(&int.to_le_bytes());
+            },
+            Element::Variable(var) => {
+                self.tree.push(VARIABLE);
+                self.string(var);
+            },
+            Element::Function(ref func) => {
+                self.tree.push(FUNCTION);
+                self.string(&func.desc.name);
+                write_varint(&mut self.tree, func.args.len());
+                func.args.iter().for_each(|arg| self.element(arg));
+            },
+            Element::BinOp(ref binop) => {
+                self.tree.push(BINOP | operator_code(binop.op));
+                self.element(&binop.lhs);
+                self.element(&binop.rhs);
+            },
+            Element::UnOp(ref unop) => {
+                self.tree.push(UNOP | operator_code(unop.op));
+                self.element(&unop.operand);
+            },
+        }
+    }
+
+    /// Encodes the index of a name, interning it if needed.
+    fn string(&mut self, string: &'el str) {
+        let next = self.strings.len();
+        let index = *self.indices.entry(string).or_insert(next);
+        if index == next {
+            self.strings.push(string);
+        }
+        write_varint(&mut self.tree, index);
+    }
+}
+
+/// Decoder of an expression tree, validating it against a context.
+struct Decoder<'bytes, 'ctx> {
+    /// The encoded expression.
+    bytes: &'bytes [u8],
+    /// The context the functions and variables are checked against.
+    ctx: &'ctx Context<'ctx>,
+    /// The current position in the bytes.
+    cursor: usize,
+    /// The interned names.
+    strings: Vec<&'bytes str>,
+}
+
+impl<'bytes> Decoder<'bytes, '_> {
+    /// Checks that a variable is neither defined by the context nor undeclared.
+    fn check_variable(&self, var: &str) -> Result<(), DecodeError> {
+        if self.ctx.defines(var) {
+            yeet!(DecodeError::NotAVariable(var.to_owned()));
+        }
+        if let Some(expected) = self.ctx.get_expected_vars() {
+            if !expected.contains(var) {
+                yeet!(DecodeError::UndeclaredVariable(var.to_owned()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Decodes an element at the given depth.
+    fn element(
+        &mut self,
+        depth: usize,
+    ) -> Result<Element<'bytes>, DecodeError> {
+        if depth >= MAX_DEPTH {
+            yeet!(DecodeError::TooDeep);
+        }
+        let element = match self.read_u8()? {
+            NUMBER => {
+                let num = self.take(8)?;
+                let mut array = [0; 8];
+                array.copy_from_slice(num);
+                #[allow(clippy::little_endian_bytes)]
+                Element::Number(f64::from_le_bytes(array))
+            },
+            INTEGER => {
+                let int = self.take(8)?;
+                let mut array = [0; 8];
+                array.copy_from_slice(int);
+                #[allow(clippy::little_endian_bytes)]
+                Element::Integer(u64::from_le_bytes(array))
+            },
+            VARIABLE => {
+                let var = self.read_string()?;
+                self.check_variable(var)?;
+                Element::Variable(var)
+            },
+            FUNCTION => {
+                let name = self.read_string()?;
+                let func = self.ctx.find_function(name).ok_or_else(|| {
+                    DecodeError::UnknownFunction(name.to_owned())
+                })?;
+                let nb_args = self.read_varint()?;
+                if !func.arity.contains(nb_args) {
+                    yeet!(DecodeError::ArityMismatch(name.to_owned(), nb_args));
+                }
+                let args = iter::repeat_with(|| self.element(depth + 1))
+                    .take(nb_args)
+                    .collect::<Result<_, _>>()?;
+                FunctionCall::new_element(func, args)
+            },
+            code => {
+                match (code & 0xF0, Operator::ALL.get(usize::from(code & 0x0F)))
+                {
+                    (BINOP, Some(&op)) if op.is_binary() => {
+                        let lhs = self.element(depth + 1)?;
+                        let rhs = self.element(depth + 1)?;
+                        BinOp::new_element(op, lhs, rhs)
+                    },
+                    (UNOP, Some(&op)) if op.is_unary() => {
+                        UnOp::new_element(op, self.element(depth + 1)?)
+                    },
+                    _ => yeet!(DecodeError::InvalidOpcode(code)),
+                }
+            },
+        };
+        Ok(element)
+    }
+
+    /// Reads the index of an interned name.
+    fn read_string(&mut self) -> Result<&'bytes str, DecodeError> {
+        let index = self.read_varint()?;
+        self.strings
+            .get(index)
+            .copied()
+            .ok_or(DecodeError::InvalidStringIndex(index))
+    }
+
+    /// Reads the table of interned names.
+    fn read_strings(&mut self) -> Result<(), DecodeError> {
+        let count = self.read_varint()?;
+        // the count isn't trusted to preallocate, each name takes at least a byte
+        self.strings
+            .reserve(count.min(self.bytes.len() - self.cursor));
+        for _ in 0..count {
+            let len = self.read_varint()?;
+            let string = str::from_utf8(self.take(len)?)
+                .map_err(|_err| DecodeError::InvalidUtf8)?;
+            self.strings.push(string);
+        }
+        Ok(())
+    }
+
+    /// Reads a byte.
+    fn read_u8(&mut self) -> Result<u8, DecodeError> {
+        let byte = *self
+            .bytes
+            .get(self.cursor)
+            .ok_or(DecodeError::UnexpectedEnd)?;
+        self.cursor += 1;
+        Ok(byte)
+    }
+
+    /// Reads an unsigned LEB128 integer.
+    fn read_varint(&mut self) -> Result<usize, DecodeError> {
+        let mut value: usize = 0;
+        let mut shift: u32 = 0;
+        loop {
+            let byte = self.read_u8()?;
+            let bits = usize::from(byte & 0x7F);
+            value |= bits
+                .checked_shl(shift)
+                .filter(|shifted| shifted >> shift == bits)
+                .ok_or(DecodeError::IntegerOverflow)?;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    /// Takes the next `len` bytes.
+    fn take(&mut self, len: usize) -> Result<&'bytes [u8], DecodeError> {
+        let taken = self
+            .cursor
+            .checked_add(len)
+            .and_then(|end| self.bytes.get(self.cursor..end))
+            .ok_or(DecodeError::UnexpectedEnd)?;
+        self.cursor += len;
+        Ok(taken)
+    }
+}
+
+/// Represents an error that occurs when decoding an encoded expression.
+#[derive(Debug, Eq, PartialEq, thiserror::Error, Clone, Hash)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// A function is called with a number of arguments it doesn't accept.
+    #[error("Decoding error: `{0}` called with {1} arguments")]
+    ArityMismatch(String, usize),
+    /// An integer doesn't fit in a `usize`.
+    #[error("Decoding error: integer overflow")]
+    IntegerOverflow,
+    /// The bytes don't start with the expected header.
+    #[error("Decoding error: not an encoded expression")]
+    InvalidHeader,
+    /// An opcode is unknown.
+    #[error("Decoding error: invalid opcode {0:#04x}")]
+    InvalidOpcode(u8),
+    /// A name refers to a missing entry of the names table.
+    #[error("Decoding error: invalid name index {0}")]
+    InvalidStringIndex(usize),
+    /// A name isn't valid UTF-8.
+    #[error("Decoding error: invalid UTF-8 in a name")]
+    InvalidUtf8,
+    /// A variable is now defined by the context (as a symbol, a constant or a function).
+    #[error("Decoding error: `{0}` is defined by the context")]
+    NotAVariable(String),
+    /// The tree is nested deeper than the decoder accepts.
+    #[error("Decoding error: expression nested too deeply")]
+    TooDeep,
+    /// Bytes remain after the expression.
+    #[error("Decoding error: {0} trailing bytes")]
+    TrailingBytes(usize),
+    /// A variable isn't one of the context's expected variables.
+    #[error("Decoding error: variable `{0}` is not declared")]
+    UndeclaredVariable(String),
+    /// The bytes end in the middle of the expression.
+    #[error("Decoding error: unexpected end of input")]
+    UnexpectedEnd,
+    /// A function isn't known (or isn't permitted) by the context.
+    #[error("Decoding error: unknown function `{0}`")]
+    UnknownFunction(String),
+    /// The encoding version isn't supported, the expression must be encoded again.
+    #[error("Decoding error: unsupported version {0} (expected {VERSION})")]
+    UnsupportedVersion(u8),
+}
+
+/// Returns the code of an operator, its index in [`Operator::ALL`].
+const fn operator_code(op: Operator) -> u8 {
+    match op {
+        Operator::Plus => 0,
+        Operator::Minus => 1,
+        Operator::Times => 2,
+        Operator::Divide => 3,
+        Operator::IntDivide => 4,
+        Operator::Power => 5,
+        Operator::Modulo => 6,
+        Operator::Factorial => 7,
+        Operator::BitAnd => 8,
+        Operator::BitOr => 9,
+        Operator::BitXor => 10,
+        Operator::ShiftLeft => 11,
+        Operator::ShiftRight => 12,
+    }
+}
+
+/// Encodes an expression tree, see [`Xprs::to_bytes`].
+pub fn encode(root: &Element) -> Vec<u8> {
+    let mut encoder = Encoder::default();
+    encoder.element(root);
+
+    let mut bytes = MAGIC.to_vec();
+    bytes.push(VERSION);
+    write_varint(&mut bytes, encoder.strings.len());
+    for string in encoder.strings {
+        write_varint(&mut bytes, string.len());
+        bytes.extend_from_slice(string.as_bytes());
+    }
+    bytes.extend(encoder.tree);
+    bytes
+}
+
+/// Decodes an expression tree, see [`Xprs::from_bytes`].
+pub fn decode<'bytes>(
+    bytes: &'bytes [u8],
+    ctx: &Context,
+) -> Result<Xprs<'bytes>, DecodeError> {
+    let mut decoder = Decoder {
+        bytes,
+        cursor: 0,
+        strings: Vec::new(),
+        ctx,
+    };
+    if decoder.take(MAGIC.len()).ok() != Some(MAGIC.as_slice()) {
+        yeet!(DecodeError::InvalidHeader);
+    }
+    let version = decoder.read_u8()?;
+    if version != VERSION {
+        yeet!(DecodeError::UnsupportedVersion(version));
+    }
+    decoder.read_strings()?;
+    let root = decoder.element(0)?;
+    if decoder.cursor != bytes.len() {
+        yeet!(DecodeError::TrailingBytes(bytes.len() - decoder.cursor));
+    }
+
+    let mut vars = HashSet::new();
+    root.find_variables(&mut vars);
+    Ok(Xprs { root, vars })
+}
+
+/// Appends an unsigned LEB128 integer.
+fn write_varint(bytes: &mut Vec<u8>, mut value: usize) {
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    while value >= 0x80 {
+        // masked to 7 bits
+        bytes.push((value & 0x7F) as u8 | 0x80);
+        value >>= 7_u32;
+    }
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    bytes.push(value as u8);
+}
@@ -0,0 +1,207 @@
+/* Crate imports */
+use super::{is_ascii_identifier, Target};
+use crate::token::{Function, Operator};
+
+/// Names that can't be used as identifiers: the C99 keywords,
+/// and the `math.h` functions and macros called by the generated code.
+const RESERVED: [&str; 69] = [
+    "auto",
+    "break",
+    "case",
+    "char",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+    "_Bool",
+    "_Complex",
+    "_Imaginary",
+    "acos",
+    "acosh",
+    "asin",
+    "asinh",
+    "atan",
+    "atan2",
+    "atanh",
+    "cbrt",
+    "ceil",
+    "cos",
+    "cosh",
+    "exp",
+    "fabs",
+    "floor",
+    "fmax",
+    "fmin",
+    "fmod",
+    "hypot",
+    "isfinite",
+    "log",
+    "log10",
+    "pow",
+    "round",
+    "sin",
+    "sinh",
+    "sqrt",
+    "tan",
+    "tanh",
+    "tgamma",
+    "trunc",
+    "NAN",
+    "INFINITY",
+];
+
+/// C99 target using `math.h`, see [`Xprs::to_c`](crate::Xprs::to_c).
+pub struct C99;
+
+impl Target for C99 {
+    fn binary(op: Operator, lhs: String, rhs: String) -> Option<String> {
+        match op {
+            Operator::Plus
+            | Operator::Minus
+            | Operator::Times
+            | Operator::Divide => Some(format!("({lhs} {op} {rhs})")),
+            Operator::IntDivide => Some(format!("trunc({lhs} / {rhs})")),
+            Operator::Power => Some(format!("pow({lhs}, {rhs})")),
+            Operator::Modulo => Some(format!("fmod({lhs}, {rhs})")),
+            Operator::BitAnd
+            | Operator::BitOr
+            | Operator::BitXor
+            | Operator::ShiftLeft
+            | Operator::ShiftRight
+            | Operator::Factorial => None,
+        }
+    }
+
+    #[allow(clippy::pattern_type_mismatch)]
+    fn built_in(name: &str, args: Vec<String>) -> Option<String> {
+        let code = match (name, args.as_slice()) {
+            ("ln", [arg]) => format!("log({arg})"),
+            ("log", [arg]) => format!("log10({arg})"),
+            ("logn", [arg, base]) => format!("(log({arg}) / log({base}))"),
+            ("abs", [arg]) => format!("fabs({arg})"),
+            ("gamma", [arg]) => format!("tgamma({arg})"),
+            ("recip", [arg]) => format!("(1.0 / {arg})"),
+            ("fract", [arg]) => format!("({arg} - trunc({arg}))"),
+            ("round", [arg, digits]) if digits == "0.0" => {
+                format!("round({arg})")
+            },
+            // the number is returned as is when scaling it overflows
+            ("round", [arg, digits]) => {
+                let factor = format!("pow(10.0, trunc({digits}))");
+                format!(
+                    "(isfinite({factor}) && isfinite({arg} * {factor}) ? round({arg} * {factor}) / {factor} : {arg})"
+                )
+            },
+            ("min" | "max", [first, rest @ ..]) if !rest.is_empty() => {
+                rest.iter().fold(first.clone(), |acc, arg| {
+                    format!("f{name}({acc}, {arg})")
+                })
+            },
+            (
+                "sin" | "sinh" | "asin" | "asinh" | "cos" | "cosh" | "acos"
+                | "acosh" | "tan" | "tanh" | "atan" | "atan2" | "atanh"
+                | "sqrt" | "cbrt" | "exp" | "floor" | "ceil" | "trunc"
+                | "hypot",
+                _,
+            ) => format!("{name}({})", args.join(", ")),
+            // `factorial`, `rand`, `sum`, `mean` and `min` or `max` of a single number
+            _ => return None,
+        };
+        Some(code)
+    }
+
+    fn external(
+        path: &[String],
+        func: &Function,
+        args: Vec<String>,
+    ) -> Option<String> {
+        // C has no slices, nor namespaces
+        (func.arity.max == Some(func.arity.min))
+            .then(|| format!("{}({})", path.join("_"), args.join(", ")))
+    }
+
+    fn function(name: &str, params: &[(String, bool)], body: &str) -> String {
+        let typed_params = if params.is_empty() {
+            "void".to_owned()
+        } else {
+            params
+                .iter()
+                .map(|param| format!("double {}", param.0))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let unused = params
+            .iter()
+            .filter(|param| !param.1)
+            .map(|param| ["    (void)", &param.0, ";\n"].concat())
+            .collect::<String>();
+        format!(
+            "double {name}({typed_params}) {{\n{unused}    return {body};\n}}\n"
+        )
+    }
+
+    fn identifier(name: &str) -> Option<String> {
+        (is_ascii_identifier(name) && !RESERVED.contains(&name))
+            .then(|| name.to_owned())
+    }
+
+    fn number(num: f64) -> String {
+        if num.is_nan() {
+            "NAN".to_owned()
+        } else if num == f64::INFINITY {
+            "INFINITY".to_owned()
+        } else if num == f64::NEG_INFINITY {
+            "(-INFINITY)".to_owned()
+        } else if num.is_sign_negative() {
+            format!("({num:?})")
+        } else {
+            // `Debug` keeps a decimal point or an exponent, and all the digits
+            format!("{num:?}")
+        }
+    }
+
+    fn unary(op: Operator, operand: String) -> Option<String> {
+        match op {
+            Operator::Minus => Some(format!("(-{operand})")),
+            Operator::Factorial => None,
+            // the other operators aren't unary
+            Operator::Plus
+            | Operator::Times
+            | Operator::Divide
+            | Operator::IntDivide
+            | Operator::Power
+            | Operator::Modulo
+            | Operator::BitAnd
+            | Operator::BitOr
+            | Operator::BitXor
+            | Operator::ShiftLeft
+            | Operator::ShiftRight => Some(operand),
+        }
+    }
+}
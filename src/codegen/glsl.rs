@@ -0,0 +1,184 @@
+/* Crate imports */
+use super::{is_ascii_identifier, Target};
+use crate::token::{Function, Operator};
+
+/// Names that can't be used as identifiers: the GLSL keywords, reserved words and scalar, vector and matrix types,
+/// and the built-in functions called by (or close to) the generated code, the sampler and image types are checked apart.
+#[rustfmt::skip]
+const RESERVED: [&str; 154] = [
+    // keywords
+    "attribute", "const", "uniform", "varying", "buffer", "shared", "coherent",
+    "volatile", "restrict", "readonly", "writeonly", "layout", "centroid",
+    "flat", "smooth", "noperspective", "patch", "sample", "break", "continue",
+    "do", "for", "while", "switch", "case", "default", "if", "else",
+    "subroutine", "in", "out", "inout", "true", "false", "invariant", "precise",
+    "discard", "return", "lowp", "mediump", "highp", "precision", "struct",
+    // reserved for future use
+    "common", "partition", "active", "asm", "class", "union", "enum", "typedef",
+    "template", "this", "resource", "goto", "inline", "noinline", "public",
+    "static", "extern", "external", "interface", "long", "short", "half",
+    "fixed", "unsigned", "superp", "input", "output", "hvec2", "hvec3", "hvec4",
+    "fvec2", "fvec3", "fvec4", "filter", "sizeof", "cast", "namespace", "using",
+    // types
+    "float", "double", "int", "uint", "bool", "void", "atomic_uint", "mat2",
+    "mat3", "mat4", "dmat2", "dmat3", "dmat4", "mat2x2", "mat2x3", "mat2x4",
+    "mat3x2", "mat3x3", "mat3x4", "mat4x2", "mat4x3", "mat4x4", "dmat2x2",
+    "dmat2x3", "dmat2x4", "dmat3x2", "dmat3x3", "dmat3x4", "dmat4x2", "dmat4x3",
+    "dmat4x4", "vec2", "vec3", "vec4", "dvec2", "dvec3", "dvec4", "ivec2",
+    "ivec3", "ivec4", "uvec2", "uvec3", "uvec4", "bvec2", "bvec3", "bvec4",
+    // functions
+    "main", "abs", "acos", "acosh", "asin", "asinh", "atan", "atanh", "ceil",
+    "cos", "cosh", "exp", "floor", "isinf", "isnan", "log", "max", "min", "mod",
+    "pow", "round", "sin", "sinh", "sqrt", "tan", "tanh", "trunc",
+];
+
+/// Dimensions of the sampler and image types (`sampler2D`, `usampler2DArray`, `image2DMS`…).
+#[rustfmt::skip]
+const OPAQUE_DIMENSIONS: [&str; 13] = [
+    "", "1D", "2D", "3D", "Cube", "2DRect", "3DRect", "1DArray", "2DArray",
+    "CubeArray", "Buffer", "2DMS", "2DMSArray",
+];
+
+/// GLSL target (version 1.30 and later), computing with `float`s,
+/// see [`Xprs::to_glsl`](crate::Xprs::to_glsl).
+pub struct Glsl;
+
+impl Target for Glsl {
+    fn binary(op: Operator, lhs: String, rhs: String) -> Option<String> {
+        match op {
+            Operator::Plus
+            | Operator::Minus
+            | Operator::Times
+            | Operator::Divide => Some(format!("({lhs} {op} {rhs})")),
+            Operator::IntDivide => Some(format!("trunc({lhs} / {rhs})")),
+            Operator::Power => Some(format!("pow({lhs}, {rhs})")),
+            // `mod` is floored, `%` takes the sign of `lhs`
+            Operator::Modulo => {
+                Some(format!("({lhs} - {rhs} * trunc({lhs} / {rhs}))"))
+            },
+            Operator::BitAnd
+            | Operator::BitOr
+            | Operator::BitXor
+            | Operator::ShiftLeft
+            | Operator::ShiftRight
+            | Operator::Factorial => None,
+        }
+    }
+
+    #[allow(clippy::pattern_type_mismatch)]
+    fn built_in(name: &str, args: Vec<String>) -> Option<String> {
+        let code = match (name, args.as_slice()) {
+            ("ln", [arg]) => format!("log({arg})"),
+            ("log", [arg]) => format!("(log({arg}) / log(10.0))"),
+            ("logn", [arg, base]) => format!("(log({arg}) / log({base}))"),
+            ("atan2", [lhs, rhs]) => format!("atan({lhs}, {rhs})"),
+            ("hypot", [lhs, rhs]) => {
+                format!("sqrt(({lhs} * {lhs}) + ({rhs} * {rhs}))")
+            },
+            ("recip", [arg]) => format!("(1.0 / {arg})"),
+            // GLSL's `fract` is `x - floor(x)`
+            ("fract", [arg]) => format!("({arg} - trunc({arg}))"),
+            ("round", [arg, digits]) if digits == "0.0" => {
+                format!("round({arg})")
+            },
+            // the number is returned as is when scaling it overflows
+            ("round", [arg, digits]) => {
+                let factor = format!("pow(10.0, trunc({digits}))");
+                let scaled = format!("({arg} * {factor})");
+                format!(
+                    "((isinf({factor}) || isinf({scaled}) || isnan({scaled})) ? {arg} : round({scaled}) / {factor})"
+                )
+            },
+            ("min" | "max", [first, rest @ ..]) if !rest.is_empty() => {
+                rest.iter().fold(first.clone(), |acc, arg| {
+                    format!("{name}({acc}, {arg})")
+                })
+            },
+            (
+                "sin" | "sinh" | "asin" | "asinh" | "cos" | "cosh" | "acos"
+                | "acosh" | "tan" | "tanh" | "atan" | "atanh" | "sqrt" | "exp"
+                | "abs" | "floor" | "ceil" | "trunc",
+                _,
+            ) => format!("{name}({})", args.join(", ")),
+            // `cbrt`, `factorial`, `gamma`, `rand`, `sum`, `mean` and `min` or `max` of a single number
+            _ => return None,
+        };
+        Some(code)
+    }
+
+    fn external(
+        path: &[String],
+        func: &Function,
+        args: Vec<String>,
+    ) -> Option<String> {
+        // GLSL has no arrays of unknown size, nor namespaces
+        (func.arity.max == Some(func.arity.min))
+            .then(|| format!("{}({})", path.join("_"), args.join(", ")))
+    }
+
+    fn function(name: &str, params: &[(String, bool)], body: &str) -> String {
+        let typed_params = params
+            .iter()
+            .map(|param| format!("float {}", param.0))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("float {name}({typed_params}) {{\n    return {body};\n}}\n")
+    }
+
+    fn identifier(name: &str) -> Option<String> {
+        // `gl_` prefixed names and double underscores are reserved
+        (is_ascii_identifier(name)
+            && !RESERVED.contains(&name)
+            && !is_opaque_type(name)
+            && !name.starts_with("gl_")
+            && !name.contains("__"))
+        .then(|| name.to_owned())
+    }
+
+    fn number(num: f64) -> String {
+        // GLSL has no constants for the non-finite numbers
+        if num.is_nan() {
+            "(0.0 / 0.0)".to_owned()
+        } else if num == f64::INFINITY {
+            "(1.0 / 0.0)".to_owned()
+        } else if num == f64::NEG_INFINITY {
+            "(-1.0 / 0.0)".to_owned()
+        } else if num.is_sign_negative() {
+            format!("({num:?})")
+        } else {
+            // `Debug` keeps a decimal point or an exponent
+            format!("{num:?}")
+        }
+    }
+
+    fn unary(op: Operator, operand: String) -> Option<String> {
+        match op {
+            Operator::Minus => Some(format!("(-{operand})")),
+            Operator::Factorial => None,
+            // the other operators aren't unary
+            Operator::Plus
+            | Operator::Times
+            | Operator::Divide
+            | Operator::IntDivide
+            | Operator::Power
+            | Operator::Modulo
+            | Operator::BitAnd
+            | Operator::BitOr
+            | Operator::BitXor
+            | Operator::ShiftLeft
+            | Operator::ShiftRight => Some(operand),
+        }
+    }
+}
+
+/// Whether a name is a sampler or image type, possibly with an `i` or `u` prefix and a `Shadow` suffix.
+fn is_opaque_type(name: &str) -> bool {
+    [
+        "sampler", "isampler", "usampler", "image", "iimage", "uimage",
+    ]
+    .iter()
+    .filter_map(|prefix| name.strip_prefix(prefix))
+    .any(|dims| {
+        OPAQUE_DIMENSIONS.contains(&dims.strip_suffix("Shadow").unwrap_or(dims))
+    })
+}
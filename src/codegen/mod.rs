@@ -0,0 +1,234 @@
+/* Modules */
+/// C99 target module.
+pub mod c99;
+/// GLSL target module.
+pub mod glsl;
+/// Python target module.
+pub mod numpy;
+pub mod runtime;
+/// Rust target module.
+pub mod rust;
+/* Built-in imports */
+use core::marker::PhantomData;
+use std::collections::HashMap;
+/* Crate imports */
+use crate::{
+    context::Context,
+    element::Element,
+    integer::Integer as _,
+    token::{Function, Identifier, Operator},
+    utils::hidden_macros::yeet,
+    xprs::Xprs,
+};
+
+/// A target language of the code generation.
+///
+/// Operations are emitted fully parenthesized, so the target's precedence rules don't matter.
+/// The operations and functions without an equivalent in the target are emitted as [`None`].
+pub trait Target {
+    /// Emits a binary operation.
+    fn binary(op: Operator, lhs: String, rhs: String) -> Option<String>;
+
+    /// Emits a call to a built-in function.
+    fn built_in(name: &str, args: Vec<String>) -> Option<String>;
+
+    /// Emits a call to a function of the context, `path` being its namespaces and name as identifiers.
+    fn external(
+        path: &[String],
+        func: &Function,
+        args: Vec<String>,
+    ) -> Option<String>;
+
+    /// Emits a function named `name` taking the `params` and returning `body`,
+    /// each parameter being paired with whether it appears in the body.
+    fn function(name: &str, params: &[(String, bool)], body: &str) -> String;
+
+    /// Returns a name written as an identifier of the target, if it's a valid one.
+    fn identifier(name: &str) -> Option<String>;
+
+    /// Emits a number.
+    fn number(num: f64) -> String;
+
+    /// Emits a unary operation.
+    fn unary(op: Operator, operand: String) -> Option<String>;
+}
+
+/// Emitter of the expression tree for a target.
+struct Emitter<'ids, 'names, T> {
+    /// The default context, resolving the built-in functions.
+    built_ins: Context<'static>,
+    /// The identifiers of the parameters, by variable name.
+    identifiers: &'ids HashMap<&'names str, String>,
+    /// The target language.
+    target: PhantomData<T>,
+    /// The operators and functions without an equivalent in the target, in order of appearance.
+    unsupported: Vec<String>,
+}
+
+impl<T: Target> Emitter<'_, '_, T> {
+    /// Emits an element.
+    fn element(&mut self, element: &Element) -> Result<String, CodegenError> {
+        let code = match *element {
+            Element::Number(num) => Some(T::number(num)),
+            // the generated code works on floats
+            Element::Integer(int) => Some(T::number(int.to_f64())),
+            Element::Variable(var) => {
+                Some(self.identifiers.get(var).cloned().ok_or_else(|| {
+                    CodegenError::UnboundVariable(var.to_owned())
+                })?)
+            },
+            Element::UnOp(ref unop) => {
+                let operand = self.element(&unop.operand)?;
+                T::unary(unop.op, operand)
+                    .or_else(|| self.unsupported(unop.op.to_string()))
+            },
+            Element::BinOp(ref binop) => {
+                let lhs = self.element(&binop.lhs)?;
+                let rhs = self.element(&binop.rhs)?;
+                T::binary(binop.op, lhs, rhs)
+                    .or_else(|| self.unsupported(binop.op.to_string()))
+            },
+            Element::Function(ref func) => {
+                let args = func
+                    .args
+                    .iter()
+                    .map(|arg| self.element(arg))
+                    .collect::<Result<_, _>>()?;
+                let code = if self.is_built_in(&func.desc) {
+                    T::built_in(&func.desc.name, args)
+                } else {
+                    let path = func
+                        .desc
+                        .name
+                        .split('.')
+                        .map(identifier::<T>)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    T::external(&path, &func.desc, args)
+                };
+                code.or_else(|| self.unsupported(func.desc.name.to_string()))
+            },
+        };
+        // the code of an unsupported operation is never used
+        Ok(code.unwrap_or_default())
+    }
+
+    /// Whether a function is a built-in one, and not a function of the context with the same name.
+    fn is_built_in(&self, func: &Function) -> bool {
+        matches!(
+            Identifier::from_str(&func.name, &self.built_ins),
+            Identifier::Function(ref built_in) if built_in == func
+        )
+    }
+
+    /// Records an operator or a function without an equivalent in the target.
+    fn unsupported(&mut self, name: String) -> Option<String> {
+        if !self.unsupported.contains(&name) {
+            self.unsupported.push(name);
+        }
+        None
+    }
+}
+
+/// Represents an error that occurs when generating code from an expression.
+#[derive(Debug, Eq, PartialEq, thiserror::Error, Clone, Hash)]
+#[non_exhaustive]
+pub enum CodegenError {
+    /// A parameter is given more than once.
+    #[error("Codegen error: duplicate parameter `{0}`")]
+    DuplicateParameter(String),
+    /// A name isn't a valid identifier in the target language.
+    #[error(
+        "Codegen error: `{0}` isn't a valid identifier in the target language"
+    )]
+    InvalidIdentifier(String),
+    /// A variable of the expression isn't one of the parameters.
+    #[error("Codegen error: variable `{0}` isn't a parameter")]
+    UnboundVariable(String),
+    /// Operators or functions of the expression have no equivalent in the target language.
+    #[error(
+        "Codegen error: no equivalent in the target language for `{}`",
+        .0.join("`, `")
+    )]
+    Unsupported(Vec<String>),
+}
+
+/// Generates a function of the target computing the expression, see [`Xprs::to_rust`], [`Xprs::to_c`] and [`Xprs::to_glsl`].
+pub fn generate<T: Target>(
+    xprs: &Xprs,
+    name: &str,
+    params: &[&str],
+) -> Result<String, CodegenError> {
+    let fn_name = identifier::<T>(name)?;
+    let mut identifiers = HashMap::with_capacity(params.len());
+    for &param in params {
+        if identifiers.insert(param, identifier::<T>(param)?).is_some() {
+            yeet!(CodegenError::DuplicateParameter(param.to_owned()));
+        }
+    }
+    let mut unbound = xprs
+        .vars
+        .iter()
+        .filter(|var| !identifiers.contains_key(*var))
+        .collect::<Vec<_>>();
+    unbound.sort_unstable();
+    if let Some(&&var) = unbound.first() {
+        yeet!(CodegenError::UnboundVariable(var.to_owned()));
+    }
+
+    let body = emit::<T>(xprs, &identifiers)?;
+    let typed_params = params
+        .iter()
+        .map(|param| {
+            // all identifiers have been inserted above
+            let ident = identifiers.get(param).cloned().unwrap_or_default();
+            (ident, xprs.vars.contains(param))
+        })
+        .collect::<Vec<_>>();
+    Ok(T::function(&fn_name, &typed_params, &body))
+}
+
+/// Generates an expression of the target computing the expression,
+/// its variables being written as identifiers, see [`Xprs::to_numpy`].
+pub fn expression<T: Target>(xprs: &Xprs) -> Result<String, CodegenError> {
+    let mut vars = xprs.vars.iter().copied().collect::<Vec<_>>();
+    // reports the first invalid variable
+    vars.sort_unstable();
+    let identifiers = vars
+        .into_iter()
+        .map(|var| Ok((var, identifier::<T>(var)?)))
+        .collect::<Result<_, CodegenError>>()?;
+    emit::<T>(xprs, &identifiers)
+}
+
+/// Emits the expression, failing if an operator or a function has no equivalent in the target.
+fn emit<T: Target>(
+    xprs: &Xprs,
+    identifiers: &HashMap<&str, String>,
+) -> Result<String, CodegenError> {
+    let mut emitter = Emitter::<T> {
+        identifiers,
+        built_ins: Context::default(),
+        unsupported: Vec::new(),
+        target: PhantomData,
+    };
+    let code = emitter.element(&xprs.root)?;
+    if !emitter.unsupported.is_empty() {
+        yeet!(CodegenError::Unsupported(emitter.unsupported));
+    }
+    Ok(code)
+}
+
+/// Returns a name written as an identifier of the target.
+fn identifier<T: Target>(name: &str) -> Result<String, CodegenError> {
+    T::identifier(name)
+        .ok_or_else(|| CodegenError::InvalidIdentifier(name.to_owned()))
+}
+
+/// Whether a name is made of ASCII letters, digits and underscores, not starting with a digit.
+fn is_ascii_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|chr| chr.is_ascii_alphabetic() || chr == '_')
+        && chars.all(|chr| chr.is_ascii_alphanumeric() || chr == '_')
+}
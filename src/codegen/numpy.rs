@@ -0,0 +1,160 @@
+/* Crate imports */
+use super::{is_ascii_identifier, Target};
+use crate::token::{Function, Operator};
+
+/// Names that can't be used as identifiers: the Python keywords,
+/// and the modules used by the generated code.
+const RESERVED: [&str; 37] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break",
+    "class", "continue", "def", "del", "elif", "else", "except", "finally",
+    "for", "from", "global", "if", "import", "in", "is", "lambda", "nonlocal",
+    "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+    "np", "scipy",
+];
+
+/// Python target, vectorised with `numpy`, see [`Xprs::to_numpy`](crate::Xprs::to_numpy).
+pub struct NumPy;
+
+impl Target for NumPy {
+    fn binary(op: Operator, lhs: String, rhs: String) -> Option<String> {
+        match op {
+            Operator::Plus
+            | Operator::Minus
+            | Operator::Times
+            | Operator::Divide => Some(format!("({lhs} {op} {rhs})")),
+            Operator::IntDivide => Some(format!("np.trunc({lhs} / {rhs})")),
+            Operator::Power => Some(format!("np.power({lhs}, {rhs})")),
+            // `np.fmod` takes the sign of `lhs`, like `%`
+            Operator::Modulo => Some(format!("np.fmod({lhs}, {rhs})")),
+            // the bitwise operators result in `NaN` for non-integers
+            Operator::BitAnd
+            | Operator::BitOr
+            | Operator::BitXor
+            | Operator::ShiftLeft
+            | Operator::ShiftRight
+            | Operator::Factorial => None,
+        }
+    }
+
+    #[allow(clippy::pattern_type_mismatch)]
+    fn built_in(name: &str, args: Vec<String>) -> Option<String> {
+        let code = match (name, args.as_slice()) {
+            ("ln", [arg]) => format!("np.log({arg})"),
+            ("log", [arg]) => format!("np.log10({arg})"),
+            ("logn", [arg, base]) => {
+                format!("(np.log({arg}) / np.log({base}))")
+            },
+            // `asin` is `np.arcsin`
+            ("asin" | "acos" | "atan" | "asinh" | "acosh" | "atanh", _) => {
+                let func = name.trim_start_matches('a');
+                format!("np.arc{func}({})", args.join(", "))
+            },
+            ("atan2", [lhs, rhs]) => format!("np.arctan2({lhs}, {rhs})"),
+            ("recip", [arg]) => format!("(1.0 / {arg})"),
+            ("fract", [arg]) => format!("({arg} - np.trunc({arg}))"),
+            // `np.round` rounds half to even
+            ("round", [arg, digits]) if digits == "0.0" => {
+                format!("np.copysign(np.floor(np.abs({arg}) + 0.5), {arg})")
+            },
+            // the number is returned as is when scaling it overflows
+            ("round", [arg, digits]) => {
+                let factor = format!("np.power(10.0, np.trunc({digits}))");
+                format!(
+                    "np.where(np.isfinite({factor}) & np.isfinite({arg} * {factor}), np.copysign(np.floor(np.abs({arg} * {factor}) + 0.5), {arg}) / {factor}, {arg})"
+                )
+            },
+            ("gamma", [arg]) => format!("scipy.special.gamma({arg})"),
+            ("factorial", [arg]) => factorial(arg),
+            ("sum", []) => "0.0".to_owned(),
+            ("sum", _) => format!("({})", args.join(" + ")),
+            // the mean of no numbers is `NaN`, like when evaluated
+            ("mean", []) => "np.nan".to_owned(),
+            ("mean", _) => {
+                format!("(({}) / {}.0)", args.join(" + "), args.len())
+            },
+            // `fmin` and `fmax` ignore `NaN`s, like `f64::min` and `f64::max`
+            ("min", _) => args.iter().fold("np.inf".to_owned(), |acc, arg| {
+                format!("np.fmin({acc}, {arg})")
+            }),
+            ("max", _) => {
+                args.iter().fold("(-np.inf)".to_owned(), |acc, arg| {
+                    format!("np.fmax({acc}, {arg})")
+                })
+            },
+            (
+                "sin" | "sinh" | "cos" | "cosh" | "tan" | "tanh" | "sqrt"
+                | "cbrt" | "exp" | "abs" | "floor" | "ceil" | "trunc" | "hypot",
+                _,
+            ) => format!("np.{name}({})", args.join(", ")),
+            // `rand` and the functions with unexpected arguments
+            _ => return None,
+        };
+        Some(code)
+    }
+
+    fn external(
+        path: &[String],
+        _func: &Function,
+        args: Vec<String>,
+    ) -> Option<String> {
+        // namespaces are attributes, variadic functions take their arguments as `*args`
+        Some(format!("{}({})", path.join("."), args.join(", ")))
+    }
+
+    fn function(name: &str, params: &[(String, bool)], body: &str) -> String {
+        let names = params
+            .iter()
+            .map(|param| param.0.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("def {name}({names}):\n    return {body}\n")
+    }
+
+    fn identifier(name: &str) -> Option<String> {
+        (is_ascii_identifier(name) && !RESERVED.contains(&name))
+            .then(|| name.to_owned())
+    }
+
+    fn number(num: f64) -> String {
+        if num.is_nan() {
+            "np.nan".to_owned()
+        } else if num == f64::INFINITY {
+            "np.inf".to_owned()
+        } else if num == f64::NEG_INFINITY {
+            "(-np.inf)".to_owned()
+        } else if num.is_sign_negative() {
+            format!("({num:?})")
+        } else {
+            // `Debug` keeps a decimal point or an exponent, and all the digits
+            format!("{num:?}")
+        }
+    }
+
+    fn unary(op: Operator, operand: String) -> Option<String> {
+        let code = match op {
+            Operator::Minus => format!("(-{operand})"),
+            Operator::Factorial => factorial(&operand),
+            // the other operators aren't unary
+            Operator::Plus
+            | Operator::Times
+            | Operator::Divide
+            | Operator::IntDivide
+            | Operator::Power
+            | Operator::Modulo
+            | Operator::BitAnd
+            | Operator::BitOr
+            | Operator::BitXor
+            | Operator::ShiftLeft
+            | Operator::ShiftRight => operand,
+        };
+        Some(code)
+    }
+}
+
+/// Vectorised factorial, `NaN` for negative and non-integer numbers like when evaluated
+/// (`scipy.special.factorial` is `0` for negative numbers and the gamma function for the others).
+fn factorial(operand: &str) -> String {
+    format!(
+        "np.where(({operand} >= 0.0) & ({operand} == np.trunc({operand})), scipy.special.factorial({operand}), np.nan)"
+    )
+}
@@ -0,0 +1,72 @@
+//! Runtime helpers called by the Rust code generated with [`Xprs::to_rust`](crate::Xprs::to_rust).
+//!
+//! The `xprs!` macro of the `xprs-macros` crate calls them too,
+//! for the operations that don't map to an [`f64`] method.
+//! They behave like the evaluation of the expression does.
+
+/* Crate imports */
+use crate::utils::{bitwise, built_in_functions, factorial, random};
+
+/// Factorial of a number (the `!` operator and the `factorial` function),
+/// `NaN` if it's negative or not an integer.
+#[inline]
+#[must_use]
+pub fn factorial(num: f64) -> f64 {
+    factorial::factorial(num)
+}
+
+/// Gamma function (the `gamma` function).
+#[inline]
+#[must_use]
+pub fn gamma(num: f64) -> f64 {
+    factorial::gamma(num)
+}
+
+/// Rounds a number to the given number of decimal digits (the `round` function).
+#[inline]
+#[must_use]
+pub fn round(num: f64, digits: f64) -> f64 {
+    built_in_functions::round(num, digits)
+}
+
+/// Pseudo-random number in `[0, 1)` (the `rand` function).
+#[inline]
+#[must_use]
+pub fn rand() -> f64 {
+    random::random()
+}
+
+/// Bitwise AND of two integers (the `&` operator), `NaN` if an operand isn't an integer.
+#[inline]
+#[must_use]
+pub fn bit_and(lhs: f64, rhs: f64) -> f64 {
+    bitwise::bit_and(lhs, rhs)
+}
+
+/// Bitwise OR of two integers (the `|` operator), `NaN` if an operand isn't an integer.
+#[inline]
+#[must_use]
+pub fn bit_or(lhs: f64, rhs: f64) -> f64 {
+    bitwise::bit_or(lhs, rhs)
+}
+
+/// Bitwise XOR of two integers (the `xor` operator), `NaN` if an operand isn't an integer.
+#[inline]
+#[must_use]
+pub fn bit_xor(lhs: f64, rhs: f64) -> f64 {
+    bitwise::bit_xor(lhs, rhs)
+}
+
+/// Left shift of an integer (the `<<` operator), `NaN` if an operand isn't an integer or on overflow.
+#[inline]
+#[must_use]
+pub fn shift_left(lhs: f64, rhs: f64) -> f64 {
+    bitwise::shift_left(lhs, rhs)
+}
+
+/// Right shift of an integer (the `>>` operator), `NaN` if an operand isn't an integer or on overflow.
+#[inline]
+#[must_use]
+pub fn shift_right(lhs: f64, rhs: f64) -> f64 {
+    bitwise::shift_right(lhs, rhs)
+}
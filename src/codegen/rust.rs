@@ -0,0 +1,154 @@
+/* Crate imports */
+use super::{is_ascii_identifier, Target};
+use crate::token::{Function, Operator};
+
+/// Path of the runtime helpers in the generated code.
+const RUNTIME: &str = "::xprs::runtime";
+
+/// Keywords that must be written as raw identifiers (`r#match`).
+const KEYWORDS: [&str; 49] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const",
+    "continue", "do", "dyn", "else", "enum", "extern", "false", "final", "fn",
+    "for", "gen", "if", "impl", "in", "let", "loop", "macro", "match", "mod",
+    "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized",
+    "use", "virtual", "where", "while", "yield", "union",
+];
+
+/// Rust target, see [`Xprs::to_rust`](crate::Xprs::to_rust).
+pub struct Rust;
+
+impl Target for Rust {
+    fn binary(op: Operator, lhs: String, rhs: String) -> Option<String> {
+        let code = match op {
+            Operator::Plus
+            | Operator::Minus
+            | Operator::Times
+            | Operator::Divide
+            | Operator::Modulo => format!("({lhs} {op} {rhs})"),
+            Operator::IntDivide => format!("({lhs} / {rhs}).trunc()"),
+            Operator::Power => format!("{lhs}.powf({rhs})"),
+            Operator::BitAnd => format!("{RUNTIME}::bit_and({lhs}, {rhs})"),
+            Operator::BitOr => format!("{RUNTIME}::bit_or({lhs}, {rhs})"),
+            Operator::BitXor => format!("{RUNTIME}::bit_xor({lhs}, {rhs})"),
+            Operator::ShiftLeft => {
+                format!("{RUNTIME}::shift_left({lhs}, {rhs})")
+            },
+            Operator::ShiftRight => {
+                format!("{RUNTIME}::shift_right({lhs}, {rhs})")
+            },
+            // isn't binary
+            Operator::Factorial => format!("{RUNTIME}::factorial({lhs})"),
+        };
+        Some(code)
+    }
+
+    #[allow(clippy::pattern_type_mismatch)]
+    fn built_in(name: &str, args: Vec<String>) -> Option<String> {
+        let code = match (name, args.as_slice()) {
+            ("log", [arg]) => format!("{arg}.log10()"),
+            ("logn", [arg, base]) => format!("{arg}.log({base})"),
+            ("round", [arg, digits]) if digits == "0.0_f64" => {
+                format!("{arg}.round()")
+            },
+            ("round" | "factorial" | "gamma" | "rand", _) => {
+                format!("{RUNTIME}::{name}({})", args.join(", "))
+            },
+            ("sum", []) => "0.0_f64".to_owned(),
+            ("sum", _) => format!("({})", args.join(" + ")),
+            // the mean of no numbers is `NaN`, like when evaluated
+            ("mean", []) => "f64::NAN".to_owned(),
+            ("mean", _) => {
+                format!("(({}) / {}.0_f64)", args.join(" + "), args.len())
+            },
+            ("min", _) => {
+                args.iter().fold("f64::INFINITY".to_owned(), |acc, arg| {
+                    format!("{acc}.min({arg})")
+                })
+            },
+            ("max", _) => args
+                .iter()
+                .fold("f64::NEG_INFINITY".to_owned(), |acc, arg| {
+                    format!("{acc}.max({arg})")
+                }),
+            // `f64` methods named like the built-ins
+            (_, [arg, rest @ ..]) => {
+                format!("{arg}.{name}({})", rest.join(", "))
+            },
+            (_, []) => format!("{name}()"),
+        };
+        Some(code)
+    }
+
+    fn external(
+        path: &[String],
+        func: &Function,
+        args: Vec<String>,
+    ) -> Option<String> {
+        let joined = args.join(", ");
+        let fn_path = path.join("::");
+        let code = if func.arity.max == Some(func.arity.min) {
+            format!("{fn_path}({joined})")
+        } else {
+            // variadic functions take a slice, like `xprs_fn!` ones
+            format!("{fn_path}(&[{joined}])")
+        };
+        Some(code)
+    }
+
+    fn function(name: &str, params: &[(String, bool)], body: &str) -> String {
+        let typed_params = params
+            .iter()
+            .map(|param| format!("{}: f64", param.0))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "#[allow(unused_parens, unused_variables)]\npub fn {name}({typed_params}) -> f64 {{\n    {body}\n}}\n"
+        )
+    }
+
+    fn identifier(name: &str) -> Option<String> {
+        match name {
+            // can't be raw identifiers
+            "_" | "self" | "Self" | "super" | "crate" => None,
+            _ if !is_ascii_identifier(name) => None,
+            _ if KEYWORDS.contains(&name) => Some(format!("r#{name}")),
+            _ => Some(name.to_owned()),
+        }
+    }
+
+    fn number(num: f64) -> String {
+        if num.is_nan() {
+            "f64::NAN".to_owned()
+        } else if num == f64::INFINITY {
+            "f64::INFINITY".to_owned()
+        } else if num == f64::NEG_INFINITY {
+            "f64::NEG_INFINITY".to_owned()
+        } else if num.is_sign_negative() {
+            format!("({num:?}_f64)")
+        } else {
+            // `Debug` keeps a decimal point or an exponent, and all the digits
+            format!("{num:?}_f64")
+        }
+    }
+
+    fn unary(op: Operator, operand: String) -> Option<String> {
+        let code = match op {
+            Operator::Minus => format!("(-{operand})"),
+            Operator::Factorial => format!("{RUNTIME}::factorial({operand})"),
+            // `+x` isn't valid Rust, it's `x` (the other operators aren't unary)
+            Operator::Plus
+            | Operator::Times
+            | Operator::Divide
+            | Operator::IntDivide
+            | Operator::Power
+            | Operator::Modulo
+            | Operator::BitAnd
+            | Operator::BitOr
+            | Operator::BitXor
+            | Operator::ShiftLeft
+            | Operator::ShiftRight => operand,
+        };
+        Some(code)
+    }
+}
@@ -1,7 +1,93 @@
 /* Built-in imports */
+extern crate alloc;
+#[cfg(feature = "serde")]
+use alloc::collections::BTreeMap;
+use alloc::{borrow::Cow, collections::BTreeSet, sync::Arc};
 use std::collections::{HashMap, HashSet};
 /* Crate imports */
-use crate::token::Function;
+#[cfg(any(feature = "stats", feature = "finance", feature = "physics"))]
+use crate::packs;
+#[cfg(feature = "serde")]
+use crate::serialization::{ContextRepr, ContextSeed, Number};
+use crate::{
+    parser::{ErrorKind, ParseError},
+    token::{Function, Identifier},
+    utils::{built_in_constants, hidden_macros::yeet, suggestions},
+    Parser,
+};
+
+/// Macro for building a [`Context`] declaratively.
+///
+/// Entries are separated by commas and can be:
+/// - a variable: `x = 1.0`,
+/// - a Rust function and its number of arguments: `g = fn_pointer/2`,
+/// - a function with an expression body: `f(a, b) = a * b + 1`,
+///   the body can use the previously declared variables and functions,
+/// - the expected variables: `expect [z, w]`.
+///
+/// Names, arities and bodies tokens are checked at compile time,
+/// the bodies themselves are parsed when the context is built (and panic if invalid,
+/// see [`Context::try_inline_fn`] to handle the errors).
+///
+/// Note: since `name = path/2` declares a function,
+/// wrap a constant division in parentheses: `x = (PI / 2.0)`.
+///
+/// # Example
+///
+/// ```
+/// use xprs::{context, Parser};
+///
+/// fn hypot(x: f64, y: f64) -> f64 {
+///     x.hypot(y)
+/// }
+///
+/// let ctx = context! {
+///     x = 1.0,
+///     y = 2.0,
+///     f(a, b) = a * b + 1,
+///     g = hypot/2,
+///     expect [z, w],
+/// };
+///
+/// let xprs = Parser::new_with_ctx(ctx).parse("f(x, y) + g(z, w)")?;
+/// assert_eq!(xprs.eval(&[("z", 3.0), ("w", 4.0)].into()), Ok(8.0));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[macro_export]
+macro_rules! context {
+    //// entries munching ////
+    (@entries $ctx:ident;) => {};
+    (@entries $ctx:ident; expect [$($var:ident),* $(,)?] $(, $($rest:tt)*)?) => {
+        $ctx.set_expected_vars([$(stringify!($var)),*].into());
+        $crate::context!(@entries $ctx; $($($rest)*)?);
+    };
+    (@entries $ctx:ident; $name:ident($($param:ident),* $(,)?) = $body:expr $(, $($rest:tt)*)?) => {
+        let func = $ctx.inline_fn(
+            stringify!($name),
+            &[$(stringify!($param)),*],
+            stringify!($body),
+        );
+        $ctx.set_fn(func).ok();
+        $crate::context!(@entries $ctx; $($($rest)*)?);
+    };
+    (@entries $ctx:ident; $name:ident = $($function:ident)::+ / $nb_args:tt $(, $($rest:tt)*)?) => {
+        $ctx.set_fn($crate::xprs_fn!(stringify!($name), $($function)::+, $nb_args)).ok();
+        $crate::context!(@entries $ctx; $($($rest)*)?);
+    };
+    (@entries $ctx:ident; $name:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $ctx.set_var(stringify!($name), $value).ok();
+        $crate::context!(@entries $ctx; $($($rest)*)?);
+    };
+
+    ($($entries:tt)*) => {{
+        // an empty context is never mutated,
+        // a new context has no parent so its symbols can always be set
+        #[allow(unused_mut)]
+        let mut ctx = $crate::Context::default();
+        $crate::context!(@entries ctx; $($entries)*);
+        ctx
+    }};
+}
 
 /// Represents a symbol in the context.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
@@ -27,6 +113,15 @@ impl From<Function> for Symbol {
     }
 }
 
+/// Represents an error that occurs when setting the symbols of a [`Context`].
+#[derive(Debug, Eq, PartialEq, thiserror::Error, Clone, Hash)]
+#[non_exhaustive]
+pub enum ContextError {
+    /// A symbol would silently shadow the one defined by a parent context.
+    #[error("Context error: `{0}` is defined by a parent context, use `Context::shadow` to shadow it")]
+    ImplicitShadowing(String),
+}
+
 /// Represents the context for the mathematical expression parser.
 ///
 /// # Examples
@@ -37,9 +132,9 @@ impl From<Function> for Symbol {
 /// let sin_xprs_func = xprs_fn!("sin", f64::sin, 1);
 /// let mut context = Context::default()
 ///     .with_expected_vars(["y"].into())
-///     .with_var("x", 42.0)
+///     .with_var("x", 42.0)?
 ///     // clone because assert_eq! is used later
-///     .with_fn(sin_xprs_func.clone());
+///     .with_fn(sin_xprs_func.clone())?;
 ///
 /// let x_var = context.get("x");
 /// assert_eq!(x_var, Some(&Symbol::Variable(42.0)));
@@ -49,52 +144,723 @@ impl From<Function> for Symbol {
 ///
 /// let expected_vars = context.get_expected_vars();
 /// assert_eq!(expected_vars, Some(&["y"].into()));
+/// # Ok::<(), xprs::ContextError>(())
+/// ```
+///
+/// Contexts can be layered: a child context references a shared parent,
+/// lookups fall through to the parent when a symbol isn't defined in the child.
+/// Redefining a parent's symbol in the child has to be explicit with [`Context::shadow`] (or [`Context::with_shadow`]),
+/// the parent is never modified.
+///
+/// ```
+/// use std::sync::Arc;
+/// use xprs::{Context, Parser, Symbol};
+///
+/// let base = Arc::new(Context::default().with_var("x", 1.0)?.with_var("y", 2.0)?);
+/// // cheap per-request layer
+/// let request = Context::default().with_parent(Arc::clone(&base))?.with_shadow("y", 3.0);
+///
+/// assert_eq!(request.get("x"), Some(&Symbol::Variable(1.0)));
+/// assert!(request.shadows("y"));
+///
+/// let xprs = Parser::new_with_ctx(request).parse("x + y")?;
+/// assert_eq!(xprs.eval_no_vars(), Ok(4.0));
+/// assert_eq!(base.get("y"), Some(&Symbol::Variable(2.0)));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// Symbols can also be registered under namespaces (which are contexts themselves)
+/// and used with their qualified name (`math.sin`).
+/// Importing a namespace makes its symbols usable without qualification.
+///
+/// ```
+/// use xprs::{Context, Parser, xprs_fn};
+///
+/// let phys = Context::default().with_var("c", 299_792_458.0)?;
+/// let math = Context::default().with_fn(xprs_fn!("double", |x| 2.0 * x, 1))?;
+/// let context = Context::default()
+///     .with_namespace("phys", phys)
+///     .with_namespace("math", math)
+///     .with_import("math");
+///
+/// let xprs = Parser::new_with_ctx(context).parse("math.double(phys.c) - double(phys.c)")?;
+/// assert_eq!(xprs.eval_no_vars(), Ok(0.0));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 #[derive(Debug, Default, PartialEq, Clone)]
 pub struct Context<'names> {
-    /// The symbols that are available in the context.
-    symbols: HashMap<&'names str, Symbol>,
+    /// The only permitted built-in functions, inherited from the parent if unset.
+    allowed_functions: Option<HashSet<&'names str>>,
+    /// Whether the built-in constants and functions are available, inherited from the parent if unset.
+    built_ins: Option<bool>,
+    /// Whether constants are looked up case-insensitively, inherited from the parent if unset.
+    case_insensitive_constants: Option<bool>,
+    /// The constants added to the built-in ones.
+    constants: HashMap<&'names str, f64>,
+    /// The forbidden built-in functions (in addition to the parent's ones).
+    denied_functions: HashSet<&'names str>,
     /// Optional set of expected variables.
     expected_vars: Option<HashSet<&'names str>>,
+    /// The hidden constants (built-in or added by a parent).
+    hidden_constants: HashSet<&'names str>,
+    /// The namespaces whose symbols can be used without qualification.
+    imports: Vec<&'names str>,
+    /// The namespaces of the context, by name.
+    namespaces: HashMap<&'names str, Self>,
+    /// Optional parent context, lookups fall through to it.
+    parent: Option<Arc<Self>>,
+    /// The names explicitly shadowed, see [`Context::shadow`].
+    shadowed: HashSet<&'names str>,
+    /// The symbols that are available in the context.
+    /// Keys are owned for functions with an owned name.
+    symbols: HashMap<Cow<'names, str>, Symbol>,
 }
 
 impl<'names> Context<'names> {
-    /// Sets the value of a variable in the context.
+    /// Returns the value of a constant added to the context or to its parents.
+    fn added_constant(
+        &self,
+        name: &str,
+        case_insensitive: bool,
+    ) -> Option<f64> {
+        let constant = self.constants.get(name).copied().or_else(|| {
+            case_insensitive.then(|| {
+                self.constants
+                    .iter()
+                    .find(|constant| constant.0.eq_ignore_ascii_case(name))
+                    .map(|constant| *constant.1)
+            })?
+        });
+        constant.or_else(|| {
+            self.parent.as_ref()?.added_constant(name, case_insensitive)
+        })
+    }
+
+    /// The allow-list of built-in functions of the context or of its nearest parent.
+    fn allowed_functions(&self) -> Option<&HashSet<&str>> {
+        self.allowed_functions
+            .as_ref()
+            .or_else(|| self.parent.as_ref()?.allowed_functions())
+    }
+
+    /// Whether the built-in constants and functions are available.
     #[inline]
-    pub fn set_var<T: Into<f64>>(&mut self, name: &'names str, value: T) {
-        self.symbols.insert(name, value.into().into());
+    #[must_use]
+    pub fn built_ins_enabled(&self) -> bool {
+        self.built_ins.unwrap_or_else(|| {
+            self.parent
+                .as_ref()
+                .map_or(true, |parent| parent.built_ins_enabled())
+        })
     }
 
-    /// Sets the value of a variable in the context, returning the context.
+    /// Whether constants are looked up case-insensitively.
     #[inline]
     #[must_use]
-    pub fn with_var<T: Into<f64>>(
-        mut self,
+    pub fn case_insensitive_constants(&self) -> bool {
+        self.case_insensitive_constants.unwrap_or_else(|| {
+            self.parent
+                .as_ref()
+                .is_some_and(|parent| parent.case_insensitive_constants())
+        })
+    }
+
+    /// Returns an error if a parent defines the name and the context doesn't explicitly shadow it,
+    /// a symbol of the context would silently shadow it.
+    fn check_shadowing(&self, name: &str) -> Result<(), ContextError> {
+        let implicit = !self.shadowed.contains(name)
+            && self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.get(name).is_some());
+        if implicit {
+            yeet!(ContextError::ImplicitShadowing(name.to_owned()));
+        }
+        Ok(())
+    }
+
+    /// Returns an error for the first symbol of the context (by name)
+    /// that would silently shadow a symbol of a parent, see [`Context::check_shadowing`].
+    fn check_symbols_shadowing(&self) -> Result<(), ContextError> {
+        let implicit = self
+            .symbols
+            .keys()
+            .filter(|name| self.check_shadowing(name).is_err())
+            .min();
+        if let Some(name) = implicit {
+            yeet!(ContextError::ImplicitShadowing(name.clone().into_owned()));
+        }
+        Ok(())
+    }
+
+    /// Defines a symbol, returning the one it overrides (defined in the context or by one of its parents).
+    fn define(
+        &mut self,
+        name: Cow<'names, str>,
+        symbol: Symbol,
+    ) -> Option<Symbol> {
+        let shadowed = self
+            .parent
+            .as_ref()
+            .and_then(|parent| parent.get(&name))
+            .cloned();
+        self.symbols.insert(name, symbol).or(shadowed)
+    }
+
+    /// Whether a name is defined by the context (or by its namespaces and parents) or is a built-in,
+    /// so it can't be a variable.
+    pub(crate) fn defines(&self, name: &str) -> bool {
+        !matches!(self.lookup(name), Ok(None))
+            || !matches!(
+                Identifier::from_str(name, self),
+                Identifier::Variable(_)
+            )
+    }
+
+    /// Returns a function of the context (or of its namespaces and parents) or a permitted built-in one, by name.
+    pub(crate) fn find_function(&self, name: &str) -> Option<Function> {
+        match self.lookup(name).ok()?.map(Cow::into_owned) {
+            // qualified functions keep their qualified name, as when parsing
+            Some(Symbol::Function(func)) if name.contains('.') => {
+                Some(func.with_name(name.to_owned()))
+            },
+            Some(Symbol::Function(func)) => Some(func),
+            Some(Symbol::Variable(_)) => None,
+            None => {
+                if let Identifier::Function(func) =
+                    Identifier::from_str(name, self)
+                {
+                    self.is_built_in_function_permitted(&func.name)
+                        .then_some(func)
+                } else {
+                    None
+                }
+            },
+        }
+    }
+
+    /// Creates a context from its serialized form, resolving the functions against `registry`.
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_repr(
+        repr: ContextRepr<'names>,
+        registry: &Context,
+    ) -> Result<Self, String> {
+        let mut symbols = HashMap::new();
+        for (name, value) in repr.variables {
+            symbols.insert(name, Symbol::Variable(value.0));
+        }
+        for (name, func_name) in repr.functions {
+            let func = registry
+                .find_function(&func_name)
+                .ok_or_else(|| format!("unknown function `{func_name}`"))?;
+            symbols.insert(name, Symbol::Function(func));
+        }
+        let namespaces = repr
+            .namespaces
+            .into_iter()
+            .map(|(name, namespace)| {
+                Ok((name, Self::from_repr(namespace, registry)?))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self {
+            symbols,
+            expected_vars: repr
+                .expected_vars
+                .map(|vars| vars.into_iter().collect()),
+            parent: None,
+            shadowed: repr.shadowed.into_iter().collect(),
+            namespaces,
+            imports: repr.imports,
+            constants: repr
+                .constants
+                .into_iter()
+                .map(|(name, value)| (name, value.0))
+                .collect(),
+            hidden_constants: repr.hidden_constants.into_iter().collect(),
+            case_insensitive_constants: repr.case_insensitive_constants,
+            built_ins: repr.built_ins,
+            allowed_functions: repr
+                .allowed_functions
+                .map(|names| names.into_iter().collect()),
+            denied_functions: repr.denied_functions.into_iter().collect(),
+        })
+    }
+
+    /// Returns the value of a symbol in the context, or in its parents.
+    /// Qualified names (`namespace.name`) are looked up in the namespaces.
+    /// Returns [`None`] if the symbol is unknown or ambiguous,
+    /// constants of namespaces are not symbols and are only resolved when parsing.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Symbol> {
+        match self.lookup(name) {
+            Ok(Some(Cow::Borrowed(symbol))) => Some(symbol),
+            Ok(Some(Cow::Owned(_)) | None) | Err(_) => None,
+        }
+    }
+
+    /// Returns the value of a constant, either added to the context (or to its parents) or built-in.
+    /// Returns [`None`] if the constant is unknown or hidden.
+    #[inline]
+    #[must_use]
+    pub fn get_constant(&self, name: &str) -> Option<f64> {
+        let case_insensitive = self.case_insensitive_constants();
+        if self.is_constant_hidden(name, case_insensitive) {
+            return None;
+        }
+        self.added_constant(name, case_insensitive).or_else(|| {
+            self.built_ins_enabled()
+                .then(|| built_in_constants::get(name, case_insensitive))?
+        })
+    }
+
+    /// Retrieves the set of expected variables from the context, or from its parents.
+    #[inline]
+    #[must_use]
+    pub fn get_expected_vars(&self) -> Option<&HashSet<&str>> {
+        self.expected_vars
+            .as_ref()
+            .or_else(|| self.parent.as_ref()?.get_expected_vars())
+    }
+
+    /// Returns the value of a symbol defined in the context itself, ignoring its parents.
+    #[inline]
+    #[must_use]
+    pub fn get_local(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.get(name)
+    }
+
+    /// Returns a namespace of the context, or of its parents.
+    #[inline]
+    #[must_use]
+    pub fn get_namespace(&self, name: &str) -> Option<&Self> {
+        self.namespaces
+            .get(name)
+            .or_else(|| self.parent.as_ref()?.get_namespace(name))
+    }
+
+    /// Hides a constant (built-in or added by a parent), its name is then treated as a variable.
+    #[inline]
+    pub fn hide_constant(&mut self, name: &'names str) {
+        self.hidden_constants.insert(name);
+    }
+
+    /// Imports a namespace, its symbols can then be used without qualification.
+    /// Symbols of the context itself take precedence over the imported ones,
+    /// a symbol defined by several imported namespaces must be qualified.
+    /// Importing an unknown namespace has no effect.
+    #[inline]
+    pub fn import(&mut self, namespace: &'names str) {
+        self.imports.push(namespace);
+    }
+
+    /// Whether a built-in function is permitted by the allow-list and the deny-lists
+    /// of the context and its parents.
+    /// Aliases are checked by the function's name (`invert` is `recip`).
+    #[inline]
+    #[must_use]
+    pub fn is_built_in_function_permitted(&self, name: &str) -> bool {
+        self.allowed_functions()
+            .map_or(true, |allowed| allowed.contains(name))
+            && !self.is_function_denied(name)
+    }
+
+    /// Whether a constant is hidden by the context or by its parents.
+    fn is_constant_hidden(&self, name: &str, case_insensitive: bool) -> bool {
+        self.hidden_constants.iter().any(|&hidden| {
+            if case_insensitive {
+                hidden.eq_ignore_ascii_case(name)
+            } else {
+                hidden == name
+            }
+        }) || self.parent.as_ref().is_some_and(|parent| {
+            parent.is_constant_hidden(name, case_insensitive)
+        })
+    }
+
+    /// Whether a built-in function is denied by the context or by its parents.
+    fn is_function_denied(&self, name: &str) -> bool {
+        self.denied_functions.contains(name)
+            || self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.is_function_denied(name))
+    }
+
+    /// Looks up a symbol in the context, or in its parents.
+    /// Qualified names (`namespace.name`) must refer to an existing symbol
+    /// or to a constant of the namespace, unknown unqualified names return `Ok(None)`.
+    pub(crate) fn lookup(
+        &self,
+        name: &str,
+    ) -> Result<Option<Cow<'_, Symbol>>, ErrorKind> {
+        match name.split_once('.') {
+            Some((namespace, member)) => {
+                self.lookup_member("", namespace, member).map(Some)
+            },
+            None => self.lookup_unqualified(name),
+        }
+    }
+
+    /// Looks up `member` in the namespace `namespace`, `prefix` being the path of the context.
+    fn lookup_member(
+        &self,
+        prefix: &str,
+        namespace: &str,
+        member: &str,
+    ) -> Result<Cow<'_, Symbol>, ErrorKind> {
+        let path = format!("{prefix}{namespace}");
+        let Some(ctx) = self.get_namespace(namespace) else {
+            let names = self.namespace_names();
+            let suggestions = suggestions::closest(namespace, names)
+                .into_iter()
+                .map(|name| format!("{prefix}{name}"));
+            return Err(ErrorKind::UnknownNamespace(
+                path,
+                suggestions.collect(),
+            ));
+        };
+        if let Some((inner, inner_member)) = member.split_once('.') {
+            return ctx.lookup_member(&format!("{path}."), inner, inner_member);
+        }
+        let found = ctx.lookup_unqualified(member)?;
+        found.or_else(|| ctx.namespace_constant(member)).ok_or_else(
+            #[cold]
+            || {
+                let names = ctx.symbol_names();
+                let suggestions = suggestions::closest(member, names)
+                    .into_iter()
+                    .map(|name| format!("{path}.{name}"));
+                ErrorKind::UnknownNamespaceMember(
+                    format!("{path}.{member}"),
+                    suggestions.collect(),
+                )
+            },
+        )
+    }
+
+    /// Looks up an unqualified name in the context, its imported namespaces and its parents.
+    fn lookup_unqualified(
+        &self,
+        name: &str,
+    ) -> Result<Option<Cow<'_, Symbol>>, ErrorKind> {
+        if let Some(symbol) = self.symbols.get(name) {
+            return Ok(Some(Cow::Borrowed(symbol)));
+        }
+        let mut imported = self.imports.iter().filter_map(|&namespace| {
+            let ctx = self.get_namespace(namespace)?;
+            let symbol = ctx
+                .lookup_unqualified(name)
+                .ok()
+                .flatten()
+                .or_else(|| ctx.namespace_constant(name))?;
+            Some((namespace, symbol))
+        });
+        match (imported.next(), imported.next()) {
+            (Some((_, symbol)), None) => return Ok(Some(symbol)),
+            (Some(first), Some(second)) => {
+                let candidates = [first, second]
+                    .into_iter()
+                    .chain(imported)
+                    .map(|(namespace, _)| format!("{namespace}.{name}"));
+                return Err(ErrorKind::AmbiguousIdentifier(
+                    name.to_owned(),
+                    candidates.collect(),
+                ));
+            },
+            (None, _) => {},
+        }
+        self.parent
+            .as_ref()
+            .map_or(Ok(None), |parent| parent.lookup_unqualified(name))
+    }
+
+    /// Returns a constant added to a namespace as a symbol, the built-in ones are not members of namespaces.
+    fn namespace_constant(&self, name: &str) -> Option<Cow<'_, Symbol>> {
+        let case_insensitive = self.case_insensitive_constants();
+        if self.is_constant_hidden(name, case_insensitive) {
+            return None;
+        }
+        let value = self.added_constant(name, case_insensitive)?;
+        Some(Cow::Owned(Symbol::Variable(value)))
+    }
+
+    /// The names of the namespaces of the context and its parents, sorted.
+    fn namespace_names(&self) -> BTreeSet<&str> {
+        let mut names = self
+            .parent
+            .as_ref()
+            .map(|parent| parent.namespace_names())
+            .unwrap_or_default();
+        names.extend(self.namespaces.keys().copied());
+        names
+    }
+
+    /// Returns the parent of the context.
+    #[inline]
+    #[must_use]
+    pub const fn parent(&self) -> Option<&Arc<Self>> {
+        self.parent.as_ref()
+    }
+
+    /// Returns a [`DeserializeSeed`](serde::de::DeserializeSeed) resolving the functions referenced by name against the context,
+    /// to deserialize an [`Xprs`](crate::Xprs), its root element or a [`Context`] using custom functions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use serde::de::DeserializeSeed;
+    /// use xprs::{xprs_fn, Context, Parser, Xprs};
+    ///
+    /// let ctx = Context::default().with_fn(xprs_fn!("double", |x| 2.0 * x, 1))?;
+    /// let xprs = Parser::new_with_ctx(ctx.clone()).parse("double(x) + 1")?;
+    /// let json = serde_json::to_string(&xprs)?;
+    ///
+    /// let mut deserializer = serde_json::Deserializer::from_str(&json);
+    /// let restored = ctx.seed::<Xprs>().deserialize(&mut deserializer)?;
+    /// assert_eq!(restored.eval(&[("x", 2.0)].into()), Ok(5.0));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "serde")]
+    #[inline]
+    #[must_use]
+    pub const fn seed<T>(&self) -> ContextSeed<'_, 'names, T> {
+        ContextSeed::new(self)
+    }
+
+    /// Sets the only built-in functions permitted in the context (allow-list),
+    /// using any other built-in function is a parsing error.
+    #[inline]
+    pub fn set_allowed_built_in_functions(
+        &mut self,
+        functions: HashSet<&'names str>,
+    ) {
+        self.allowed_functions = Some(functions);
+    }
+
+    /// Sets whether the built-in constants and functions are available, enabled by default.
+    /// Their names are treated as variables when disabled.
+    #[inline]
+    pub fn set_built_ins(&mut self, enabled: bool) {
+        self.built_ins = Some(enabled);
+    }
+
+    /// Sets whether constants are looked up case-insensitively (`PI`, `Pi` and `pi`), disabled by default.
+    #[inline]
+    pub fn set_case_insensitive_constants(&mut self, case_insensitive: bool) {
+        self.case_insensitive_constants = Some(case_insensitive);
+    }
+
+    /// Adds a constant to the built-in ones.
+    /// Unlike variables of the context, constants can be looked up case-insensitively and hidden.
+    #[inline]
+    pub fn set_constant(&mut self, name: &'names str, value: f64) {
+        self.constants.insert(name, value);
+    }
+
+    /// Sets the built-in functions forbidden in the context (deny-list),
+    /// using one of them is a parsing error.
+    #[inline]
+    pub fn set_denied_built_in_functions(
+        &mut self,
+        functions: HashSet<&'names str>,
+    ) {
+        self.denied_functions = functions;
+    }
+
+    /// Sets the expected variables for the context.
+    #[inline]
+    pub fn set_expected_vars(&mut self, expected_vars: HashSet<&'names str>) {
+        self.expected_vars = Some(expected_vars);
+    }
+
+    /// Sets a function in the context.
+    /// Returns the symbol of the context it overrides.
+    ///
+    /// # Errors
+    ///
+    /// A [`ContextError`] is returned if a parent defines the name, see [`Context::shadow`] to shadow it.
+    #[inline]
+    pub fn set_fn(
+        &mut self,
+        func: Function,
+    ) -> Result<Option<Symbol>, ContextError> {
+        self.check_shadowing(&func.name)?;
+        Ok(self.symbols.insert(func.name.clone(), func.into()))
+    }
+
+    /// Sets a namespace in the context, its symbols are used as `namespace.name`.
+    /// The name of the namespace cannot contain a `.`, namespaces can be nested instead.
+    #[inline]
+    pub fn set_namespace(&mut self, name: &'names str, namespace: Self) {
+        self.namespaces.insert(name, namespace);
+    }
+
+    /// Sets the value of a variable in the context.
+    /// Returns the symbol of the context it overrides.
+    ///
+    /// # Errors
+    ///
+    /// A [`ContextError`] is returned if a parent defines the name, see [`Context::shadow`] to shadow it.
+    #[inline]
+    pub fn set_var<T: Into<f64>>(
+        &mut self,
         name: &'names str,
         value: T,
+    ) -> Result<Option<Symbol>, ContextError> {
+        self.check_shadowing(name)?;
+        Ok(self.symbols.insert(name.into(), value.into().into()))
+    }
+
+    /// Defines a symbol in the context, shadowing the one defined by a parent (which is left unchanged).
+    /// Returns the symbol it overrides, defined in the context or shadowed in one of its parents.
+    /// The name can then be set with [`Context::set_var`] and [`Context::set_fn`],
+    /// even if the parent is attached afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use xprs::{Context, Symbol};
+    ///
+    /// let base = Arc::new(Context::default().with_var("y", 2.0)?);
+    /// let mut request = Context::default().with_parent(Arc::clone(&base))?;
+    ///
+    /// assert!(request.set_var("y", 3.0).is_err());
+    /// assert_eq!(request.shadow("y", 3.0), Some(Symbol::Variable(2.0)));
+    /// assert_eq!(request.get("y"), Some(&Symbol::Variable(3.0)));
+    /// assert_eq!(base.get("y"), Some(&Symbol::Variable(2.0)));
+    /// # Ok::<(), xprs::ContextError>(())
+    /// ```
+    #[inline]
+    pub fn shadow<S: Into<Symbol>>(
+        &mut self,
+        name: &'names str,
+        symbol: S,
+    ) -> Option<Symbol> {
+        self.shadowed.insert(name);
+        self.define(name.into(), symbol.into())
+    }
+
+    /// Whether the context shadows a symbol defined by one of its parents.
+    #[inline]
+    #[must_use]
+    pub fn shadows(&self, name: &str) -> bool {
+        self.symbols.contains_key(name)
+            && self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.get(name).is_some())
+    }
+
+    /// The names of the symbols and added constants of the context and its parents, sorted.
+    fn symbol_names(&self) -> BTreeSet<&str> {
+        let mut names = self
+            .parent
+            .as_ref()
+            .map(|parent| parent.symbol_names())
+            .unwrap_or_default();
+        names.extend(self.symbols.keys().map(AsRef::as_ref));
+        names.extend(self.constants.keys().copied());
+        names
+    }
+
+    /// Returns the serialized form of the context, its parent is not serialized.
+    #[cfg(feature = "serde")]
+    pub(crate) fn to_repr(&self) -> ContextRepr<'_> {
+        let mut variables = BTreeMap::new();
+        let mut functions = BTreeMap::new();
+        self.symbols
+            .iter()
+            .for_each(|(name, symbol)| match *symbol {
+                Symbol::Variable(value) => {
+                    variables
+                        .insert(Cow::Borrowed(name.as_ref()), Number(value));
+                },
+                Symbol::Function(ref func) => {
+                    functions.insert(
+                        Cow::Borrowed(name.as_ref()),
+                        Cow::Borrowed(func.name.as_ref()),
+                    );
+                },
+            });
+        ContextRepr {
+            variables,
+            functions,
+            expected_vars: self
+                .expected_vars
+                .as_ref()
+                .map(|vars| vars.iter().copied().collect()),
+            namespaces: self
+                .namespaces
+                .iter()
+                .map(|(&name, namespace)| (name, namespace.to_repr()))
+                .collect(),
+            imports: self.imports.clone(),
+            constants: self
+                .constants
+                .iter()
+                .map(|(&name, &value)| (name, Number(value)))
+                .collect(),
+            hidden_constants: self.hidden_constants.iter().copied().collect(),
+            shadowed: self.shadowed.iter().copied().collect(),
+            case_insensitive_constants: self.case_insensitive_constants,
+            built_ins: self.built_ins,
+            allowed_functions: self
+                .allowed_functions
+                .as_ref()
+                .map(|names| names.iter().copied().collect()),
+            denied_functions: self.denied_functions.iter().copied().collect(),
+        }
+    }
+
+    /// Sets the only built-in functions permitted in the context, returning the context.
+    #[inline]
+    #[must_use]
+    pub fn with_allowed_built_in_functions(
+        mut self,
+        functions: HashSet<&'names str>,
     ) -> Self {
-        self.symbols.insert(name, value.into().into());
+        self.allowed_functions = Some(functions);
         self
     }
 
-    /// Sets a function in the context.
+    /// Sets whether the built-in constants and functions are available, returning the context.
     #[inline]
-    pub fn set_fn(&mut self, func: Function) {
-        self.symbols.insert(func.name, func.into());
+    #[must_use]
+    pub const fn with_built_ins(mut self, enabled: bool) -> Self {
+        self.built_ins = Some(enabled);
+        self
     }
 
-    /// Sets a function in the context, returning the context.
+    /// Sets whether constants are looked up case-insensitively, returning the context.
     #[inline]
     #[must_use]
-    pub fn with_fn(mut self, func: Function) -> Self {
-        self.symbols.insert(func.name, func.into());
+    pub const fn with_case_insensitive_constants(
+        mut self,
+        case_insensitive: bool,
+    ) -> Self {
+        self.case_insensitive_constants = Some(case_insensitive);
         self
     }
 
-    /// Sets the expected variables for the context.
+    /// Adds a constant to the built-in ones, returning the context.
     #[inline]
-    pub fn set_expected_vars(&mut self, expected_vars: HashSet<&'names str>) {
-        self.expected_vars = Some(expected_vars);
+    #[must_use]
+    pub fn with_constant(mut self, name: &'names str, value: f64) -> Self {
+        self.constants.insert(name, value);
+        self
+    }
+
+    /// Sets the built-in functions forbidden in the context, returning the context.
+    #[inline]
+    #[must_use]
+    pub fn with_denied_built_in_functions(
+        mut self,
+        functions: HashSet<&'names str>,
+    ) -> Self {
+        self.denied_functions = functions;
+        self
     }
 
     /// Sets the expected variables for the context, returning the context.
@@ -108,28 +874,225 @@ impl<'names> Context<'names> {
         self
     }
 
-    /// Sets the symbols for the context.
+    /// Registers the financial functions pack
+    /// (`pv`, `fv`, `pmt`, `nper`, `rate`, `npv`, `irr` and `compound`) under a namespace, returning the context.
+    /// Their names are common variable names, the namespace keeps them available (`fin.pmt(rate, nper, pv)`).
+    #[cfg(feature = "finance")]
     #[inline]
     #[must_use]
-    pub fn with_symbols(
+    pub fn with_finance_functions(mut self, namespace: &'names str) -> Self {
+        let mut functions = Self::default();
+        for func in packs::finance::FUNCTIONS {
+            functions.define(func.name.clone(), func.into());
+        }
+        self.set_namespace(namespace, functions);
+        self
+    }
+
+    /// Sets a function in the context, returning the context.
+    ///
+    /// # Errors
+    ///
+    /// A [`ContextError`] is returned if a parent defines the name, see [`Context::with_shadow`] to shadow it.
+    #[inline]
+    pub fn with_fn(mut self, func: Function) -> Result<Self, ContextError> {
+        self.set_fn(func)?;
+        Ok(self)
+    }
+
+    /// Hides a constant, returning the context.
+    #[inline]
+    #[must_use]
+    pub fn with_hidden_constant(mut self, name: &'names str) -> Self {
+        self.hidden_constants.insert(name);
+        self
+    }
+
+    /// Imports a namespace, returning the context.
+    #[inline]
+    #[must_use]
+    pub fn with_import(mut self, namespace: &'names str) -> Self {
+        self.imports.push(namespace);
+        self
+    }
+
+    /// Sets a namespace in the context, returning the context.
+    #[inline]
+    #[must_use]
+    pub fn with_namespace(
         mut self,
-        symbols: HashMap<&'names str, Symbol>,
+        name: &'names str,
+        namespace: Self,
     ) -> Self {
-        self.symbols = symbols;
+        self.namespaces.insert(name, namespace);
         self
     }
 
-    /// Returns the value of a symbol in the context.
+    /// Sets the parent of the context, returning the context.
+    /// Symbols and expected variables not defined in the context are looked up in the parent.
+    ///
+    /// # Errors
+    ///
+    /// A [`ContextError`] is returned if the parent defines a symbol of the context
+    /// that isn't explicitly shadowed, see [`Context::with_shadow`].
+    #[inline]
+    pub fn with_parent(
+        mut self,
+        parent: Arc<Self>,
+    ) -> Result<Self, ContextError> {
+        self.parent = Some(parent);
+        self.check_symbols_shadowing()?;
+        Ok(self)
+    }
+
+    /// Registers the physical constants pack (CODATA values in SI units, see [`physics`](crate::physics))
+    /// under a namespace, returning the context.
+    /// Its members are constants, folded when parsing like the built-in ones (`phys.c` is `299792458`).
+    #[cfg(feature = "physics")]
     #[inline]
     #[must_use]
-    pub fn get(&self, name: &str) -> Option<&Symbol> {
-        self.symbols.get(name)
+    pub fn with_physical_constants(mut self, namespace: &'names str) -> Self {
+        let mut constants = Self::default();
+        for constant in packs::physics::CONSTANTS {
+            constants.set_constant(constant.name, constant.value);
+        }
+        self.set_namespace(namespace, constants);
+        self
+    }
+
+    /// Defines a symbol in the context, shadowing the one defined by a parent, returning the context.
+    #[inline]
+    #[must_use]
+    pub fn with_shadow<S: Into<Symbol>>(
+        mut self,
+        name: &'names str,
+        symbol: S,
+    ) -> Self {
+        self.shadow(name, symbol);
+        self
     }
 
-    /// Retrieves the set of expected variables from the context.
+    /// Registers the statistical and special functions pack
+    /// (`median`, `var`, `stddev`, `erf`, `erfc`, `beta`, `lgamma`, `binomial`, `normal_pdf`, `normal_cdf`,
+    /// `clamp`, `sign`, `lerp`, `deg`, `rad`, `gcd` and `lcm`), returning the context.
+    ///
+    /// # Errors
+    ///
+    /// A [`ContextError`] is returned if a parent defines one of the functions.
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn with_stats_functions(mut self) -> Result<Self, ContextError> {
+        for func in packs::stats::FUNCTIONS {
+            self.set_fn(func)?;
+        }
+        Ok(self)
+    }
+
+    /// Sets the symbols for the context.
+    ///
+    /// # Errors
+    ///
+    /// A [`ContextError`] is returned if a parent defines one of the names, see [`Context::with_shadow`] to shadow it.
+    #[inline]
+    pub fn with_symbols(
+        mut self,
+        symbols: HashMap<&'names str, Symbol>,
+    ) -> Result<Self, ContextError> {
+        self.symbols = symbols
+            .into_iter()
+            .map(|(name, symbol)| (name.into(), symbol))
+            .collect();
+        self.check_symbols_shadowing()?;
+        Ok(self)
+    }
+
+    /// Sets the value of a variable in the context, returning the context.
+    ///
+    /// # Errors
+    ///
+    /// A [`ContextError`] is returned if a parent defines the name, see [`Context::with_shadow`] to shadow it.
+    #[inline]
+    pub fn with_var<T: Into<f64>>(
+        mut self,
+        name: &'names str,
+        value: T,
+    ) -> Result<Self, ContextError> {
+        self.set_var(name, value)?;
+        Ok(self)
+    }
+}
+
+impl Context<'_> {
+    /// Builds a function from an expression body, used by [`context!`](crate::context!),
+    /// see [`Context::try_inline_fn`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body is not a valid expression,
+    /// if it uses a variable that isn't a parameter
+    /// or if there are more than 255 parameters.
+    #[doc(hidden)]
     #[inline]
     #[must_use]
-    pub const fn get_expected_vars(&self) -> Option<&HashSet<&str>> {
-        self.expected_vars.as_ref()
+    #[allow(clippy::panic)]
+    pub fn inline_fn(
+        &self,
+        name: &'static str,
+        params: &[&str],
+        body: &'static str,
+    ) -> Function {
+        self.try_inline_fn(name, params, body)
+            .unwrap_or_else(|err| panic!("invalid body for `{name}`: {err}"))
+    }
+
+    /// Builds a function from an expression body, like the `f(a, b) = a * b + 1` entries of [`context!`](crate::context!).
+    /// The body is parsed with the context, so it can use its variables and functions.
+    ///
+    /// # Errors
+    ///
+    /// A [`ParseError`] is returned if the body is not a valid expression,
+    /// if it uses a variable that isn't a parameter or if there are more than 255 parameters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::{Context, Parser};
+    ///
+    /// let ctx = Context::default().with_var("k", 3.0)?;
+    /// let scale = ctx.try_inline_fn("scale", &["x"], "k * x")?;
+    /// assert!(ctx.try_inline_fn("scale", &["x"], "k * y").is_err());
+    ///
+    /// let parser = Parser::new_with_ctx(ctx.with_fn(scale)?);
+    /// assert_eq!(parser.parse("scale(2)")?.eval_no_vars(), Ok(6.0));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    pub fn try_inline_fn<N: Into<Cow<'static, str>>>(
+        &self,
+        name: N,
+        params: &[&str],
+        body: &'static str,
+    ) -> Result<Function, ParseError> {
+        let Ok(nb_args) = u8::try_from(params.len()) else {
+            yeet!(ParseError::new_too_many_parameters(body, params.len()));
+        };
+        // the body can only use the parameters as variables
+        let ctx = self
+            .clone()
+            .with_expected_vars(params.iter().copied().collect());
+        let xprs = Parser::new_with_ctx(ctx).parse(body)?;
+        let names: Vec<String> =
+            params.iter().map(|&param| param.to_owned()).collect();
+        // the arity guarantees the number of arguments
+        Ok(Function::new_dyn(
+            name,
+            move |args| {
+                let values =
+                    names.iter().map(String::as_str).zip(args.iter().copied());
+                xprs.eval(&values.collect()).unwrap_or(f64::NAN)
+            },
+            Some(nb_args),
+        )
+        .with_owned_params(params.iter().copied()))
     }
 }
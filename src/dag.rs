@@ -0,0 +1,245 @@
+/* Clippy Config */
+#![allow(clippy::std_instead_of_core)]
+/* Built-in imports */
+extern crate alloc;
+use alloc::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+/* Crate imports */
+use crate::{
+    element::Element,
+    integer::Integer as _,
+    token::{Function, Operator},
+    utils::hidden_macros::trust_me,
+    xprs::{EvalError, Xprs},
+};
+
+/// An [`Xprs`] where structurally equal sub-expressions are shared,
+/// see [`Xprs::to_dag`].
+///
+/// Every node of the directed acyclic graph is evaluated once,
+/// no matter how many times it appears in the expression.
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub struct XprsDag<'a> {
+    /// The unique nodes of the expression in evaluation order,
+    /// operands come before their operator and the root is the last node.
+    nodes: Vec<Node<'a>>,
+    /// The set of variables present in the expression.
+    vars: HashSet<&'a str>,
+}
+
+impl<'a> XprsDag<'a> {
+    /// Evaluates the expression using the provided variable values.
+    /// Returns an [`f64`] if the evaluation is successful, or an [`EvalError`] if an error occurs.
+    ///
+    /// # Errors
+    ///
+    /// An [`EvalError`] is returned if a variable is not provided.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::Xprs;
+    ///
+    /// let dag = Xprs::try_from("sin(x + 2y) * y + sin(2y + x)")?.to_dag();
+    /// assert_eq!(
+    ///     dag.eval(&[("x", 1.0), ("y", 2.0)].into()),
+    ///     Ok(5.0_f64.sin() * 3.0)
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    pub fn eval(
+        &self,
+        variables: &HashMap<&str, f64>,
+    ) -> Result<f64, EvalError> {
+        let mut values: Vec<f64> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            // nodes only refer to previous nodes, which are already evaluated
+            let value_of = |idx: usize| *trust_me!(values.get_unchecked(idx));
+            let value = match *node {
+                Node::Number(num) => num,
+                Node::Variable(name) => *variables.get(name).ok_or_else(
+                    #[cold]
+                    || EvalError::MissingVariable(name.to_owned()),
+                )?,
+                Node::UnOp(op, operand) => op.eval_unary(value_of(operand)),
+                Node::BinOp(op, lhs, rhs) => {
+                    op.eval_binary(value_of(lhs), value_of(rhs))
+                },
+                Node::Function(ref func, ref args) => {
+                    let arg_values = args
+                        .iter()
+                        .map(|&arg| value_of(arg))
+                        .collect::<Vec<_>>();
+                    (func.func)(&arg_values)
+                },
+            };
+            values.push(value);
+        }
+
+        Ok(values.pop().unwrap_or(f64::NAN))
+    }
+
+    /// Simple wrapper around [`XprsDag::eval`] that doesn't require any variables.
+    /// This will obviously fail if the expression contains variables.
+    ///
+    /// # Errors
+    ///
+    /// An [`EvalError`] is returned if the expression contains variables.
+    #[inline]
+    pub fn eval_no_vars(&self) -> Result<f64, EvalError> {
+        self.eval(&[].into())
+    }
+
+    /// Returns the number of unique nodes in the expression.
+    #[inline]
+    #[must_use]
+    pub fn nb_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Creates a new [`XprsDag`] from an [`Xprs`], sharing its common sub-expressions.
+    pub(crate) fn new(xprs: &Xprs<'a>) -> Self {
+        let mut builder = DagBuilder::default();
+        builder.add(&xprs.root);
+        Self {
+            nodes: builder.nodes,
+            vars: xprs.vars.clone(),
+        }
+    }
+
+    /// Returns the set of variables present in the expression.
+    #[inline]
+    #[must_use]
+    pub const fn vars(&self) -> &HashSet<&'a str> {
+        &self.vars
+    }
+}
+
+/// A node of an [`XprsDag`], operands are referred to by their index.
+#[derive(Debug, PartialEq, Clone)]
+enum Node<'a> {
+    /// A binary operation.
+    BinOp(Operator, usize, usize),
+    /// A function call.
+    Function(Function, Vec<usize>),
+    /// A number.
+    Number(f64),
+    /// A unary operation.
+    UnOp(Operator, usize),
+    /// A variable.
+    Variable(&'a str),
+}
+
+impl Node<'_> {
+    /// Returns the called function if the node is a function call.
+    const fn function(&self) -> Option<&Function> {
+        if let Self::Function(ref func, _) = *self {
+            Some(func)
+        } else {
+            None
+        }
+    }
+}
+
+/// The hashable identity of a node, two nodes with the same key are structurally equal.
+#[derive(PartialEq, Eq, Hash)]
+enum NodeKey<'a> {
+    /// A binary operation, operands are sorted for commutative operators.
+    BinOp(Operator, usize, usize),
+    /// A function call, by the function's name.
+    Function(Cow<'static, str>, Vec<usize>),
+    /// A number, by its bits.
+    Number(u64),
+    /// A unary operation.
+    UnOp(Operator, usize),
+    /// A variable.
+    Variable(&'a str),
+}
+
+/// Builds the nodes of an [`XprsDag`] bottom-up (hash-consing).
+#[derive(Default)]
+struct DagBuilder<'a> {
+    /// The index of every shareable node.
+    ids: HashMap<NodeKey<'a>, usize>,
+    /// The unique nodes.
+    nodes: Vec<Node<'a>>,
+}
+
+impl<'a> DagBuilder<'a> {
+    /// Adds an element and its operands, returns the index of its node.
+    fn add(&mut self, element: &Element<'a>) -> usize {
+        let (node, shared_key) = match *element {
+            Element::Number(num) => {
+                (Node::Number(num), Some(NodeKey::Number(num.to_bits())))
+            },
+            // the DAG is evaluated on floats
+            Element::Integer(int) => {
+                let num = int.to_f64();
+                (Node::Number(num), Some(NodeKey::Number(num.to_bits())))
+            },
+            Element::Variable(name) => {
+                (Node::Variable(name), Some(NodeKey::Variable(name)))
+            },
+            Element::UnOp(ref unop) => {
+                let operand = self.add(&unop.operand);
+                (
+                    Node::UnOp(unop.op, operand),
+                    Some(NodeKey::UnOp(unop.op, operand)),
+                )
+            },
+            Element::BinOp(ref binop) => {
+                let lhs = self.add(&binop.lhs);
+                let rhs = self.add(&binop.rhs);
+                let key = if binop.op.is_commutative() {
+                    NodeKey::BinOp(binop.op, lhs.min(rhs), lhs.max(rhs))
+                } else {
+                    NodeKey::BinOp(binop.op, lhs, rhs)
+                };
+                (Node::BinOp(binop.op, lhs, rhs), Some(key))
+            },
+            Element::Function(ref func) => {
+                let args = func
+                    .args
+                    .iter()
+                    .map(|arg| self.add(arg))
+                    .collect::<Vec<_>>();
+                let key = func.desc.is_cacheable().then(|| {
+                    NodeKey::Function(func.desc.name.clone(), args.clone())
+                });
+                (Node::Function(func.desc.clone(), args), key)
+            },
+        };
+
+        let Some(key) = shared_key else {
+            return self.push(node);
+        };
+        match self.ids.get(&key) {
+            // functions are only identified by their name in the key
+            Some(&idx) if Self::same_function(self.nodes.get(idx), &node) => {
+                idx
+            },
+            Some(_) => self.push(node),
+            None => {
+                let idx = self.push(node);
+                self.ids.insert(key, idx);
+                idx
+            },
+        }
+    }
+
+    /// Pushes a node, returns its index.
+    fn push(&mut self, node: Node<'a>) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    /// Whether two nodes call the same function (`true` if they aren't function calls).
+    fn same_function(shared: Option<&Node>, node: &Node) -> bool {
+        shared
+            .and_then(Node::function)
+            .zip(node.function())
+            .map_or(true, |(func1, func2)| func1 == func2)
+    }
+}
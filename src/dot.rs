@@ -0,0 +1,136 @@
+/* Built-in imports */
+use core::fmt;
+/* Crate imports */
+use crate::element::Element;
+
+/// Fill color of the highlighted nodes.
+const HIGHLIGHT_COLOR: &str = "#ffd27f";
+
+/// Displays an element as a Graphviz DOT graph, see [`Xprs::to_dot`](crate::Xprs::to_dot).
+pub struct Dot<'el, 'a> {
+    /// The element compared to, to highlight the nodes that differ from it.
+    pub other: Option<&'el Element<'a>>,
+    /// The rendered element.
+    pub root: &'el Element<'a>,
+}
+
+impl fmt::Display for Dot<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "digraph xprs {{")?;
+        // keeps the operands in order, from left to right
+        writeln!(f, "    ordering=out;")?;
+        let counterpart =
+            self.other.map_or(Counterpart::Ignored, Counterpart::Node);
+        node(f, self.root, counterpart, &mut 0)?;
+        writeln!(f, "}}")
+    }
+}
+
+/// The node at the same place in the compared tree.
+#[derive(Clone, Copy)]
+enum Counterpart<'el, 'a> {
+    /// The tree isn't compared.
+    Ignored,
+    /// An ancestor differs, so there's no node at the same place.
+    Missing,
+    /// The node at the same place.
+    Node(&'el Element<'a>),
+}
+
+/// Displays a label escaped for a DOT quoted string.
+struct Escaped<'str>(&'str str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chr in self.0.chars() {
+            if matches!(chr, '"' | '\\') {
+                write!(f, "\\")?;
+            }
+            write!(f, "{chr}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes a node, its edges and its children in pre-order.
+fn node(
+    fmt: &mut fmt::Formatter<'_>,
+    element: &Element,
+    counterpart: Counterpart,
+    next_id: &mut usize,
+) -> fmt::Result {
+    let id = *next_id;
+    *next_id += 1;
+
+    let label = label_of(element);
+    let shape = match *element {
+        Element::Number(_) | Element::Integer(_) => "plaintext",
+        Element::Variable(_) => "ellipse",
+        Element::BinOp(_) | Element::UnOp(_) => "circle",
+        Element::Function(_) => "box",
+    };
+    let operands = children(element);
+    // a node is unchanged if it has the same label and number of children,
+    // its children are then compared one by one
+    let other_children = match counterpart {
+        Counterpart::Node(other) if label_of(other) == label => {
+            Some(children(other))
+                .filter(|others| others.len() == operands.len())
+        },
+        Counterpart::Ignored | Counterpart::Node(_) | Counterpart::Missing => {
+            None
+        },
+    };
+    let is_highlighted = !matches!(counterpart, Counterpart::Ignored)
+        && other_children.is_none();
+
+    write!(
+        fmt,
+        "    n{id} [label=\"{}\", shape={shape}",
+        Escaped(&label)
+    )?;
+    if is_highlighted {
+        write!(fmt, ", style=filled, fillcolor=\"{HIGHLIGHT_COLOR}\"")?;
+    }
+    writeln!(fmt, "];")?;
+
+    for (idx, child) in operands.into_iter().enumerate() {
+        let child_counterpart = match other_children {
+            Some(ref others) => others
+                .get(idx)
+                .copied()
+                .map_or(Counterpart::Missing, Counterpart::Node),
+            None if matches!(counterpart, Counterpart::Ignored) => {
+                Counterpart::Ignored
+            },
+            None => Counterpart::Missing,
+        };
+        writeln!(fmt, "    n{id} -> n{next_id};")?;
+        node(fmt, child, child_counterpart, next_id)?;
+    }
+    Ok(())
+}
+
+/// Returns the label of a node: its number, variable, operator or function name.
+fn label_of(element: &Element) -> String {
+    match *element {
+        Element::Number(num) => num.to_string(),
+        Element::Integer(int) => int.to_string(),
+        Element::Variable(var) => var.to_owned(),
+        Element::BinOp(ref binop) => binop.op.to_string(),
+        Element::UnOp(ref unop) => unop.op.to_string(),
+        Element::Function(ref func) => func.desc.name.to_string(),
+    }
+}
+
+/// Returns the children of a node, in order.
+fn children<'el, 'a>(element: &'el Element<'a>) -> Vec<&'el Element<'a>> {
+    match *element {
+        Element::Number(_) | Element::Integer(_) | Element::Variable(_) => {
+            Vec::new()
+        },
+        Element::BinOp(ref binop) => vec![&binop.lhs, &binop.rhs],
+        Element::UnOp(ref unop) => vec![&unop.operand],
+        Element::Function(ref func) => func.args.iter().collect(),
+    }
+}
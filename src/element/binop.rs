@@ -37,24 +37,12 @@ impl fmt::Display for BinOp<'_> {
 }
 
 impl PartialEq for BinOp<'_> {
-    #[allow(clippy::unreachable)]
     fn eq(&self, other: &Self) -> bool {
+        let same_operands = self.lhs == other.lhs && self.rhs == other.rhs;
+        let swapped_operands =
+            || self.lhs == other.rhs && self.rhs == other.lhs;
         self.op == other.op
-            && match self.op {
-                // non commutative operators
-                Operator::Divide
-                | Operator::Minus
-                | Operator::Modulo
-                | Operator::Power => {
-                    self.lhs == other.lhs && self.rhs == other.rhs
-                },
-                // commutative operators
-                Operator::Plus | Operator::Times => {
-                    (self.lhs == other.lhs && self.rhs == other.rhs)
-                        || (self.lhs == other.rhs && self.rhs == other.lhs)
-                },
-                // not a binary operator
-                Operator::Factorial => unreachable!(),
-            }
+            && (same_operands
+                || (self.op.is_commutative() && swapped_operands()))
     }
 }
@@ -1,6 +1,8 @@
 /* Built-in imports */
 use core::fmt;
 use std::collections::HashSet;
+/* Crate imports */
+use crate::integer::Integer as _;
 /* Modules */
 /// Binary operation module.
 mod binop;
@@ -16,6 +18,9 @@ pub use function_call::FunctionCall;
 pub use simplify::Simplify;
 pub use unop::UnOp;
 
+/// Every integer up to 2^53 is exactly represented as an [`f64`].
+pub const MAX_EXACT_INTEGER: u64 = 1 << f64::MANTISSA_DIGITS;
+
 /// Represents an element in the abstract syntax tree (AST).
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 #[non_exhaustive]
@@ -28,6 +33,9 @@ pub enum Element<'a> {
     UnOp(Box<UnOp<'a>>),
     /// A function call.
     Function(Box<FunctionCall<'a>>),
+    /// An integer literal too large to be exactly represented as an [`f64`],
+    /// kept as is for [`Xprs::eval_int`](crate::Xprs::eval_int).
+    Integer(u64),
     /// A variable.
     Variable(&'a str),
 }
@@ -39,6 +47,7 @@ impl fmt::Display for Element<'_> {
             Self::BinOp(ref binop) => write!(fmt, "{binop}"),
             Self::UnOp(ref unop) => write!(fmt, "{unop}"),
             Self::Function(ref func) => write!(fmt, "{func}"),
+            Self::Integer(int) => write!(fmt, "{int}"),
             Self::Variable(var) => write!(fmt, "{var}"),
         }
     }
@@ -88,7 +97,17 @@ impl<'a> Element<'a> {
             Self::Function(ref func) => {
                 func.args.iter().for_each(|arg| arg.find_variables(vars));
             },
-            Self::Number(_) => (),
+            Self::Number(_) | Self::Integer(_) => (),
         };
     }
+
+    /// Creates the element of an integer literal, a [`Element::Number`]
+    /// unless it's too large to be exactly represented as an [`f64`].
+    pub(crate) fn from_integer(int: u64) -> Self {
+        if int <= MAX_EXACT_INTEGER {
+            Self::Number(int.to_f64())
+        } else {
+            Self::Integer(int)
+        }
+    }
 }
@@ -1,8 +1,9 @@
 /* Crate imports */
 use crate::{
-    element::{BinOp, Element, FunctionCall, UnOp},
+    element::{BinOp, Element, FunctionCall, UnOp, MAX_EXACT_INTEGER},
+    integer::Integer as _,
     token::Operator,
-    utils::factorial::factorial,
+    utils::{bitwise, factorial::factorial},
 };
 
 /// Trait for simplifying abstract syntax tree (AST) elements.
@@ -22,7 +23,7 @@ impl<'a> Simplify<'a> for Element<'a> {
             Self::UnOp(unop) => unop.simplify_for(var),
             Self::Function(func) => func.simplify_for(var),
             Self::Variable(name) if name == var.0 => Self::Number(var.1),
-            Self::Number(_) | Self::Variable(_) => self,
+            Self::Number(_) | Self::Integer(_) | Self::Variable(_) => self,
         }
     }
 
@@ -31,7 +32,7 @@ impl<'a> Simplify<'a> for Element<'a> {
             Self::BinOp(binop) => binop.simplify(),
             Self::UnOp(unop) => unop.simplify(),
             Self::Function(func) => func.simplify(),
-            Self::Number(_) | Self::Variable(_) => self,
+            Self::Number(_) | Self::Integer(_) | Self::Variable(_) => self,
         }
     }
 }
@@ -46,7 +47,7 @@ impl<'a> Simplify<'a> for BinOp<'a> {
     #[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
     fn simplify(mut self) -> Element<'a> {
         use Element::Number;
-        use Operator::{Divide, Factorial, Minus, Modulo, Plus, Power, Times};
+        use Operator::{Divide, Minus, Modulo, Plus, Power, Times};
         self.lhs = self.lhs.simplify();
         self.rhs = self.rhs.simplify();
         match self {
@@ -131,20 +132,13 @@ impl<'a> Simplify<'a> for BinOp<'a> {
                     }),
             } => BinOp::new_element(Operator::Times, lhs, rhs),
             /////////////////////////// Divisions ///////////////////////////
-            // 0/0 => NaN // special case
+            // divisions by zero are kept to be evaluated (and reported) at runtime
+            // 0 / a => 0
             BinOp {
                 op: Divide,
                 lhs,
                 rhs,
-            } if lhs == Number(0.0) && rhs == Number(0.0) => Number(f64::NAN),
-            // 0 / a => 0
-            BinOp {
-                op: Divide, lhs, ..
-            } if lhs == Number(0.0) => Number(0.0),
-            // a / 0 => inf
-            BinOp {
-                op: Divide, rhs, ..
-            } if rhs == Number(0.0) => Number(f64::INFINITY),
+            } if lhs == Number(0.0) && rhs != Number(0.0) => Number(0.0),
             // a / 1 => a
             BinOp {
                 op: Divide,
@@ -156,7 +150,7 @@ impl<'a> Simplify<'a> for BinOp<'a> {
                 op: Divide,
                 lhs,
                 rhs,
-            } if lhs == rhs => Number(1.0),
+            } if lhs == rhs && rhs != Number(0.0) => Number(1.0),
             ////// NIGHTLY FEATURES //////
             // (-a) / (-b) => a / b
             #[cfg(NIGHTLY)]
@@ -180,12 +174,18 @@ impl<'a> Simplify<'a> for BinOp<'a> {
                 lhs,
                 rhs,
             } if lhs == Number(0.0) && rhs == Number(0.0) => Number(1.0),
-            // 0 ^ a => 0
-            BinOp { op: Power, lhs, .. } if lhs == Number(0.0) => Number(0.0),
-            // a ^ 0 => 1
+            // 0 ^ a => 0 (unless a is a non-positive number)
             BinOp {
-                op: Divide, rhs, ..
-            } if rhs == Number(0.0) => Number(1.0),
+                op: Power,
+                lhs,
+                rhs,
+            } if lhs == Number(0.0)
+                && !matches!(rhs, Number(exp) if exp <= 0.0) =>
+            {
+                Number(0.0)
+            },
+            // a ^ 0 => 1
+            BinOp { op: Power, rhs, .. } if rhs == Number(0.0) => Number(1.0),
             // a ^ 1 => a
             BinOp {
                 op: Power,
@@ -193,20 +193,13 @@ impl<'a> Simplify<'a> for BinOp<'a> {
                 rhs,
             } if rhs == Number(1.0) => lhs,
             //////////////////////////// Modulos ////////////////////////////
-            // 0 % 0 => NaN // special case
+            // modulos by zero are kept to be evaluated (and reported) at runtime
+            // 0 % a => 0
             BinOp {
                 op: Modulo,
                 lhs,
                 rhs,
-            } if lhs == Number(0.0) && rhs == Number(0.0) => Number(f64::NAN),
-            // 0 % a => 0
-            BinOp {
-                op: Modulo, lhs, ..
-            } if lhs == Number(0.0) => Number(0.0),
-            // a % 0 => NaN
-            BinOp {
-                op: Modulo, rhs, ..
-            } if rhs == Number(0.0) => Number(f64::NAN),
+            } if lhs == Number(0.0) && rhs != Number(0.0) => Number(0.0),
             // a % 1 => 0
             BinOp {
                 op: Modulo, rhs, ..
@@ -216,24 +209,14 @@ impl<'a> Simplify<'a> for BinOp<'a> {
                 op: Modulo,
                 lhs,
                 rhs,
-            } if lhs == rhs => Number(0.0),
+            } if lhs == rhs && rhs != Number(0.0) => Number(0.0),
             /////////////////////////// 2 Numbers ///////////////////////////
-            BinOp {
-                op,
-                rhs: Number(rhs),
-                lhs: Number(lhs),
-            } => {
-                #[allow(clippy::unreachable)]
-                let result = match op {
-                    Plus => lhs + rhs,
-                    Minus => lhs - rhs,
-                    Times => lhs * rhs,
-                    Divide => lhs / rhs,
-                    Power => lhs.powf(rhs),
-                    Modulo => lhs % rhs,
-                    Factorial => unreachable!(),
-                };
-                Number(result)
+            BinOp { op, lhs, rhs }
+                if matches!(lhs, Number(_) | Element::Integer(_))
+                    && matches!(rhs, Number(_) | Element::Integer(_)) =>
+            {
+                fold_binary(op, &lhs, &rhs)
+                    .unwrap_or_else(|| BinOp::new_element(op, lhs, rhs))
             },
             _ => self.into(),
         }
@@ -252,10 +235,19 @@ impl<'a> Simplify<'a> for UnOp<'a> {
         match self.op {
             Operator::Plus => self.operand,
             Operator::Factorial => match self.operand {
-                Element::Number(num) => Element::Number(factorial(num)),
+                Element::Number(num) => {
+                    // non-finite results are kept to be reported at runtime
+                    let result = factorial(num);
+                    if result.is_finite() {
+                        Element::Number(result)
+                    } else {
+                        self.into()
+                    }
+                },
                 Element::UnOp(_)
                 | Element::BinOp(_)
                 | Element::Function(_)
+                | Element::Integer(_)
                 | Element::Variable(_) => self.into(),
             },
             Operator::Minus => match self.operand {
@@ -263,12 +255,19 @@ impl<'a> Simplify<'a> for UnOp<'a> {
                 Element::UnOp(_)
                 | Element::BinOp(_)
                 | Element::Function(_)
+                | Element::Integer(_)
                 | Element::Variable(_) => self.into(),
             },
             Operator::Times
             | Operator::Divide
+            | Operator::IntDivide
             | Operator::Power
-            | Operator::Modulo => unreachable!(),
+            | Operator::Modulo
+            | Operator::BitAnd
+            | Operator::BitOr
+            | Operator::BitXor
+            | Operator::ShiftLeft
+            | Operator::ShiftRight => unreachable!(),
         }
     }
 }
@@ -301,10 +300,147 @@ impl<'a> Simplify<'a> for FunctionCall<'a> {
             })
             .collect();
 
-        if args_values.len() == self.args.len() {
-            self.call(&args_values).into()
+        // impure functions must be called at runtime
+        if args_values.len() != self.args.len() || !self.desc.is_cacheable() {
+            return self.into();
+        }
+
+        // non-finite results are kept to be reported at runtime
+        let result = self.call(&args_values);
+        if result.is_finite() {
+            result.into()
         } else {
             self.into()
         }
     }
 }
+
+/// Returns the value of an integer constant, exactly.
+fn exact_integer(element: &Element) -> Option<i128> {
+    #[allow(
+        clippy::as_conversions,
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation
+    )]
+    match *element {
+        Element::Integer(int) => Some(i128::from(int)),
+        // `MAX` gets rounded up to 2^127, the exclusive upper bound of `i128`
+        Element::Number(num)
+            if num.fract() == 0.0_f64 && num.abs() < i128::MAX as f64 =>
+        {
+            Some(num as i128)
+        },
+        Element::Number(_)
+        | Element::BinOp(_)
+        | Element::UnOp(_)
+        | Element::Function(_)
+        | Element::Variable(_) => None,
+    }
+}
+
+/// Folds a binary operation on two constants,
+/// returns [`None`] if it must be evaluated at runtime.
+///
+/// Integer results are computed exactly so that
+/// [`Xprs::eval_int`](crate::Xprs::eval_int) gets the same result as without folding.
+fn fold_binary<'a>(
+    op: Operator,
+    lhs: &Element,
+    rhs: &Element,
+) -> Option<Element<'a>> {
+    use Operator::{
+        BitAnd, BitOr, BitXor, Divide, Factorial, IntDivide, Minus, Modulo,
+        Plus, Power, ShiftLeft, ShiftRight, Times,
+    };
+    let integers = exact_integer(lhs).zip(exact_integer(rhs));
+    if let Some(folded) = integers
+        .and_then(|(lhs_int, rhs_int)| fold_integers(op, lhs_int, rhs_int))
+    {
+        return Some(folded);
+    }
+
+    let to_f64 = |element: &Element| match *element {
+        Element::Number(num) => num,
+        Element::Integer(int) => int.to_f64(),
+        Element::BinOp(_)
+        | Element::UnOp(_)
+        | Element::Function(_)
+        | Element::Variable(_) => f64::NAN,
+    };
+    let (left, right) = (to_f64(lhs), to_f64(rhs));
+    #[allow(clippy::unreachable)]
+    let result = match op {
+        Plus => left + right,
+        Minus => left - right,
+        Times => left * right,
+        Divide => left / right,
+        IntDivide => (left / right).trunc(),
+        Power => left.powf(right),
+        Modulo => left % right,
+        BitAnd => bitwise::bit_and(left, right),
+        BitOr => bitwise::bit_or(left, right),
+        BitXor => bitwise::bit_xor(left, right),
+        ShiftLeft => bitwise::shift_left(left, right),
+        ShiftRight => bitwise::shift_right(left, right),
+        Factorial => unreachable!(),
+    };
+    // non-finite results are kept to be reported at runtime, as well as
+    // results beyond 2^53 and the integer results of integers, which might be inexact
+    let is_exact = result.abs() < MAX_EXACT_INTEGER.to_f64()
+        && (integers.is_none() || result.fract() != 0.0_f64);
+    is_exact.then_some(Element::Number(result))
+}
+
+/// Folds a binary operation on two integers exactly,
+/// returns [`None`] if the result isn't an integer or doesn't fit in an element.
+fn fold_integers<'a>(
+    op: Operator,
+    lhs: i128,
+    rhs: i128,
+) -> Option<Element<'a>> {
+    use Operator::{
+        BitAnd, BitOr, BitXor, Divide, Factorial, IntDivide, Minus, Modulo,
+        Plus, Power, ShiftLeft, ShiftRight, Times,
+    };
+    // the bitwise operators work on `i64` when evaluated on floats
+    let (lhs_i64, rhs_i64) = (i64::try_from(lhs).ok(), i64::try_from(rhs).ok());
+    let bitwise = |int_op: fn(i64, i64) -> Option<i64>| {
+        lhs_i64
+            .zip(rhs_i64)
+            .and_then(|(lhs_int, rhs_int)| int_op(lhs_int, rhs_int))
+            .map(i128::from)
+    };
+    #[allow(clippy::unreachable)]
+    let result = match op {
+        Plus => lhs.checked_add(rhs)?,
+        Minus => lhs.checked_sub(rhs)?,
+        Times => lhs.checked_mul(rhs)?,
+        Divide if lhs.checked_rem(rhs)? == 0 => lhs.checked_div(rhs)?,
+        Divide => return None,
+        IntDivide => lhs.checked_div(rhs)?,
+        Power => lhs.checked_pow(u32::try_from(rhs).ok()?)?,
+        Modulo => lhs.checked_rem(rhs)?,
+        BitAnd => bitwise(|lhs_int, rhs_int| Some(lhs_int & rhs_int))?,
+        BitOr => bitwise(|lhs_int, rhs_int| Some(lhs_int | rhs_int))?,
+        BitXor => bitwise(|lhs_int, rhs_int| Some(lhs_int ^ rhs_int))?,
+        // bits shifted out aren't folded, the evaluation modes disagree on them
+        ShiftLeft => bitwise(|lhs_int, rhs_int| {
+            let res = lhs_int.checked_shl(u32::try_from(rhs_int).ok()?)?;
+            (res >> rhs_int == lhs_int).then_some(res)
+        })?,
+        ShiftRight => bitwise(|lhs_int, rhs_int| {
+            lhs_int.checked_shr(u32::try_from(rhs_int).ok()?)
+        })?,
+        Factorial => unreachable!(),
+    };
+
+    // negative integers are only exact as numbers
+    #[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+    u64::try_from(result).map_or_else(
+        |_err| {
+            (result.unsigned_abs() <= u128::from(MAX_EXACT_INTEGER))
+                .then(|| Element::Number(result as f64))
+        },
+        |int| Some(Element::from_integer(int)),
+    )
+}
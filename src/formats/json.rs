@@ -0,0 +1,343 @@
+/* Built-in imports */
+use core::fmt;
+use std::collections::HashSet;
+/* Crate imports */
+use super::{call, operation, variable, ImportError};
+use crate::{
+    binary::MAX_DEPTH, context::Context, element::Element,
+    utils::hidden_macros::yeet, xprs::Xprs,
+};
+
+/// Displays an element as a JSON AST, see [`Xprs::to_json`].
+pub struct Json<'el, 'a>(pub &'el Element<'a>);
+
+impl fmt::Display for Json<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self.0 {
+            Element::Number(num) => {
+                write!(f, r#"{{"type":"number","value":"#)?;
+                // non-finite numbers as written by Python's `json` module
+                if num.is_nan() {
+                    write!(f, "NaN")?;
+                } else if num.is_infinite() {
+                    let sign = if num < 0.0_f64 { "-" } else { "" };
+                    write!(f, "{sign}Infinity")?;
+                } else {
+                    write!(f, "{num}")?;
+                }
+                write!(f, "}}")
+            },
+            Element::Integer(int) => {
+                write!(f, r#"{{"type":"number","value":{int}}}"#)
+            },
+            Element::Variable(var) => {
+                write!(f, r#"{{"type":"variable","name":"{}"}}"#, Escaped(var))
+            },
+            Element::BinOp(ref binop) => write!(
+                f,
+                r#"{{"type":"binary","op":"{}","lhs":{},"rhs":{}}}"#,
+                binop.op,
+                Json(&binop.lhs),
+                Json(&binop.rhs)
+            ),
+            Element::UnOp(ref unop) => write!(
+                f,
+                r#"{{"type":"unary","op":"{}","operand":{}}}"#,
+                unop.op,
+                Json(&unop.operand)
+            ),
+            Element::Function(ref func) => {
+                write!(
+                    f,
+                    r#"{{"type":"call","name":"{}","args":["#,
+                    Escaped(&func.desc.name)
+                )?;
+                for (idx, arg) in func.args.iter().enumerate() {
+                    if idx != 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", Json(arg))?;
+                }
+                write!(f, "]}}")
+            },
+        }
+    }
+}
+
+/// Displays a string escaped for JSON.
+struct Escaped<'str>(&'str str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chr in self.0.chars() {
+            match chr {
+                '"' => write!(f, r#"\""#)?,
+                '\\' => write!(f, r"\\")?,
+                _ if chr.is_control() => {
+                    write!(f, r"\u{:04x}", u32::from(chr))?;
+                },
+                _ => write!(f, "{chr}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A JSON value, with its byte offset for objects.
+enum Value<'input> {
+    /// An array.
+    Array(Vec<Self>),
+    /// An integer, parsed losslessly.
+    Integer(u64),
+    /// A boolean or `null`.
+    Literal,
+    /// A number.
+    Number(f64),
+    /// An object, with its byte offset.
+    Object(Vec<(&'input str, Self)>, usize),
+    /// A string, without escapes.
+    String(&'input str),
+}
+
+impl<'input> Value<'input> {
+    /// Converts the value into an element at the given depth, checking it against the context.
+    fn element(
+        self,
+        ctx: &Context,
+        depth: usize,
+    ) -> Result<Element<'input>, ImportError> {
+        if depth >= MAX_DEPTH {
+            yeet!(ImportError::TooDeep);
+        }
+        let Self::Object(mut fields, offset) = self else {
+            yeet!(ImportError::Syntax("expected a node object".to_owned(), 0));
+        };
+        let mut field = |key: &str| {
+            let idx = fields.iter().position(|field| field.0 == key);
+            idx.map(|pos| fields.swap_remove(pos).1).ok_or_else(|| {
+                ImportError::Syntax(format!("missing `{key}`"), offset)
+            })
+        };
+        let invalid =
+            |key: &str| ImportError::Syntax(format!("invalid `{key}`"), offset);
+
+        let Self::String(kind) = field("type")? else {
+            yeet!(invalid("type"));
+        };
+        let element = match kind {
+            "number" => match field("value")? {
+                Self::Number(num) => Element::Number(num),
+                Self::Integer(int) => Element::from_integer(int),
+                Self::Array(_)
+                | Self::Literal
+                | Self::Object(..)
+                | Self::String(_) => yeet!(invalid("value")),
+            },
+            "variable" => {
+                let Self::String(name) = field("name")? else {
+                    yeet!(invalid("name"));
+                };
+                variable(name, ctx)?
+            },
+            "unary" => {
+                let (Self::String(op), operand) =
+                    (field("op")?, field("operand")?)
+                else {
+                    yeet!(invalid("op"));
+                };
+                operation(op, vec![operand.element(ctx, depth + 1)?])?
+            },
+            "binary" => {
+                let (Self::String(op), lhs, rhs) =
+                    (field("op")?, field("lhs")?, field("rhs")?)
+                else {
+                    yeet!(invalid("op"));
+                };
+                operation(
+                    op,
+                    vec![
+                        lhs.element(ctx, depth + 1)?,
+                        rhs.element(ctx, depth + 1)?,
+                    ],
+                )?
+            },
+            "call" => {
+                let (Self::String(name), Self::Array(nodes)) =
+                    (field("name")?, field("args")?)
+                else {
+                    yeet!(invalid("call"));
+                };
+                let args = nodes
+                    .into_iter()
+                    .map(|arg| arg.element(ctx, depth + 1))
+                    .collect::<Result<_, _>>()?;
+                call(name, args, ctx)?
+            },
+            _ => yeet!(invalid("type")),
+        };
+        Ok(element)
+    }
+}
+
+/// Minimal JSON parser, strings with escapes are rejected since names are identifiers.
+struct JsonParser<'input> {
+    /// The current position in the input.
+    cursor: usize,
+    /// The JSON input.
+    input: &'input str,
+}
+
+impl<'input> JsonParser<'input> {
+    /// Parses an array at the given depth.
+    fn array(&mut self, depth: usize) -> Result<Value<'input>, ImportError> {
+        let mut values = Vec::new();
+        self.cursor += 1;
+        self.skip_whitespaces();
+        if self.consume(b']') {
+            return Ok(Value::Array(values));
+        }
+        loop {
+            values.push(self.value(depth + 1)?);
+            self.skip_whitespaces();
+            if self.consume(b']') {
+                return Ok(Value::Array(values));
+            }
+            self.expect(b',')?;
+        }
+    }
+
+    /// Consumes the current byte if it's `byte`.
+    fn consume(&mut self, byte: u8) -> bool {
+        let is_byte = self.current() == Some(&byte);
+        if is_byte {
+            self.cursor += 1;
+        }
+        is_byte
+    }
+
+    /// Returns the current byte.
+    fn current(&self) -> Option<&u8> {
+        self.input.as_bytes().get(self.cursor)
+    }
+
+    /// Creates a syntax error at the current position.
+    fn error(&self, message: &str) -> ImportError {
+        ImportError::Syntax(message.to_owned(), self.cursor)
+    }
+
+    /// Consumes the current byte, which must be `byte`.
+    fn expect(&mut self, byte: u8) -> Result<(), ImportError> {
+        if !self.consume(byte) {
+            yeet!(self.error(&format!("expected `{}`", char::from(byte))));
+        }
+        Ok(())
+    }
+
+    /// Parses a number (including Python's `NaN`, `Infinity` and `-Infinity`), a boolean or `null`.
+    fn literal(&mut self) -> Result<Value<'input>, ImportError> {
+        let start = self.cursor;
+        while self.current().is_some_and(|&ch| {
+            ch.is_ascii_alphanumeric() || matches!(ch, b'-' | b'+' | b'.')
+        }) {
+            self.cursor += 1;
+        }
+        let literal = self.input.get(start..self.cursor).unwrap_or_default();
+        match literal {
+            "true" | "false" | "null" => Ok(Value::Literal),
+            _ => literal
+                .parse()
+                .map(Value::Integer)
+                .or_else(|_err| literal.parse().map(Value::Number))
+                .map_err(|_err| {
+                    ImportError::Syntax(
+                        format!("invalid literal `{literal}`"),
+                        start,
+                    )
+                }),
+        }
+    }
+
+    /// Parses an object at the given depth.
+    fn object(&mut self, depth: usize) -> Result<Value<'input>, ImportError> {
+        let offset = self.cursor;
+        let mut fields = Vec::new();
+        self.cursor += 1;
+        self.skip_whitespaces();
+        if self.consume(b'}') {
+            return Ok(Value::Object(fields, offset));
+        }
+        loop {
+            self.skip_whitespaces();
+            let key = self.string()?;
+            self.skip_whitespaces();
+            self.expect(b':')?;
+            fields.push((key, self.value(depth + 1)?));
+            self.skip_whitespaces();
+            if self.consume(b'}') {
+                return Ok(Value::Object(fields, offset));
+            }
+            self.expect(b',')?;
+        }
+    }
+
+    /// Skips the whitespaces.
+    fn skip_whitespaces(&mut self) {
+        while self.current().is_some_and(u8::is_ascii_whitespace) {
+            self.cursor += 1;
+        }
+    }
+
+    /// Parses a string without escapes.
+    fn string(&mut self) -> Result<&'input str, ImportError> {
+        self.expect(b'"')?;
+        let start = self.cursor;
+        while let Some(&ch) = self.current() {
+            match ch {
+                b'"' => {
+                    let string = self.input.get(start..self.cursor);
+                    self.cursor += 1;
+                    return Ok(string.unwrap_or_default());
+                },
+                b'\\' => yeet!(self.error("escaped strings are not supported")),
+                _ => self.cursor += 1,
+            }
+        }
+        yeet!(self.error("unterminated string"))
+    }
+
+    /// Parses a value at the given depth,
+    /// a node nests its arguments in an array so the values can be twice as deep as the elements.
+    fn value(&mut self, depth: usize) -> Result<Value<'input>, ImportError> {
+        if depth >= 2 * MAX_DEPTH {
+            yeet!(ImportError::TooDeep);
+        }
+        self.skip_whitespaces();
+        match self.current().copied() {
+            Some(b'{') => self.object(depth),
+            Some(b'[') => self.array(depth),
+            Some(b'"') => self.string().map(Value::String),
+            Some(_) => self.literal(),
+            None => yeet!(self.error("unexpected end of input")),
+        }
+    }
+}
+
+/// Parses a JSON AST, see [`Xprs::from_json`].
+pub fn parse<'input>(
+    input: &'input str,
+    ctx: &Context,
+) -> Result<Xprs<'input>, ImportError> {
+    let mut parser = JsonParser { input, cursor: 0 };
+    let value = parser.value(0)?;
+    parser.skip_whitespaces();
+    if parser.cursor != input.len() {
+        yeet!(ImportError::Syntax(
+            "unexpected trailing input".to_owned(),
+            parser.cursor
+        ));
+    }
+    let root = value.element(ctx, 0)?;
+    let mut vars = HashSet::new();
+    root.find_variables(&mut vars);
+    Ok(Xprs { root, vars })
+}
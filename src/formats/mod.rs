@@ -0,0 +1,106 @@
+/* Modules */
+/// JSON AST module.
+pub mod json;
+/// S-expressions module.
+pub mod sexpr;
+/* Crate imports */
+use crate::{
+    context::Context,
+    element::{BinOp, Element, FunctionCall, UnOp},
+    token::Operator,
+    utils::hidden_macros::yeet,
+};
+
+/// Represents an error that occurs when importing an expression from an S-expression or a JSON AST.
+#[derive(Debug, Eq, PartialEq, thiserror::Error, Clone, Hash)]
+#[non_exhaustive]
+pub enum ImportError {
+    /// A function is called with a number of arguments it doesn't accept.
+    #[error("Import error: `{0}` called with {1} arguments")]
+    ArityMismatch(String, usize),
+    /// A variable name isn't a valid identifier.
+    #[error("Import error: invalid variable name `{0}`")]
+    InvalidName(String),
+    /// A variable is defined by the context (as a symbol, a constant or a function).
+    #[error("Import error: `{0}` is defined by the context")]
+    NotAVariable(String),
+    /// An operator is applied to a number of operands it doesn't accept.
+    #[error("Import error: `{0}` applied to {1} operands")]
+    OperatorArity(String, usize),
+    /// The input is malformed, with a description and the byte offset of the error.
+    #[error("Import error: {0} at byte {1}")]
+    Syntax(String, usize),
+    /// The tree is nested deeper than the importer accepts.
+    #[error("Import error: expression nested too deeply")]
+    TooDeep,
+    /// A variable isn't one of the context's expected variables.
+    #[error("Import error: variable `{0}` is not declared")]
+    UndeclaredVariable(String),
+    /// A function isn't known (or isn't permitted) by the context.
+    #[error("Import error: unknown function `{0}`")]
+    UnknownFunction(String),
+    /// An operator is unknown.
+    #[error("Import error: unknown operator `{0}`")]
+    UnknownOperator(String),
+}
+
+/// Builds the operation `symbol` applied to its operands, checking its arity.
+fn operation<'input>(
+    symbol: &str,
+    operands: Vec<Element<'input>>,
+) -> Result<Element<'input>, ImportError> {
+    let op = Operator::from_symbol(symbol)
+        .ok_or_else(|| ImportError::UnknownOperator(symbol.to_owned()))?;
+    let nb_operands = operands.len();
+    let mut iter = operands.into_iter();
+    let element = match (iter.next(), iter.next(), iter.next()) {
+        (Some(operand), None, None) if op.is_unary() => {
+            UnOp::new_element(op, operand)
+        },
+        (Some(lhs), Some(rhs), None) if op.is_binary() => {
+            BinOp::new_element(op, lhs, rhs)
+        },
+        _ => yeet!(ImportError::OperatorArity(symbol.to_owned(), nb_operands)),
+    };
+    Ok(element)
+}
+
+/// Builds a call to the function `name` of the context, checking its arity.
+fn call<'input>(
+    name: &str,
+    args: Vec<Element<'input>>,
+    ctx: &Context,
+) -> Result<Element<'input>, ImportError> {
+    let func = ctx
+        .find_function(name)
+        .ok_or_else(|| ImportError::UnknownFunction(name.to_owned()))?;
+    if !func.arity.contains(args.len()) {
+        yeet!(ImportError::ArityMismatch(name.to_owned(), args.len()));
+    }
+    Ok(FunctionCall::new_element(func, args))
+}
+
+/// Builds a variable, checking that it's neither defined by the context nor undeclared.
+pub fn variable<'input>(
+    name: &'input str,
+    ctx: &Context,
+) -> Result<Element<'input>, ImportError> {
+    let mut chars = name.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+        && chars
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '\''));
+    if !is_identifier {
+        yeet!(ImportError::InvalidName(name.to_owned()));
+    }
+    if ctx.defines(name) {
+        yeet!(ImportError::NotAVariable(name.to_owned()));
+    }
+    if let Some(expected) = ctx.get_expected_vars() {
+        if !expected.contains(name) {
+            yeet!(ImportError::UndeclaredVariable(name.to_owned()));
+        }
+    }
+    Ok(Element::Variable(name))
+}
@@ -0,0 +1,181 @@
+/* Built-in imports */
+use core::fmt;
+use std::collections::HashSet;
+/* Crate imports */
+use super::{call, operation, variable, ImportError};
+use crate::{
+    binary::MAX_DEPTH, context::Context, element::Element,
+    utils::hidden_macros::yeet, xprs::Xprs,
+};
+
+/// Displays an element as an S-expression, see [`Xprs::to_sexpr`].
+pub struct SExpr<'el, 'a>(pub &'el Element<'a>);
+
+impl fmt::Display for SExpr<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self.0 {
+            // signed, so they aren't read back as variables
+            Element::Number(num) if num.is_nan() || num == f64::INFINITY => {
+                write!(f, "+{num}")
+            },
+            Element::Number(num) => write!(f, "{num}"),
+            Element::Integer(int) => write!(f, "{int}"),
+            Element::Variable(var) => write!(f, "{var}"),
+            Element::BinOp(ref binop) => write!(
+                f,
+                "({} {} {})",
+                binop.op,
+                SExpr(&binop.lhs),
+                SExpr(&binop.rhs)
+            ),
+            Element::UnOp(ref unop) => {
+                write!(f, "({} {})", unop.op, SExpr(&unop.operand))
+            },
+            Element::Function(ref func) => {
+                write!(f, "({}", func.desc.name)?;
+                for arg in &func.args {
+                    write!(f, " {}", SExpr(arg))?;
+                }
+                write!(f, ")")
+            },
+        }
+    }
+}
+
+/// Parser of S-expressions.
+struct SExprParser<'input, 'ctx> {
+    /// The context the functions and variables are checked against.
+    ctx: &'ctx Context<'ctx>,
+    /// The current position in the input.
+    cursor: usize,
+    /// The S-expression.
+    input: &'input str,
+}
+
+impl<'input> SExprParser<'input, '_> {
+    /// Takes an atom, until a whitespace or a parenthesis.
+    fn atom(&mut self) -> &'input str {
+        let start = self.cursor;
+        while self.current().is_some_and(|&ch| {
+            !ch.is_ascii_whitespace() && ch != b'(' && ch != b')'
+        }) {
+            self.cursor += 1;
+        }
+        self.input.get(start..self.cursor).unwrap_or_default()
+    }
+
+    /// Returns the current byte.
+    fn current(&self) -> Option<&u8> {
+        self.input.as_bytes().get(self.cursor)
+    }
+
+    /// Parses an element at the given depth, an atom or a list.
+    fn element(
+        &mut self,
+        depth: usize,
+    ) -> Result<Element<'input>, ImportError> {
+        if depth >= MAX_DEPTH {
+            yeet!(ImportError::TooDeep);
+        }
+        self.skip_whitespaces();
+        match self.current().copied() {
+            Some(b'(') => self.list(depth),
+            Some(b')') => yeet!(ImportError::Syntax(
+                "unexpected `)`".to_owned(),
+                self.cursor
+            )),
+            Some(_) => {
+                let atom = self.atom();
+                number(atom).map_or_else(|| variable(atom, self.ctx), Ok)
+            },
+            None => yeet!(ImportError::Syntax(
+                "unexpected end of input".to_owned(),
+                self.cursor
+            )),
+        }
+    }
+
+    /// Parses a list at the given depth, an operation or a function call.
+    fn list(&mut self, depth: usize) -> Result<Element<'input>, ImportError> {
+        self.cursor += 1;
+        self.skip_whitespaces();
+        let head_start = self.cursor;
+        let head = self.atom();
+        if head.is_empty() {
+            yeet!(ImportError::Syntax(
+                "expected an operator or a function name".to_owned(),
+                head_start
+            ));
+        }
+        let mut args = Vec::new();
+        loop {
+            self.skip_whitespaces();
+            if self.current() == Some(&b')') {
+                self.cursor += 1;
+                break;
+            }
+            args.push(self.element(depth + 1)?);
+        }
+        if number(head).is_some() {
+            yeet!(ImportError::Syntax(
+                format!("`{head}` can't be applied"),
+                head_start
+            ));
+        }
+        let is_function = head
+            .chars()
+            .next()
+            .is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+            && head != "xor";
+        if is_function {
+            call(head, args, self.ctx)
+        } else {
+            operation(head, args)
+        }
+    }
+
+    /// Skips the whitespaces.
+    fn skip_whitespaces(&mut self) {
+        while self.current().is_some_and(u8::is_ascii_whitespace) {
+            self.cursor += 1;
+        }
+    }
+}
+
+/// Parses an S-expression, see [`Xprs::from_sexpr`].
+pub fn parse<'input>(
+    input: &'input str,
+    ctx: &Context,
+) -> Result<Xprs<'input>, ImportError> {
+    let mut parser = SExprParser {
+        input,
+        cursor: 0,
+        ctx,
+    };
+    let root = parser.element(0)?;
+    parser.skip_whitespaces();
+    if parser.cursor != input.len() {
+        yeet!(ImportError::Syntax(
+            "unexpected trailing input".to_owned(),
+            parser.cursor
+        ));
+    }
+    let mut vars = HashSet::new();
+    root.find_variables(&mut vars);
+    Ok(Xprs { root, vars })
+}
+
+/// Parses a number atom, which starts with a digit, a `.` or a sign
+/// (`inf` or `NaN` are names, the non-finite numbers are `+inf`, `-inf` and `+NaN`).
+/// Integers are parsed losslessly, see [`Element::Integer`].
+fn number<'input>(atom: &str) -> Option<Element<'input>> {
+    if !atom.starts_with(|ch: char| {
+        ch.is_ascii_digit() || matches!(ch, '.' | '+' | '-')
+    }) {
+        return None;
+    }
+    atom.parse()
+        .map(Element::from_integer)
+        .or_else(|_err| atom.parse().map(Element::Number))
+        .ok()
+}
@@ -0,0 +1,363 @@
+/* Clippy Config */
+#![allow(clippy::std_instead_of_core)]
+/* Built-in imports */
+use core::ops::{BitAnd, BitOr, BitXor};
+use std::collections::HashMap;
+/* Crate imports */
+use crate::{
+    element::Element,
+    token::Operator,
+    utils::{
+        factorial::factorial,
+        hidden_macros::{trust_me, yeet},
+    },
+    xprs::{EvalError, FailedNode},
+};
+
+/// Implements [`Integer`] for primitive integer types.
+macro_rules! impl_integer {
+    ($($int:ty),+) => {$(
+        impl Integer for $int {
+            const ONE: Self = 1;
+            const ZERO: Self = 0;
+
+            #[inline]
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$int>::checked_add(self, rhs)
+            }
+
+            #[inline]
+            fn checked_div(self, rhs: Self) -> Option<Self> {
+                <$int>::checked_div(self, rhs)
+            }
+
+            #[inline]
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$int>::checked_mul(self, rhs)
+            }
+
+            #[inline]
+            fn checked_neg(self) -> Option<Self> {
+                <$int>::checked_neg(self)
+            }
+
+            #[inline]
+            fn checked_pow(self, exp: u32) -> Option<Self> {
+                <$int>::checked_pow(self, exp)
+            }
+
+            #[inline]
+            fn checked_rem(self, rhs: Self) -> Option<Self> {
+                <$int>::checked_rem(self, rhs)
+            }
+
+            #[inline]
+            fn checked_shl(self, rhs: u32) -> Option<Self> {
+                <$int>::checked_shl(self, rhs)
+            }
+
+            #[inline]
+            fn checked_shr(self, rhs: u32) -> Option<Self> {
+                <$int>::checked_shr(self, rhs)
+            }
+
+            #[inline]
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$int>::checked_sub(self, rhs)
+            }
+
+            #[inline]
+            fn from_f64(num: f64) -> Option<Self> {
+                const ZERO: f64 = 0.0;
+                // `MAX` gets rounded up to the next power of two,
+                // making it the exclusive upper bound of the type
+                #[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+                let range = (<$int>::MIN as f64)..(<$int>::MAX as f64);
+                // `fract` of NaN and infinities is NaN
+                if num.fract() != ZERO || !range.contains(&num) {
+                    return None;
+                }
+                Some(trust_me!(num.to_int_unchecked::<Self>()))
+            }
+
+            #[inline]
+            fn from_u64(int: u64) -> Option<Self> {
+                Self::try_from(int).ok()
+            }
+
+            #[inline]
+            #[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            #[inline]
+            fn to_u32(self) -> Option<u32> {
+                u32::try_from(self).ok()
+            }
+        }
+    )+};
+}
+
+/// Integer types an [`Xprs`](crate::Xprs) can be evaluated with,
+/// see [`Xprs::eval_int`](crate::Xprs::eval_int).
+///
+/// Implemented for [`i64`] and [`u64`].
+pub trait Integer:
+    Copy
+    + PartialEq
+    + PartialOrd
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+{
+    /// The one value of the integer type.
+    const ONE: Self;
+    /// The zero value of the integer type.
+    const ZERO: Self;
+
+    /// Checked integer addition.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// Checked integer division (truncates towards zero).
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    /// Checked integer multiplication.
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    /// Checked negation.
+    fn checked_neg(self) -> Option<Self>;
+    /// Checked exponentiation.
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+    /// Checked integer remainder.
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+    /// Checked left shift, fails if the shift amount is too large.
+    fn checked_shl(self, rhs: u32) -> Option<Self>;
+    /// Checked right shift, fails if the shift amount is too large.
+    fn checked_shr(self, rhs: u32) -> Option<Self>;
+    /// Checked integer subtraction.
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    /// Converts an [`f64`] into the integer type.
+    /// Returns [`None`] if the value isn't an integer or is out of range.
+    fn from_f64(num: f64) -> Option<Self>;
+    /// Converts an [`u64`] into the integer type.
+    /// Returns [`None`] if the value is out of range.
+    fn from_u64(int: u64) -> Option<Self>;
+    /// Converts the integer into an [`f64`], possibly losing precision.
+    fn to_f64(self) -> f64;
+    /// Converts the integer into an [`u32`] (used for exponents and shifts).
+    fn to_u32(self) -> Option<u32>;
+}
+
+impl_integer!(i64, u64);
+
+/// An internal struct used for evaluating expressions on integers.
+///
+/// Mirrors `XprsImpl` but every operation is checked.
+pub struct IntXprsImpl<'a, T> {
+    /// A reference to the map of variables and their corresponding values.
+    variables: &'a HashMap<&'a str, T>,
+}
+
+impl<T: Integer> IntXprsImpl<'_, T> {
+    /// Evaluates a binary operation on two integers.
+    fn eval_binop(op: Operator, left: T, right: T) -> Result<T, IntErrorKind> {
+        use IntErrorKind::{DivisionByZero, NotAnInteger, Overflow};
+
+        if matches!(
+            op,
+            Operator::Divide | Operator::IntDivide | Operator::Modulo
+        ) && right == T::ZERO
+        {
+            return Err(DivisionByZero);
+        }
+
+        #[allow(clippy::unreachable)]
+        let res = match op {
+            Operator::Plus => left.checked_add(right).ok_or(Overflow)?,
+            Operator::Minus => left.checked_sub(right).ok_or(Overflow)?,
+            Operator::Times => left.checked_mul(right).ok_or(Overflow)?,
+            // regular division must be exact
+            Operator::Divide => {
+                if left.checked_rem(right).ok_or(Overflow)? != T::ZERO {
+                    return Err(NotAnInteger(left.to_f64() / right.to_f64()));
+                }
+                left.checked_div(right).ok_or(Overflow)?
+            },
+            Operator::IntDivide => left.checked_div(right).ok_or(Overflow)?,
+            Operator::Modulo => left.checked_rem(right).ok_or(Overflow)?,
+            Operator::Power => {
+                if let Some(exp) = right.to_u32() {
+                    left.checked_pow(exp).ok_or(Overflow)?
+                } else if left == T::ZERO {
+                    if right < T::ZERO {
+                        return Err(DivisionByZero);
+                    }
+                    T::ZERO
+                // negative or huge exponents only give integers for the bases -1, 0 and 1,
+                // where only the parity of the exponent matters
+                } else if left == T::ONE || Some(left) == T::ONE.checked_neg() {
+                    if right & T::ONE == T::ONE {
+                        left
+                    } else {
+                        T::ONE
+                    }
+                } else if right < T::ZERO {
+                    return Err(NotAnInteger(
+                        left.to_f64().powf(right.to_f64()),
+                    ));
+                } else {
+                    return Err(Overflow);
+                }
+            },
+            Operator::BitAnd => left & right,
+            Operator::BitOr => left | right,
+            Operator::BitXor => left ^ right,
+            Operator::ShiftLeft => {
+                let amount = right.to_u32().ok_or(Overflow)?;
+                let res = left.checked_shl(amount).ok_or(Overflow)?;
+                // bits shifted out of the integer are an overflow
+                if res.checked_shr(amount) != Some(left) {
+                    return Err(Overflow);
+                }
+                res
+            },
+            Operator::ShiftRight => {
+                let amount = right.to_u32().ok_or(Overflow)?;
+                left.checked_shr(amount).ok_or(Overflow)?
+            },
+            Operator::Factorial => unreachable!(),
+        };
+
+        Ok(res)
+    }
+
+    /// Evaluates an element within an expression and returns the result.
+    pub(crate) fn eval_element(
+        &self,
+        element: &Element,
+    ) -> Result<T, EvalError> {
+        let res = match *element {
+            Element::Number(num) => {
+                to_int(num, || FailedNode::new(element, &[]))?
+            },
+            Element::Integer(int) => T::from_u64(int).ok_or_else(
+                #[cold]
+                || EvalError::Overflow(FailedNode::new(element, &[])),
+            )?,
+            Element::Variable(name) => *self.variables.get(name).ok_or_else(
+                #[cold]
+                || EvalError::MissingVariable(name.to_owned()),
+            )?,
+            Element::UnOp(ref unop) => {
+                let operand = self.eval_element(&unop.operand)?;
+                let node = || FailedNode::new(element, &[operand.to_f64()]);
+                #[allow(clippy::unreachable)]
+                match unop.op {
+                    Operator::Plus => operand,
+                    Operator::Minus => operand
+                        .checked_neg()
+                        .ok_or_else(|| EvalError::Overflow(node()))?,
+                    // the float factorial is exact for every result fitting in 64 bits
+                    Operator::Factorial => {
+                        to_int(factorial(operand.to_f64()), node)?
+                    },
+                    Operator::Times
+                    | Operator::Divide
+                    | Operator::IntDivide
+                    | Operator::Power
+                    | Operator::Modulo
+                    | Operator::BitAnd
+                    | Operator::BitOr
+                    | Operator::BitXor
+                    | Operator::ShiftLeft
+                    | Operator::ShiftRight => unreachable!(),
+                }
+            },
+            Element::BinOp(ref binop) => {
+                let left = self.eval_element(&binop.lhs)?;
+                let right = self.eval_element(&binop.rhs)?;
+                Self::eval_binop(binop.op, left, right).map_err(
+                    #[cold]
+                    |kind| {
+                        let node = FailedNode::new(
+                            element,
+                            &[left.to_f64(), right.to_f64()],
+                        );
+                        kind.into_error(node)
+                    },
+                )?
+            },
+            Element::Function(ref func) => {
+                // functions work on floats, their arguments must be exact
+                // and their result must be an integer
+                let ints = func
+                    .args
+                    .iter()
+                    .map(|arg| self.eval_element(arg))
+                    .collect::<Result<Vec<_>, EvalError>>()?;
+                let args: Vec<f64> =
+                    ints.iter().map(|&int| int.to_f64()).collect();
+                if ints
+                    .iter()
+                    .zip(&args)
+                    .any(|(&int, &arg)| T::from_f64(arg) != Some(int))
+                {
+                    yeet!(EvalError::PrecisionLoss(FailedNode::new(
+                        element, &args
+                    )));
+                }
+                to_int(func.call(&args), || FailedNode::new(element, &args))?
+            },
+        };
+
+        Ok(res)
+    }
+
+    /// Creates a new [`IntXprsImpl`] instance.
+    pub(crate) const fn new<'a>(
+        variables: &'a HashMap<&str, T>,
+    ) -> IntXprsImpl<'a, T> {
+        IntXprsImpl { variables }
+    }
+}
+
+/// The kind of error an integer operation can fail with,
+/// turned into an [`EvalError`] once the failing node is known.
+enum IntErrorKind {
+    /// A division or a modulo by zero.
+    DivisionByZero,
+    /// A value isn't an integer.
+    NotAnInteger(f64),
+    /// A result doesn't fit in the integer type.
+    Overflow,
+}
+
+impl IntErrorKind {
+    /// Returns the reason why a float can't be converted into an integer.
+    fn from_f64(num: f64) -> Self {
+        const ZERO: f64 = 0.0;
+        if num.is_infinite() || num.fract() == ZERO {
+            Self::Overflow
+        } else {
+            Self::NotAnInteger(num)
+        }
+    }
+
+    /// Creates the [`EvalError`] corresponding to the kind.
+    fn into_error(self, node: FailedNode) -> EvalError {
+        match self {
+            Self::DivisionByZero => EvalError::DivisionByZero(node),
+            Self::Overflow => EvalError::Overflow(node),
+            Self::NotAnInteger(num) => EvalError::NotAnInteger(num),
+        }
+    }
+}
+
+/// Converts a float into an integer, failing with the appropriate [`EvalError`].
+fn to_int<T: Integer>(
+    num: f64,
+    node: impl FnOnce() -> FailedNode,
+) -> Result<T, EvalError> {
+    T::from_f64(num).ok_or_else(
+        #[cold]
+        || IntErrorKind::from_f64(num).into_error(node()),
+    )
+}
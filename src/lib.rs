@@ -38,6 +38,7 @@
 //!   It also works on functions (e.g. `sin(0)` will be transformed into `0`) and "logical" result like `(x - x) * (....)` will be transformed into `0` since `x - x` is `0` no matter what `x` is.
 //!
 //!   Note: nightly channel enables even more optimizations thanks to `box_patterns` feature gate.
+//!   Note2: operations resulting in `f64::NAN` or infinities (e.g. `1 / 0`) are not folded so they can be reported by `eval_strict`.
 //!
 //! <br />
 //!
@@ -67,6 +68,38 @@
 //!
 //!   Note: `Display` and `Debug` shows additional parenthesis to make the order of operations more obvious.
 //!
+//! <br />
+//!
+//! - **`stats`**:
+//!
+//!   Adds a statistical and special functions pack, registered with `Context::with_stats_functions`:
+//!   `median`, `var` (sample variance), `stddev`, `erf`, `erfc`, `beta`, `lgamma`, `binomial(n, k)`, `normal_pdf(x, mu = 0, sigma = 1)`, `normal_cdf(x, mu = 0, sigma = 1)`,
+//!   `clamp(x, min, max)`, `sign`, `lerp(start, end, ratio)`, `deg` and `rad` (angle conversions), `gcd` and `lcm`.
+//!
+//! <br />
+//!
+//! - **`finance`**:
+//!
+//!   Adds a financial functions pack, registered under a namespace with `Context::with_finance_functions` (`fin.pmt(rate, nper, pv)`), following the spreadsheets' conventions (money paid out is negative, `when` is `0` for payments at the end of the periods and `1` at their beginning):
+//!   `pv(rate, nper, pmt, fv = 0, when = 0)`, `fv(rate, nper, pmt, pv = 0, when = 0)`, `pmt(rate, nper, pv, fv = 0, when = 0)`, `nper(rate, pmt, pv, fv = 0, when = 0)`,
+//!   `rate(nper, pmt, pv, fv = 0, when = 0, guess = 0.1)`, `npv(rate, flows...)`, `irr(flows...)` (`rate` and `irr` are found iteratively and are `NaN` if they don't converge) and `compound(principal, rate, periods, frequency = 1)`.
+//!
+//! <br />
+//!
+//! - **`physics`**:
+//!
+//!   Adds a pack of physical constants (CODATA 2018 recommended values in SI units), registered under a namespace with `Context::with_physical_constants`:
+//!   `c`, `h`, `hbar`, `e_charge`, `k_B`, `N_A`, `R`, `F`, `sigma`, `G`, `alpha`, `eps0`, `mu0`, `m_e`, `m_p`, `m_n`, `m_u`, `a_0`, `R_inf`, `g_n` and `atm`.
+//!   They are constants and get folded when parsing (`phys.h * phys.c` is a single number), their uncertainties and units are listed in the `physics` module.
+//!
+//! <br />
+//!
+//! - **`serde`**:
+//!
+//!   Implements `Serialize` and `Deserialize` for `Xprs` (as its structured AST, e.g. `{"bin_op": {"lhs": {"number": 2.0}, "op": "times", "rhs": {"variable": "x"}}}`) and `Context` (variables, function references, namespaces and settings, but not its parent).
+//!   Functions are referenced by name since they can't be serialized, plain deserialization only resolves the built-in ones, use `Context::seed` to resolve them against a registry context.
+//!   Names are borrowed from the input, and non-finite numbers are the strings `"inf"`, `"-inf"` and `"nan"` in human-readable formats like JSON.
+//!
 //! ## Usage
 //!
 //! ### Simple examples
@@ -83,6 +116,8 @@
 //! ```
 //!
 //! Note: Numbers are parsed as [`f64`] so you can use scientific notation (e.g. `1e-3`) with underscores (e.g. `1_000_000e2`).
+//! Hexadecimal (`0xFF`), binary (`0b1010`) and octal (`0o17`) integer literals are supported as long as they fit in a [`u64`],
+//! they are kept exact for [`Xprs::eval_int`].
 //!
 //! If you want to evaluate a calculus that contains variables, you can use the `eval` method (or `eval_unchecked` if you know for sure you're not missing any variables):
 //!
@@ -98,6 +133,55 @@
 //! }
 //! ```
 //!
+//! If your values don't live in a `HashMap` (e.g. a struct or a database row), the `eval_with` method takes a [`VariableResolver`] instead, which only resolves the variables reached by the evaluation.
+//! It is implemented for `HashMap`s, `BTreeMap`s, slices and arrays of `(name, value)` pairs and closures:
+//!
+//! ```rust
+//! use xprs::Xprs;
+//!
+//! fn main() {
+//!     let xprs = Xprs::try_from("price * quantity").unwrap();
+//!     let resolver = |name: &str| match name {
+//!         "price" => Some(2.5),
+//!         "quantity" => Some(4.0),
+//!         _ => None,
+//!     };
+//!     println!("price * quantity = {}", xprs.eval_with(&resolver).unwrap());
+//!     println!("price * quantity = {}", xprs.eval_with(&[("price", 2.5), ("quantity", 4.0)]).unwrap());
+//! }
+//! ```
+//!
+//! By default, invalid operations silently result in `f64::NAN` or infinities (e.g. `sqrt(-1)` or `1 / 0`).
+//! If you'd rather get an error telling you which part of the calculus failed, you can use the `eval_strict` method:
+//!
+//! ```rust
+//! use xprs::Xprs;
+//!
+//! fn main() {
+//!     let xprs = Xprs::try_from("1 + ln(x)").unwrap();
+//!     // Evaluation error: domain error in `ln(x)`
+//!     println!("{}", xprs.eval_strict(&[("x", 0.0)].into()).unwrap_err());
+//! }
+//! ```
+//!
+//! If you need to know how a result was obtained, the `eval_trace` method records the value of every part of the calculus:
+//!
+//! ```rust
+//! use xprs::Xprs;
+//!
+//! fn main() {
+//!     let xprs = Xprs::try_from("1 + sin(x) * 3").unwrap();
+//!     let trace = xprs.eval_trace(&[("x", 2.0)].into()).unwrap();
+//!     // (1 + (sin(x) * 3)) = 3.727892280477045
+//!     //   1
+//!     //   (sin(x) * 3) = 2.727892280477045
+//!     //     sin(x) = 0.9092974268256817
+//!     //       x = 2
+//!     //     3
+//!     print!("{trace}");
+//! }
+//! ```
+//!
 //! You can also turn the calculus into a function and use it later:
 //!
 //! ```rust
@@ -113,6 +197,80 @@
 //! You can use functions `bind`, `bind2` etc up to `bind9` to bind variables to the calculus.
 //! If you ever need more, you can use the `bind_n` and `bind_n_runtime` methods which takes an array of size N or a slice respectively.
 //!
+//! If you evaluate a large expression repeating the same sub-expressions many times, you can use the `to_dag` method.
+//! It shares the structurally equal sub-expressions (e.g. `sin(x + 2y)` and `sin(2y + x)`) so each of them is only evaluated once:
+//!
+//! ```rust
+//! use xprs::Xprs;
+//!
+//! fn main() {
+//!     let dag = Xprs::try_from("sin(x + 2y) * cos(x + 2y) + sin(2y + x)")
+//!         .unwrap()
+//!         .to_dag();
+//!     println!("result = {}", dag.eval(&[("x", 1.0), ("y", 2.0)].into()).unwrap());
+//! }
+//! ```
+//!
+//! If you need exact integer arithmetic (e.g. for register calculations), you can use the `eval_int` method with [`i64`] or [`u64`] values.
+//! Every operation is checked, overflows and divisions by zero are reported as an [`EvalError`]:
+//!
+//! ```rust
+//! use xprs::Xprs;
+//!
+//! fn main() {
+//!     let xprs = Xprs::try_from("(reg >> 4) & 0xF").unwrap();
+//!     println!(
+//!         "(reg >> 4) & 0xF = {:#x}",
+//!         xprs.eval_int::<u64>(&[("reg", 0xAB)].into()).unwrap()
+//!     );
+//! }
+//! ```
+//!
+//! If you parse many expressions at startup, you can cache them with the `to_bytes` method, a compact and versioned binary encoding.
+//! `Xprs::from_bytes` loads them back without parsing, checking them against a context (known functions, no variable defined by the context):
+//!
+//! ```rust
+//! use xprs::{Parser, Xprs};
+//!
+//! fn main() {
+//!     let parser = Parser::default();
+//!     let bytes = parser.parse("2 * sin(x) + 1").unwrap().to_bytes();
+//!     let xprs = Xprs::from_bytes(&bytes, parser.ctx()).unwrap();
+//!     println!("2 * sin(x) + 1 = {}", xprs.eval(&[("x", 0.5)].into()).unwrap());
+//! }
+//! ```
+//!
+//! The tree can also be exported as an S-expression (`to_sexpr`, also printed by the REPL) or as a JSON AST (`to_json`) for tools in other languages,
+//! and imported back with `Xprs::from_sexpr` and `Xprs::from_json`, checked against a context the same way:
+//!
+//! ```rust
+//! use xprs::{Parser, Xprs};
+//!
+//! fn main() {
+//!     let parser = Parser::default();
+//!     let xprs = parser.parse("2 * sin(x) + 1").unwrap();
+//!     println!("{}", xprs.to_sexpr()); // (+ (* 2 (sin x)) 1)
+//!     println!("{}", xprs.to_json()); // {"type":"binary","op":"+","lhs":{...},"rhs":{"type":"number","value":1}}
+//!     let imported = Xprs::from_sexpr("(+ (* 2 (sin x)) 1)", parser.ctx()).unwrap();
+//!     assert_eq!(imported, xprs);
+//! }
+//! ```
+//!
+//! To draw the tree (e.g. when debugging precedence), `to_dot` renders it as a [Graphviz](https://graphviz.org) DOT graph,
+//! and `to_dot_diff` highlights the nodes that differ from another expression, like before and after a simplification:
+//!
+//! ```rust
+//! use xprs::Xprs;
+//!
+//! fn main() {
+//!     let xprs = Xprs::try_from("sin(x * y) + z").unwrap();
+//!     let simplified = xprs.clone().simplify_for(("y", 1.0));
+//!     // render them with `dot -Tsvg`
+//!     println!("{}", xprs.to_dot_diff(&simplified));
+//!     println!("{}", simplified.to_dot_diff(&xprs));
+//! }
+//! ```
+//!
 //! Notes:
 //! All `bind` function (except `bind_n_runtime`) returns a [`Result`] of a function which is guaranteed to return a [`f64`].
 //! `bind_n_runtime` returns a [`Result`] of a function which also returns a [`Result`] of a [`f64`] since there are no guarantees that the array/slice will be of the correct size.
@@ -125,6 +283,7 @@
 //!
 //! Functions need to have a signature of `fn(&[f64]) -> f64` so they all have the same signature and can be called the same way.
 //! We also need a name and the number of arguments the function takes, which is an [`Option<usize>`], if [`None`] then the function can take any number of arguments.
+//! If your function takes a range of arguments, you can set its [`Arity`] (minimum and maximum number of arguments) with `with_arity`, or give default values to its last parameters with `with_defaults`.
 //! You can define functions like so:
 //!
 //! ```rust
@@ -145,6 +304,18 @@
 //! const SUM: Function = Function::new_static("sum", variadic_sum, None);
 //! // or with the macro (no wrapping is done for variadic functions)
 //! const SUM_MACRO: Function = xprs_fn!("sum", variadic_sum);
+//! // between 1 and 3 arguments, or at least 1 argument (no wrapping is done either)
+//! const NORM: Function = xprs_fn!("norm", |args: &[f64]| args.iter().map(|x| x * x).sum::<f64>().sqrt(), 1..=3);
+//! const SUM_NON_EMPTY: Function = xprs_fn!("sum", variadic_sum, 1..);
+//!
+//! fn scale(x: f64, factor: f64) -> f64 {
+//!     x * factor
+//! }
+//!
+//! // `factor` defaults to 2 when omitted (`scale(x)` is `scale(x, 2)`)
+//! const SCALE: Function = xprs_fn!("scale", scale, 2).with_defaults(&[2.0]);
+//! // parameters can be named so arguments can be given by name: `scale(factor = 3, x = 1)`
+//! const NAMED_SCALE: Function = xprs_fn!("scale", scale, 2).with_params(&["x", "factor"]);
 //!
 //! // if a functions captures a variable (cannot be coerced to a static function)
 //! const X: f64 = 42.0;
@@ -155,6 +326,25 @@
 //!     // or with the macro (will do an automatic wrapping)
 //!     let CAPTURES_MACRO: Function = xprs_fn!("captures", dyn captures, 1);
 //! }
+//!
+//! // names don't have to be static (e.g. functions loaded from a config file)
+//! fn load_function(name: String, param: String, factor: f64) -> Function {
+//!     Function::new_dyn(name, move |args| args[0] * factor, Some(1)).with_owned_params([param])
+//! }
+//! ```
+//!
+//! Functions are assumed to be pure and deterministic, calls with constant arguments are evaluated at parse time (see `compile-time-optimizations` feature).
+//! If a function has side effects or reads a clock, a random source or some mutable state, mark it with `impure` or `non_deterministic`, it will then be called on every evaluation:
+//!
+//! ```rust
+//! use xprs::{Function, xprs_fn};
+//!
+//! fn read_sensor(id: f64) -> f64 {
+//!     // actually reads the sensor
+//!     id
+//! }
+//!
+//! const SENSOR: Function = xprs_fn!("sensor", read_sensor, 1).non_deterministic();
 //! ```
 //!
 //! To use a [`Context`] and a [`Parser`] you can do the following:
@@ -164,9 +354,9 @@
 //!
 //! fn main() {
 //!     let mut context = Context::default()
-//!         .with_fn(xprs_fn!("double", |x| 2. * x, 1))
-//!         .with_var("foo", 1.0);
-//!     context.set_var("bar", 2.0);
+//!         .with_fn(xprs_fn!("double", |x| 2. * x, 1)).unwrap()
+//!         .with_var("foo", 1.0).unwrap();
+//!     context.set_var("bar", 2.0).unwrap();
 //!
 //!     let xprs = Parser::new_with_ctx(context)
 //!         .parse("double(foo) + bar")
@@ -177,6 +367,46 @@
 //!
 //! Note: [`Context`] is just a wrapper around a `HashMap` so you cannot have a function and a constant with the same name (the last one will override the first one).
 //!
+//! Contexts can be layered: a child [`Context`] references a parent shared via an [`Arc`](std::sync::Arc), lookups fall through to the parent and the child can explicitly shadow the parent's symbols with [`Context::shadow`].
+//! This way a shared base of functions and constants can be reused with a cheap per-request layer:
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use xprs::{Context, Parser};
+//!
+//! fn main() {
+//!     let base = Arc::new(Context::default().with_var("rate", 0.2).unwrap());
+//!
+//!     for amount in [10.0, 20.0] {
+//!         let request = Context::default()
+//!             .with_parent(Arc::clone(&base)).unwrap()
+//!             .with_var("amount", amount).unwrap();
+//!         let xprs = Parser::new_with_ctx(request).parse("amount * (1 + rate)").unwrap();
+//!         println!("{amount} -> {}", xprs.eval_no_vars().unwrap());
+//!     }
+//! }
+//! ```
+//!
+//! To avoid name collisions between libraries, symbols can be registered under namespaces (which are [`Context`]s themselves) and used with their qualified name, like `math.sin` or `phys.c`.
+//! A namespace can be imported so its symbols can be used without qualification, a name defined by several imported namespaces has to be qualified.
+//! Unknown namespaces, unknown qualified symbols and ambiguous names are reported as parsing errors with suggestions.
+//!
+//! ```rust
+//! use xprs::{xprs_fn, Context, Parser};
+//!
+//! fn main() {
+//!     let context = Context::default()
+//!         .with_namespace("phys", Context::default().with_var("c", 299_792_458.0).unwrap())
+//!         .with_namespace("math", Context::default().with_fn(xprs_fn!("double", |x| 2. * x, 1)).unwrap())
+//!         .with_import("math");
+//!     let parser = Parser::new_with_ctx(context);
+//!
+//!     let xprs = parser.parse("double(phys.c) + math.double(1)").unwrap();
+//!     println!("double(phys.c) + math.double(1) = {}", xprs.eval_no_vars().unwrap());
+//!     println!("{}", parser.parse("phys.h").unwrap_err()); // Unknown symbol in namespace: `phys.h`
+//! }
+//! ```
+//!
 //! You can also use the [`Context`] to restrict the allowed variables in the calculus:
 //!
 //! ```rust
@@ -195,6 +425,31 @@
 //! }
 //! ```
 //!
+//! The [`context!`] macro builds a [`Context`] declaratively, functions are either Rust functions with their number of arguments or expressions of their parameters:
+//!
+//! ```rust
+//! use xprs::{context, Parser};
+//!
+//! fn hypot(x: f64, y: f64) -> f64 {
+//!     x.hypot(y)
+//! }
+//!
+//! fn main() {
+//!     let context = context! {
+//!         x = 1.0,
+//!         y = 2.0,
+//!         f(a, b) = a * b + 1,
+//!         g = hypot/2,
+//!         expect [z, w],
+//!     };
+//!
+//!     let xprs = Parser::new_with_ctx(context)
+//!         .parse("f(x, y) + g(z, w)")
+//!         .unwrap();
+//!     println!("f(x, y) + g(3, 4) = {}", xprs.eval(&[("z", 3.0), ("w", 4.0)].into()).unwrap());
+//! }
+//! ```
+//!
 //! ### Error handling
 //!
 //! All errors are implemented using the [`thiserror`](https://crates.io/crates/thiserror).
@@ -207,30 +462,70 @@
 //!
 //! Xprs supports the following operations:
 //!
-//! - Binary operations: `+`, `-`, `*`, `/`, `^`, `%`.
+//! - Binary operations: `+`, `-`, `*`, `/`, `//` (integer division), `^`, `%`.
+//! - Bitwise operations: `&`, `|`, `xor`, `<<`, `>>` (lower precedence than arithmetic ones, like in C).
 //! - Unary operations: `+`, `-`, `!`.
 //!
+//! Note: bitwise operations are only supported on integers. Calling them on a float will result in `f64::NAN` (use `eval_int` for checked integer arithmetic).
 //! Note: `!` (factorial) is only supported on positive integers. Calling it on a negative integer or a float will result in `f64::NAN`. Also `-4!` is interpreted as `-(4!)` and not `(-4)!`.
 //!
 //! #### Built-in constants
 //!
-//! | Constant | Value | Approximation       |
-//! | -------- | ----- | ------------------- |
-//! | `PI`     | `π`   | `3.141592653589793` |
-//! | `E`      | `e`   | `2.718281828459045` |
+//! | Constant | Value      | Approximation       |
+//! | -------- | ---------- | ------------------- |
+//! | `pi`     | `π`        | `3.141592653589793` |
+//! | `e`      | `e`        | `2.718281828459045` |
+//! | `tau`    | `2π`       | `6.283185307179586` |
+//! | `phi`    | `φ`        | `1.618033988749895` |
+//! | `sqrt2`  | `√2`       | `1.414213562373095` |
+//! | `inf`    | `∞`        | `f64::INFINITY`     |
+//! | `nan`    | not a number | `f64::NAN`        |
+//!
+//! A [`Context`] can add its own constants (`with_constant`), hide some of them (`with_hidden_constant`), look them up case-insensitively (`with_case_insensitive_constants`) or disable the built-in constants and functions entirely (`with_built_ins(false)`), their names then being treated as variables:
+//!
+//! ```rust
+//! use xprs::{Context, Parser};
+//!
+//! fn main() {
+//!     let context = Context::default()
+//!         .with_constant("g", 9.81)
+//!         .with_hidden_constant("e")
+//!         .with_case_insensitive_constants(true);
+//!     let xprs = Parser::new_with_ctx(context).parse("PI * e * G").unwrap();
+//!     println!("PI * e * G = {}", xprs.eval(&[("e", 2.0)].into()).unwrap());
+//! }
+//! ```
 //!
 //! #### Built-in functions
 //!
 //! Xprs supports a variety of functions:
 //!
 //! - trigonometric functions: `sin`, `cos`, `tan`, `asin`, `acos`, `atan`, `atan2`, `sinh`, `cosh`, `tanh`, `asinh`, `acosh`, `atanh`.
-//! - logarithmic functions: `ln` (base 2), `log` (base 10), `logn` (base n, used as `logn(num, base)`).
+//! - logarithmic functions: `ln` (base 2), `log` (base 10), `logn` (base n, used as `logn(num, base)`, the base defaults to `e`).
 //! - power functions: `sqrt`, `cbrt`, `exp`.
-//! - rounding functions: `floor`, `ceil`, `round`, `trunc`.
+//! - rounding functions: `floor`, `ceil`, `round` (optionally to a number of decimal digits, used as `round(num, digits)`), `trunc`.
 //! - other functions: `abs`, `min`, `max`, `hypot`, `fract`, `recip` (`invert` alias), `sum`, `mean`, `factorial` and `gamma`.
+//! - random function: `rand()` (pseudo-random number in `[0, 1)`, not suited for cryptography).
 //!
 //! Note: `min` and `max` can take any number of arguments (if none, returns `f64::INFINITY` and `-f64::INFINITY` respectively).
 //! Note2: `sum` and `mean` can take any number of arguments (if none, returns `0` and `f64::NAN` respectively).
+//! Note3: functions taking no arguments are called with empty parentheses (e.g. `rand()`).
+//!
+//! Built-in functions can be overridden by defining a function with the same name in the [`Context`].
+//! For sandboxed formulas, a [`Context`] can also restrict them with an allow-list (`with_allowed_built_in_functions`) or a deny-list (`with_denied_built_in_functions`), using a forbidden function (or `!` when `factorial` is forbidden) is a parsing error:
+//!
+//! ```rust
+//! use xprs::{Context, Parser};
+//!
+//! fn main() {
+//!     let context = Context::default()
+//!         .with_denied_built_in_functions(["factorial", "gamma"].into());
+//!     let parser = Parser::new_with_ctx(context);
+//!
+//!     assert!(parser.parse("sin(2) * 3").is_ok());
+//!     println!("{}", parser.parse("factorial(170)").unwrap_err()); // Function not permitted in this context: `factorial`
+//! }
+//! ```
 //!
 //! ### Advanced examples
 //!
@@ -267,7 +562,7 @@
 //!     let xprs_hof = Xprs::try_from("2x + y").unwrap();
 //!     let fn_hof = xprs_hof.bind2("x", "y").unwrap();
 //!     let hof = xprs_fn!("hof", dyn fn_hof, 2);
-//!     let ctx = Context::default().with_fn(hof);
+//!     let ctx = Context::default().with_fn(hof).unwrap();
 //!     let parser = Parser::new_with_ctx(ctx);
 //!
 //!     let xprs = parser.parse("hof(2, 3)").unwrap();
@@ -276,6 +571,55 @@
 //! }
 //! ```
 //!
+//! ## Code generation
+//!
+//! An [`Xprs`] can be compiled into Rust source code with the `to_rust` method, for example from a build script, so hot loops don't go through the tree.
+//! The parameters of the generated function are the given variables, in order, and the operations without an [`f64`] method call the [`runtime`] helpers.
+//!
+//! ```rust
+//! use xprs::Xprs;
+//!
+//! fn main() {
+//!     let xprs = Xprs::try_from("2x + sin(y) ^ 2").unwrap();
+//!
+//!     println!("{}", xprs.to_rust("f", &["x", "y"]).unwrap());
+//!     // #[allow(unused_parens, unused_variables)]
+//!     // pub fn f(x: f64, y: f64) -> f64 {
+//!     //     ((2.0_f64 * x) + y.sin().powf(2.0_f64))
+//!     // }
+//! }
+//! ```
+//!
+//! The companion `xprs-macros` crate does it at compile time with the `xprs!` macro, `xprs!("2x + sin(y) ^ 2", x, y)` being a native `fn(f64, f64) -> f64`.
+//!
+//! The same function can be generated as C99 (`to_c`, with `math.h`) or GLSL (`to_glsl`, with scalar `float`s) code, for embedded targets and shaders.
+//! Operators and functions without an equivalent in the target (like `!`, the bitwise operators, `factorial` or the variadic `sum`) are all listed by the returned error.
+//!
+//! ```rust
+//! use xprs::Xprs;
+//!
+//! fn main() {
+//!     let xprs = Xprs::try_from("x % 2 + x ^ 3").unwrap();
+//!
+//!     println!("{}", xprs.to_c("f", &["x"]).unwrap()); // return (fmod(x, 2.0) + pow(x, 3.0));
+//!     println!("{}", xprs.to_glsl("f", &["x"]).unwrap()); // return ((x - 2.0 * trunc(x / 2.0)) + pow(x, 3.0));
+//!     println!("{}", Xprs::try_from("x! + sum(x, 1)").unwrap().to_c("f", &["x"]).unwrap_err());
+//!     // Codegen error: no equivalent in the target language for `!`, `sum`
+//! }
+//! ```
+//!
+//! To cross-check results in a notebook, `to_numpy` prints the expression as a Python expression vectorised with `numpy`, the variables being array names:
+//!
+//! ```rust
+//! use xprs::Xprs;
+//!
+//! fn main() {
+//!     let xprs = Xprs::try_from("2x + sin(y) ^ 2").unwrap();
+//!
+//!     println!("{}", xprs.to_numpy().unwrap()); // ((2.0 * x) + np.power(np.sin(y), 2.0))
+//! }
+//! ```
+//!
 //! These examples and others can be found in the [examples](./examples) directory.
 //!
 //! ## Documentation
@@ -296,8 +640,6 @@
 //! - [ ] Better CI/CD.
 //! - [ ] Remove lifetimes by replacing `&str` with something like [`byteyarn`](https://crates.io/crates/byteyarn).
 //! - [ ] Complex numbers support.
-//! - [ ] Macro for defining the [`Context`] like the one in [`evalexpr`](https://crates.io/crates/evalexpr).
-//! - [ ] Support for dynamic [`Function`] name.
 //! - [ ] Native variadics (when rust supports them in stable).
 //! - [ ] Have [`Xprs`] be generic, taking float for its return type if that's even possible (regarding the dependency on [`Context`]).
 //!
@@ -309,14 +651,35 @@
 /* Clippy config */
 #![allow(clippy::pub_use, clippy::needless_doctest_main)]
 /* Modules */
+/// The compact binary encoding module.
+mod binary;
+/// The code generation module.
+mod codegen;
 /// The context of the parser.
 mod context;
+/// The common sub-expression elimination module.
+mod dag;
+/// The Graphviz DOT export module.
+mod dot;
 /// The element of the abstract syntax tree (AST).
 mod element;
+/// The S-expression and JSON AST formats module.
+mod formats;
+/// The integer evaluation module.
+mod integer;
+/// The optional function packs module.
+mod packs;
 /// The parser module.
 mod parser;
+/// The variable resolver module.
+mod resolver;
+/// The serialization module.
+#[cfg(feature = "serde")]
+mod serialization;
 /// The token module.
 mod token;
+/// The evaluation trace module.
+mod trace;
 /// The utilities module.
 mod utils;
 /// The expression module.
@@ -325,10 +688,21 @@ mod xprs;
 #[cfg(test)]
 mod tests;
 /* Exports */
-pub use crate::context::{Context, Symbol};
+pub use crate::binary::DecodeError;
+pub use crate::codegen::{runtime, CodegenError};
+pub use crate::context::{Context, ContextError, Symbol};
+pub use crate::dag::XprsDag;
+pub use crate::formats::ImportError;
+pub use crate::integer::Integer;
+#[cfg(feature = "physics")]
+pub use crate::packs::physics;
 pub use crate::parser::{ErrorKind, ParseError, Parser};
-pub use crate::token::Function;
-pub use crate::xprs::{BindError, EvalError, Xprs};
+pub use crate::resolver::VariableResolver;
+#[cfg(feature = "serde")]
+pub use crate::serialization::ContextSeed;
+pub use crate::token::{Arity, Domain, Function, Params};
+pub use crate::trace::{Trace, TraceStep};
+pub use crate::xprs::{BindError, EvalError, FailedNode, Xprs};
 
 /* Feature safety */
 #[cfg(all(feature = "pemdas", feature = "pejmdas"))]
@@ -0,0 +1,153 @@
+/* Built-in imports */
+use core::f64;
+/* Crate imports */
+use crate::{token::Function, xprs_fn};
+
+/// The functions of the pack, see [`Context::with_finance_functions`](crate::Context::with_finance_functions).
+pub const FUNCTIONS: [Function; 8] =
+    [PV, FV, PMT, NPER, RATE, NPV, IRR, COMPOUND];
+
+/// Present value of an annuity.
+pub const PV: Function = xprs_fn!("pv", pv, 5)
+    .with_params(&["rate", "nper", "pmt", "fv", "when"])
+    .with_defaults(&[0.0_f64, 0.0_f64]);
+
+/// Future value of an annuity.
+pub const FV: Function = xprs_fn!("fv", fv, 5)
+    .with_params(&["rate", "nper", "pmt", "pv", "when"])
+    .with_defaults(&[0.0_f64, 0.0_f64]);
+
+/// Payment of an annuity.
+pub const PMT: Function = xprs_fn!("pmt", pmt, 5)
+    .with_params(&["rate", "nper", "pv", "fv", "when"])
+    .with_defaults(&[0.0_f64, 0.0_f64]);
+
+/// Number of periods of an annuity.
+pub const NPER: Function = xprs_fn!("nper", nper, 5)
+    .with_params(&["rate", "pmt", "pv", "fv", "when"])
+    .with_defaults(&[0.0_f64, 0.0_f64]);
+
+/// Interest rate per period of an annuity, found iteratively.
+pub const RATE: Function = xprs_fn!("rate", rate, 6)
+    .with_params(&["nper", "pmt", "pv", "fv", "when", "guess"])
+    .with_defaults(&[0.0_f64, 0.0_f64, 0.1_f64]);
+
+/// Net present value of cash flows at the end of each period.
+pub const NPV: Function = xprs_fn!("npv", npv, 2..);
+
+/// Internal rate of return of cash flows, the first one being at the start, found iteratively.
+pub const IRR: Function = xprs_fn!("irr", irr, 2..);
+
+/// Compound interest, value of a principal after a number of periods.
+pub const COMPOUND: Function = xprs_fn!("compound", compound, 4)
+    .with_params(&["principal", "rate", "periods", "frequency"])
+    .with_defaults(&[1.0_f64]);
+
+/// Maximum number of iterations of the solver.
+const MAX_ITERATIONS: usize = 100;
+/// Precision of the solver.
+const TOLERANCE: f64 = 1e-12;
+
+/// Growth factor of the payments, `when` being `0` (end of period) or `1` (beginning of period).
+fn payment_factor(rate: f64, when: f64) -> f64 {
+    rate.mul_add(when, 1.0_f64)
+}
+
+/// Accumulation factor of the payments, `((1 + rate)^nper - 1) / rate`.
+fn annuity_factor(rate: f64, nper: f64) -> f64 {
+    if rate == 0.0_f64 {
+        nper
+    } else {
+        (nper * rate.ln_1p()).exp_m1() / rate
+    }
+}
+
+/// The balance of an annuity, null when its values are consistent.
+fn balance(rate: f64, nper: f64, pmt: f64, pv: f64, fv: f64, when: f64) -> f64 {
+    let payments =
+        pmt * payment_factor(rate, when) * annuity_factor(rate, nper);
+    pv.mul_add((1.0_f64 + rate).powf(nper), payments) + fv
+}
+
+/// Present value of an annuity.
+fn pv(rate: f64, nper: f64, pmt: f64, fv: f64, when: f64) -> f64 {
+    let payments =
+        pmt * payment_factor(rate, when) * annuity_factor(rate, nper);
+    -(fv + payments) / (1.0_f64 + rate).powf(nper)
+}
+
+/// Future value of an annuity.
+fn fv(rate: f64, nper: f64, pmt: f64, pv: f64, when: f64) -> f64 {
+    -balance(rate, nper, pmt, pv, 0.0_f64, when)
+}
+
+/// Payment of an annuity.
+fn pmt(rate: f64, nper: f64, pv: f64, fv: f64, when: f64) -> f64 {
+    let growth = (1.0_f64 + rate).powf(nper);
+    -pv.mul_add(growth, fv)
+        / (payment_factor(rate, when) * annuity_factor(rate, nper))
+}
+
+/// Number of periods of an annuity.
+fn nper(rate: f64, pmt: f64, pv: f64, fv: f64, when: f64) -> f64 {
+    if rate == 0.0_f64 {
+        return -(pv + fv) / pmt;
+    }
+    let payment = pmt * payment_factor(rate, when);
+    (fv.mul_add(-rate, payment) / pv.mul_add(rate, payment)).ln() / rate.ln_1p()
+}
+
+/// Interest rate per period of an annuity, `NaN` if the solver doesn't converge.
+fn rate(nper: f64, pmt: f64, pv: f64, fv: f64, when: f64, guess: f64) -> f64 {
+    solve(|rate| balance(rate, nper, pmt, pv, fv, when), guess)
+}
+
+/// Net present value of cash flows at the end of each period.
+fn npv(args: &[f64]) -> f64 {
+    let (&rate, flows) = args.split_first().unwrap_or((&f64::NAN, &[]));
+    discount(rate, flows) / (1.0_f64 + rate)
+}
+
+/// Internal rate of return of cash flows, `NaN` if the solver doesn't converge.
+fn irr(flows: &[f64]) -> f64 {
+    solve(|rate| discount(rate, flows), 0.1_f64)
+}
+
+/// Value at the first period of cash flows, one per period.
+fn discount(rate: f64, flows: &[f64]) -> f64 {
+    // Horner's method, from the last cash flow to the first one
+    let factor = (1.0_f64 + rate).recip();
+    flows
+        .iter()
+        .rev()
+        .fold(0.0_f64, |acc, &flow| acc.mul_add(factor, flow))
+}
+
+/// Compound interest, value of a principal after a number of periods
+/// with interests compounded `frequency` times per period.
+fn compound(principal: f64, rate: f64, periods: f64, frequency: f64) -> f64 {
+    principal * (periods * frequency * (rate / frequency).ln_1p()).exp()
+}
+
+/// Finds a root of the function with the secant method, `NaN` if it doesn't converge.
+fn solve(func: impl Fn(f64) -> f64, guess: f64) -> f64 {
+    let mut prev = guess;
+    let mut prev_value = func(prev);
+    // second starting point, close to the guess
+    let mut current = guess.mul_add(1.1_f64, 1e-4_f64);
+    for _ in 0..MAX_ITERATIONS {
+        let value = func(current);
+        if value == 0.0_f64 {
+            return current;
+        }
+        let next = current - value * (current - prev) / (value - prev_value);
+        if !next.is_finite() {
+            break;
+        }
+        if (next - current).abs() < TOLERANCE {
+            return next;
+        }
+        (prev, prev_value, current) = (current, value, next);
+    }
+    f64::NAN
+}
@@ -0,0 +1,10 @@
+/* Modules */
+/// Module containing the financial functions pack.
+#[cfg(feature = "finance")]
+pub mod finance;
+/// Module containing the physical constants pack.
+#[cfg(feature = "physics")]
+pub mod physics;
+/// Module containing the statistical and special functions pack.
+#[cfg(feature = "stats")]
+pub mod stats;
@@ -0,0 +1,137 @@
+//! Physical constants, CODATA 2018 recommended values in SI units.
+//!
+//! | Name | Value | Standard uncertainty | Unit | Description |
+//! | --- | --- | --- | --- | --- |
+//! | `c` | 299 792 458 | exact | m s⁻¹ | Speed of light in vacuum |
+//! | `h` | 6.626 070 15 × 10⁻³⁴ | exact | J Hz⁻¹ | Planck constant |
+//! | `hbar` | 1.054 571 817 × 10⁻³⁴ | exact (truncated) | J s | Reduced Planck constant |
+//! | `e_charge` | 1.602 176 634 × 10⁻¹⁹ | exact | C | Elementary charge |
+//! | `k_B` | 1.380 649 × 10⁻²³ | exact | J K⁻¹ | Boltzmann constant |
+//! | `N_A` | 6.022 140 76 × 10²³ | exact | mol⁻¹ | Avogadro constant |
+//! | `R` | 8.314 462 618 | exact (truncated) | J mol⁻¹ K⁻¹ | Molar gas constant |
+//! | `F` | 96 485.332 12 | exact (truncated) | C mol⁻¹ | Faraday constant |
+//! | `sigma` | 5.670 374 419 × 10⁻⁸ | exact (truncated) | W m⁻² K⁻⁴ | Stefan-Boltzmann constant |
+//! | `G` | 6.674 30 × 10⁻¹¹ | 0.000 15 × 10⁻¹¹ | m³ kg⁻¹ s⁻² | Newtonian gravitation |
+//! | `alpha` | 7.297 352 5693 × 10⁻³ | 0.000 000 0011 × 10⁻³ |  | Fine-structure constant |
+//! | `eps0` | 8.854 187 8128 × 10⁻¹² | 0.000 000 0013 × 10⁻¹² | F m⁻¹ | Vacuum electric permittivity |
+//! | `mu0` | 1.256 637 062 12 × 10⁻⁶ | 0.000 000 000 19 × 10⁻⁶ | N A⁻² | Vacuum magnetic permeability |
+//! | `m_e` | 9.109 383 7015 × 10⁻³¹ | 0.000 000 0028 × 10⁻³¹ | kg | Electron mass |
+//! | `m_p` | 1.672 621 923 69 × 10⁻²⁷ | 0.000 000 000 51 × 10⁻²⁷ | kg | Proton mass |
+//! | `m_n` | 1.674 927 498 04 × 10⁻²⁷ | 0.000 000 000 95 × 10⁻²⁷ | kg | Neutron mass |
+//! | `m_u` | 1.660 539 066 60 × 10⁻²⁷ | 0.000 000 000 50 × 10⁻²⁷ | kg | Atomic mass constant |
+//! | `a_0` | 5.291 772 109 03 × 10⁻¹¹ | 0.000 000 000 80 × 10⁻¹¹ | m | Bohr radius |
+//! | `R_inf` | 10 973 731.568 160 | 0.000 021 | m⁻¹ | Rydberg constant |
+//! | `g_n` | 9.806 65 | exact (conventional) | m s⁻² | Standard acceleration of gravity |
+//! | `atm` | 101 325 | exact (conventional) | Pa | Standard atmosphere |
+
+/// The constants of the pack, see [`Context::with_physical_constants`](crate::Context::with_physical_constants).
+pub const CONSTANTS: [PhysicalConstant; 21] = [
+    PhysicalConstant::new("c", 299_792_458.0, 0.0, "m s^-1"),
+    PhysicalConstant::new("h", 6.626_070_15e-34, 0.0, "J Hz^-1"),
+    PhysicalConstant::new("hbar", 1.054_571_817e-34, 0.0, "J s"),
+    PhysicalConstant::new("e_charge", 1.602_176_634e-19, 0.0, "C"),
+    PhysicalConstant::new("k_B", 1.380_649e-23, 0.0, "J K^-1"),
+    PhysicalConstant::new("N_A", 6.022_140_76e23, 0.0, "mol^-1"),
+    PhysicalConstant::new("R", 8.314_462_618, 0.0, "J mol^-1 K^-1"),
+    PhysicalConstant::new("F", 96_485.332_12, 0.0, "C mol^-1"),
+    PhysicalConstant::new("sigma", 5.670_374_419e-8, 0.0, "W m^-2 K^-4"),
+    PhysicalConstant::new("G", 6.674_30e-11, 0.000_15e-11, "m^3 kg^-1 s^-2"),
+    PhysicalConstant::new("alpha", 7.297_352_569_3e-3, 0.000_000_001_1e-3, ""),
+    PhysicalConstant::new(
+        "eps0",
+        8.854_187_812_8e-12,
+        0.000_000_001_3e-12,
+        "F m^-1",
+    ),
+    PhysicalConstant::new(
+        "mu0",
+        1.256_637_062_12e-6,
+        0.000_000_000_19e-6,
+        "N A^-2",
+    ),
+    PhysicalConstant::new(
+        "m_e",
+        9.109_383_701_5e-31,
+        0.000_000_002_8e-31,
+        "kg",
+    ),
+    PhysicalConstant::new(
+        "m_p",
+        1.672_621_923_69e-27,
+        0.000_000_000_51e-27,
+        "kg",
+    ),
+    PhysicalConstant::new(
+        "m_n",
+        1.674_927_498_04e-27,
+        0.000_000_000_95e-27,
+        "kg",
+    ),
+    PhysicalConstant::new(
+        "m_u",
+        1.660_539_066_60e-27,
+        0.000_000_000_50e-27,
+        "kg",
+    ),
+    PhysicalConstant::new(
+        "a_0",
+        5.291_772_109_03e-11,
+        0.000_000_000_80e-11,
+        "m",
+    ),
+    PhysicalConstant::new("R_inf", 10_973_731.568_160, 0.000_021, "m^-1"),
+    PhysicalConstant::new("g_n", 9.806_65, 0.0, "m s^-2"),
+    PhysicalConstant::new("atm", 101_325.0, 0.0, "Pa"),
+];
+
+/// A physical constant with its CODATA value, see the [module documentation](self).
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[non_exhaustive]
+pub struct PhysicalConstant {
+    /// The name of the constant, as used in expressions.
+    pub name: &'static str,
+    /// The standard uncertainty of the value, `0` for exact constants.
+    pub uncertainty: f64,
+    /// The SI unit of the constant, empty for dimensionless ones.
+    pub unit: &'static str,
+    /// The value of the constant in SI units.
+    pub value: f64,
+}
+
+impl PhysicalConstant {
+    /// Whether the value of the constant is exact (by definition of the SI units or by convention).
+    #[inline]
+    #[must_use]
+    pub fn is_exact(&self) -> bool {
+        self.uncertainty == 0.0_f64
+    }
+
+    /// Creates a new physical constant.
+    const fn new(
+        name: &'static str,
+        value: f64,
+        uncertainty: f64,
+        unit: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            uncertainty,
+            unit,
+            value,
+        }
+    }
+
+    /// Returns the relative standard uncertainty of the value.
+    #[inline]
+    #[must_use]
+    pub fn relative_uncertainty(&self) -> f64 {
+        self.uncertainty / self.value.abs()
+    }
+}
+
+/// Returns a physical constant of the pack by name.
+#[inline]
+#[must_use]
+pub fn get(name: &str) -> Option<PhysicalConstant> {
+    CONSTANTS.into_iter().find(|constant| constant.name == name)
+}
@@ -0,0 +1,334 @@
+/* Built-in imports */
+use core::f64;
+/* Crate imports */
+use crate::{token::Function, utils::factorial::gamma, xprs_fn};
+
+/// The functions of the pack, see [`Context::with_stats_functions`](crate::Context::with_stats_functions).
+pub const FUNCTIONS: [Function; 17] = [
+    MEDIAN, VAR, STDDEV, ERF, ERFC, BETA, LGAMMA, BINOMIAL, NORMAL_PDF,
+    NORMAL_CDF, CLAMP, SIGN, LERP, DEG, RAD, GCD, LCM,
+];
+
+/// Median of a list of numbers.
+pub const MEDIAN: Function = xprs_fn!("median", median, 1..);
+
+/// Sample variance of a list of numbers.
+pub const VAR: Function = xprs_fn!("var", variance, 2..);
+
+/// Sample standard deviation of a list of numbers.
+pub const STDDEV: Function =
+    xprs_fn!("stddev", |args| variance(args).sqrt(), 2..);
+
+/// Error function.
+pub const ERF: Function = xprs_fn!("erf", erf, 1);
+
+/// Complementary error function.
+pub const ERFC: Function = xprs_fn!("erfc", erfc, 1);
+
+/// Beta function.
+pub const BETA: Function =
+    xprs_fn!("beta", beta, 2).with_domain("both arguments > 0");
+
+/// Natural logarithm of the absolute value of the gamma function.
+pub const LGAMMA: Function = xprs_fn!("lgamma", ln_gamma, 1);
+
+/// Binomial coefficient, number of ways to choose `k` elements among `n`.
+pub const BINOMIAL: Function = xprs_fn!("binomial", binomial, 2)
+    .with_params(&["n", "k"])
+    .with_domain("n and k are non-negative integers");
+
+/// Probability density function of the normal distribution,
+/// the standard one by default.
+pub const NORMAL_PDF: Function = xprs_fn!("normal_pdf", normal_pdf, 3)
+    .with_params(&["x", "mu", "sigma"])
+    .with_defaults(&[0.0_f64, 1.0_f64]);
+
+/// Cumulative distribution function of the normal distribution,
+/// the standard one by default.
+pub const NORMAL_CDF: Function = xprs_fn!("normal_cdf", normal_cdf, 3)
+    .with_params(&["x", "mu", "sigma"])
+    .with_defaults(&[0.0_f64, 1.0_f64]);
+
+/// Restricts a number to an interval.
+pub const CLAMP: Function = xprs_fn!("clamp", clamp, 3)
+    .with_params(&["x", "min", "max"])
+    .with_domain("min <= max");
+
+/// Sign of a number (`-1`, `0` or `1`).
+pub const SIGN: Function = xprs_fn!("sign", sign, 1);
+
+/// Linear interpolation between two numbers.
+pub const LERP: Function =
+    xprs_fn!("lerp", lerp, 3).with_params(&["start", "end", "ratio"]);
+
+/// Converts radians to degrees.
+pub const DEG: Function = xprs_fn!("deg", f64::to_degrees, 1);
+
+/// Converts degrees to radians.
+pub const RAD: Function = xprs_fn!("rad", f64::to_radians, 1);
+
+/// Greatest common divisor of a list of integers.
+pub const GCD: Function = xprs_fn!("gcd", |args| fold_integers(args, gcd), 1..)
+    .with_domain("integers");
+
+/// Least common multiple of a list of integers.
+pub const LCM: Function = xprs_fn!("lcm", |args| fold_integers(args, lcm), 1..)
+    .with_domain("integers");
+
+/// Error function.
+fn erf(num: f64) -> f64 {
+    error_functions(num).0
+}
+
+/// Complementary error function, accurate for large values unlike `1 - erf(x)`.
+fn erfc(num: f64) -> f64 {
+    error_functions(num).1
+}
+
+/// Error function and its complement, with W. J. Cody's rational approximations
+/// ("Rational Chebyshev approximations for the error function", 1969),
+/// accurate to about `1e-16`.
+/// The most accurate of the two is approximated, the other one is deduced from it.
+fn error_functions(num: f64) -> (f64, f64) {
+    /// Below this value the error function is approximated, above it its complement is.
+    const THRESHOLD: f64 = 0.468_75;
+    /// Above this value the complement underflows.
+    const MAX: f64 = 26.543;
+    /// Numerator for `|x| <= 0.46875`, in `x^2`.
+    const SMALL_NUM: [f64; 5] = [
+        0.185_777_706_184_603_15_f64,
+        3.161_123_743_870_565_5_f64,
+        113.864_154_151_050_16_f64,
+        377.485_237_685_302_f64,
+        3209.377_589_138_469_4_f64,
+    ];
+    /// Denominator for `|x| <= 0.46875`, in `x^2`.
+    const SMALL_DEN: [f64; 5] = [
+        1.0_f64,
+        23.601_290_952_344_122_f64,
+        244.024_637_934_444_17_f64,
+        1282.616_526_077_372_3_f64,
+        2844.236_833_439_171_f64,
+    ];
+    /// Numerator for `0.46875 < |x| <= 4`.
+    const MEDIUM_NUM: [f64; 9] = [
+        2.153_115_354_744_038_3e-8_f64,
+        0.564_188_496_988_670_1_f64,
+        8.883_149_794_388_377_f64,
+        66.119_190_637_141_63_f64,
+        298.635_138_197_400_1_f64,
+        881.952_221_241_769_f64,
+        1712.047_612_634_070_7_f64,
+        2051.078_377_826_071_6_f64,
+        1230.339_354_797_997_2_f64,
+    ];
+    /// Denominator for `0.46875 < |x| <= 4`.
+    const MEDIUM_DEN: [f64; 9] = [
+        1.0_f64,
+        15.744_926_110_709_835_f64,
+        117.693_950_891_312_5_f64,
+        537.181_101_862_009_9_f64,
+        1621.389_574_566_690_3_f64,
+        3290.799_235_733_459_7_f64,
+        4362.619_090_143_247_f64,
+        3439.367_674_143_721_6_f64,
+        1230.339_354_803_749_5_f64,
+    ];
+    /// Numerator for `|x| > 4`, in `1 / x^2`.
+    const LARGE_NUM: [f64; 6] = [
+        0.016_315_387_137_302_097_f64,
+        0.305_326_634_961_232_36_f64,
+        0.360_344_899_949_804_45_f64,
+        0.125_781_726_111_229_26_f64,
+        0.016_083_785_148_742_275_f64,
+        0.000_658_749_161_529_837_8_f64,
+    ];
+    /// Denominator for `|x| > 4`, in `1 / x^2`.
+    const LARGE_DEN: [f64; 6] = [
+        1.0_f64,
+        2.568_520_192_289_822_f64,
+        1.872_952_849_923_467_3_f64,
+        0.527_905_102_951_428_5_f64,
+        0.060_518_341_312_441_32_f64,
+        0.002_335_204_976_268_691_8_f64,
+    ];
+
+    let abs = num.abs();
+    if abs <= THRESHOLD {
+        let square = num * num;
+        let res = num * rational(square, &SMALL_NUM, &SMALL_DEN);
+        return (res, 1.0_f64 - res);
+    }
+    let complement = if abs >= MAX {
+        0.0_f64
+    } else {
+        let approx = if abs <= 4.0_f64 {
+            rational(abs, &MEDIUM_NUM, &MEDIUM_DEN)
+        } else {
+            let inv = (abs * abs).recip();
+            let series = inv * rational(inv, &LARGE_NUM, &LARGE_DEN);
+            (0.5_f64.mul_add(f64::consts::FRAC_2_SQRT_PI, -series)) / abs
+        };
+        // `exp(-x^2)` split in two to avoid the rounding error of `x^2`
+        let rounded = (abs * 16.0_f64).trunc() / 16.0_f64;
+        let delta = (abs - rounded) * (abs + rounded);
+        (-rounded * rounded).exp() * (-delta).exp() * approx
+    };
+    if num < 0.0_f64 {
+        (complement - 1.0_f64, 2.0_f64 - complement)
+    } else {
+        (1.0_f64 - complement, complement)
+    }
+}
+
+/// Ratio of two polynomials, their coefficients are given from the highest degree.
+fn rational(num: f64, numerator: &[f64], denominator: &[f64]) -> f64 {
+    let polynomial = |coefficients: &[f64]| {
+        coefficients
+            .iter()
+            .fold(0.0_f64, |acc, &coefficient| acc.mul_add(num, coefficient))
+    };
+    polynomial(numerator) / polynomial(denominator)
+}
+
+/// Median of a non-empty list of numbers, `NaN` if one of them is `NaN`.
+fn median(args: &[f64]) -> f64 {
+    if args.iter().any(|arg| arg.is_nan()) {
+        return f64::NAN;
+    }
+    let mut sorted = args.to_vec();
+    sorted.sort_unstable_by(f64::total_cmp);
+    let middle = sorted.len() >> 1_u32;
+    let upper = sorted.get(middle).copied().unwrap_or(f64::NAN);
+    if sorted.len() & 1 == 1 {
+        return upper;
+    }
+    let lower = sorted.get(middle - 1).copied().unwrap_or(f64::NAN);
+    lower + (upper - lower) / 2.0_f64
+}
+
+/// Sample variance (divided by `n - 1`) of a list of numbers.
+#[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+fn variance(args: &[f64]) -> f64 {
+    let len = args.len() as f64;
+    let mean = args.iter().sum::<f64>() / len;
+    let squares = args.iter().map(|arg| (arg - mean).powi(2)).sum::<f64>();
+    squares / (len - 1.0_f64)
+}
+
+/// Natural logarithm of the absolute value of the gamma function.
+fn ln_gamma(num: f64) -> f64 {
+    /// Above this value, the gamma function overflows.
+    const MAX_GAMMA: f64 = 171.0;
+    /// `ln(sqrt(2 * pi))`.
+    const LN_SQRT_TAU: f64 = 0.918_938_533_204_672_8;
+
+    if num <= 0.0_f64 && num.fract() == 0.0_f64 {
+        // poles
+        return f64::INFINITY;
+    }
+    if num < 0.0_f64 {
+        // reflection formula
+        let sin = (f64::consts::PI * num).sin().abs();
+        return f64::consts::PI.ln() - sin.ln() - ln_gamma(1.0_f64 - num);
+    }
+    if num < MAX_GAMMA {
+        return gamma(num).ln();
+    }
+    // Stirling's series
+    let inv = num.recip();
+    let inv_sq = inv * inv;
+    let series = inv_sq
+        .mul_add(-(1.0_f64 / 1260.0_f64), 1.0_f64 / 360.0_f64)
+        .mul_add(-inv_sq, 1.0_f64 / 12.0_f64)
+        * inv;
+    (num - 0.5_f64).mul_add(num.ln(), -num) + LN_SQRT_TAU + series
+}
+
+/// Beta function.
+fn beta(lhs: f64, rhs: f64) -> f64 {
+    (ln_gamma(lhs) + ln_gamma(rhs) - ln_gamma(lhs + rhs)).exp()
+}
+
+/// Binomial coefficient, exact as long as the result fits in the mantissa of a [`f64`].
+fn binomial(total: f64, chosen: f64) -> f64 {
+    let is_integer = |num: f64| num >= 0.0_f64 && num.fract() == 0.0_f64;
+    if !is_integer(total) || !is_integer(chosen) {
+        return f64::NAN;
+    }
+    if chosen > total {
+        return 0.0_f64;
+    }
+    let smallest = chosen.min(total - chosen);
+    let mut res = 1.0_f64;
+    let mut idx = 1.0_f64;
+    // `idx` is an integer, the comparison is exact
+    #[allow(clippy::while_float)]
+    while idx <= smallest {
+        // every intermediate result is a binomial coefficient, thus an integer
+        res = res * (total - smallest + idx) / idx;
+        idx += 1.0_f64;
+    }
+    res
+}
+
+/// Probability density function of the normal distribution.
+fn normal_pdf(num: f64, mu: f64, sigma: f64) -> f64 {
+    let score = (num - mu) / sigma;
+    (-0.5_f64 * score * score).exp() / (sigma * f64::consts::TAU.sqrt())
+}
+
+/// Cumulative distribution function of the normal distribution.
+fn normal_cdf(num: f64, mu: f64, sigma: f64) -> f64 {
+    0.5_f64 * erfc((mu - num) / (sigma * f64::consts::SQRT_2))
+}
+
+/// Restricts a number to an interval, `NaN` if the interval is empty.
+fn clamp(num: f64, min: f64, max: f64) -> f64 {
+    if min > max {
+        return f64::NAN;
+    }
+    num.max(min).min(max)
+}
+
+/// Sign of a number, `0` (keeping its sign) for zeros.
+fn sign(num: f64) -> f64 {
+    if num == 0.0_f64 {
+        num
+    } else {
+        num.signum()
+    }
+}
+
+/// Linear interpolation between `start` and `end`.
+fn lerp(start: f64, end: f64, ratio: f64) -> f64 {
+    (end - start).mul_add(ratio, start)
+}
+
+/// Folds a list of integers with the given operation, `NaN` if one of them isn't an integer.
+fn fold_integers(args: &[f64], op: fn(f64, f64) -> f64) -> f64 {
+    if args.iter().any(|arg| arg.fract() != 0.0_f64) {
+        return f64::NAN;
+    }
+    args.iter()
+        .map(|arg| arg.abs())
+        .reduce(op)
+        .unwrap_or(f64::NAN)
+}
+
+/// Greatest common divisor of two non-negative integers.
+#[allow(clippy::while_float)]
+fn gcd(mut lhs: f64, mut rhs: f64) -> f64 {
+    while rhs != 0.0_f64 {
+        (lhs, rhs) = (rhs, lhs % rhs);
+    }
+    lhs
+}
+
+/// Least common multiple of two non-negative integers.
+fn lcm(lhs: f64, rhs: f64) -> f64 {
+    if lhs == 0.0_f64 || rhs == 0.0_f64 {
+        return 0.0_f64;
+    }
+    lhs / gcd(lhs, rhs) * rhs
+}
@@ -1,15 +1,17 @@
 /* Clippy Config */
 #![allow(clippy::std_instead_of_core)]
 /* Built-in imports */
-use core::{fmt, str};
+extern crate alloc;
+use alloc::borrow::Cow;
+use core::{fmt, num::IntErrorKind, str};
 use std::collections::HashSet;
 /* Crate imports */
 #[cfg(feature = "compile-time-optimizations")]
 use crate::element::Simplify;
 use crate::{
-    context::Context,
+    context::{Context, Symbol},
     element::{BinOp, Element, FunctionCall, UnOp},
-    token::{Identifier, Operator},
+    token::{Arity, Function, Identifier, Operator, Params},
     utils::{
         hidden_macros::{trust_me, yeet},
         precedence,
@@ -166,6 +168,14 @@ impl<'input, 'ctx> ParserImpl<'input, 'ctx> {
 
         // Right-associative unary operators
         if self.consume_if_eq(b'!') {
+            // `!` is the built-in `factorial`, filtered like the function
+            if !self.ctx.is_built_in_function_permitted("factorial") {
+                yeet!(ParseError::new_unresolved_identifier(
+                    self,
+                    ErrorKind::FunctionNotPermitted("factorial".to_owned()),
+                    self.cursor - 1
+                ));
+            }
             el = UnOp::new_element(Operator::Factorial, el);
         }
 
@@ -227,48 +237,62 @@ impl<'input, 'ctx> ParserImpl<'input, 'ctx> {
     /// Parses an identifier in the mathematical expression.
     fn parse_identifier(&mut self) -> Result<Element<'input>, ParseError> {
         let identifier_start = self.cursor;
-        let name = self.take_while(
-            |&ch| matches!(ch, b'_' | b'\'' | b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9'),
-        );
+        let mut name = self.take_identifier_part();
+        // qualified name (`namespace.name`), the dot must be followed by a letter
+        while self.current() == Some(&b'.')
+            && self.next().is_some_and(u8::is_ascii_alphabetic)
+        {
+            self.cursor += 1;
+            self.take_identifier_part();
+            name = trust_me!(
+                #[allow(clippy::indexing_slicing)]
+                str::from_utf8_unchecked(
+                    &self.input[identifier_start..self.cursor]
+                )
+            );
+        }
 
         // checks for contexts or built-in functions
         // else defaults to variable
-        let ident = self
-            .ctx
-            .get(name)
-            .cloned()
-            .map_or_else(|| Identifier::from_str(name), Into::into);
+        let found = self.ctx.lookup(name).map_err(
+            #[cold]
+            |kind| {
+                ParseError::new_unresolved_identifier(
+                    self,
+                    kind,
+                    identifier_start,
+                )
+            },
+        )?;
+        let ident = match found.map(Cow::into_owned) {
+            // qualified functions keep their qualified name to be displayed as used
+            Some(Symbol::Function(func)) if name.contains('.') => {
+                Identifier::Function(func.with_name(name.to_owned()))
+            },
+            Some(symbol) => symbol.into(),
+            None => {
+                let ident = Identifier::from_str(name, self.ctx);
+                if let Identifier::Function(ref func) = ident {
+                    if !self.ctx.is_built_in_function_permitted(&func.name) {
+                        yeet!(ParseError::new_unresolved_identifier(
+                            self,
+                            ErrorKind::FunctionNotPermitted(name.to_owned()),
+                            identifier_start
+                        ));
+                    }
+                }
+                ident
+            },
+        };
 
         let el = match ident {
             Identifier::Constant(val) => Element::Number(val),
             Identifier::Variable(var) => Element::Variable(var),
             Identifier::Function(func) if self.consume_if_eq(b'(') => {
-                // for now the minimum number of arguments is 1
-                // self.parse_arguments() will fail if no argument is found
-                let args = self.parse_arguments()?;
+                let given_args = self.parse_arguments()?;
                 self.assert_eq_consume(b')')?;
-                if let Some(nb_args) = func.nb_args {
-                    use core::cmp::Ordering::{Equal, Greater, Less};
-                    match args.len().cmp(&nb_args.into()) {
-                        Equal => (),
-                        Less => {
-                            yeet!(ParseError::new_too_few_arguments(
-                                self,
-                                nb_args,
-                                args.len(),
-                                identifier_start
-                            ))
-                        },
-                        Greater => {
-                            yeet!(ParseError::new_too_many_arguments(
-                                self,
-                                nb_args,
-                                args.len(),
-                                identifier_start
-                            ))
-                        },
-                    }
-                }
+                let args =
+                    self.order_arguments(&func, given_args, identifier_start)?;
                 FunctionCall::new_element(func, args)
             },
             Identifier::Function(_) => {
@@ -281,6 +305,10 @@ impl<'input, 'ctx> ParserImpl<'input, 'ctx> {
 
     /// Parses a number in the mathematical expression.
     fn parse_number(&mut self) -> Result<Element<'input>, ParseError> {
+        if let Some(radix) = self.radix_prefix() {
+            return self.parse_radix_number(radix);
+        }
+
         let begin = self.cursor;
         self.skip_while(|&ch| matches!(ch, b'0'..=b'9' | b'.' | b'_'));
         // make sure to not mistake exponent (10^) with exponential (e = 2.71828..)
@@ -302,7 +330,12 @@ impl<'input, 'ctx> ParserImpl<'input, 'ctx> {
             str::from_utf8_unchecked(&self.input[begin..end])
         );
 
-        let num = ident.replace('_', "").parse().map_err(
+        let digits = ident.replace('_', "");
+        // integers are kept exact, see `Element::Integer`
+        if let Ok(int) = digits.parse() {
+            return Ok(Element::from_integer(int));
+        }
+        let num = digits.parse().map_err(
             #[cold]
             |_err| ParseError::new_malformed_number(self, ident),
         )?;
@@ -310,13 +343,90 @@ impl<'input, 'ctx> ParserImpl<'input, 'ctx> {
         Ok(Element::Number(num))
     }
 
+    /// Parses a hexadecimal, binary or octal integer literal.
+    /// The literal must fit in an [`u64`].
+    fn parse_radix_number(
+        &mut self,
+        radix: u32,
+    ) -> Result<Element<'input>, ParseError> {
+        let begin = self.cursor;
+        // skip the `0x`, `0b` or `0o` prefix
+        self.cursor += 2;
+        self.skip_while(|&ch| char::from(ch).is_digit(radix) || ch == b'_');
+        let end = self.cursor;
+
+        let ident = trust_me!(
+            #[allow(clippy::indexing_slicing)]
+            str::from_utf8_unchecked(&self.input[begin..end])
+        );
+        let digits = ident.get(2..).unwrap_or_default().replace('_', "");
+
+        let int = u64::from_str_radix(&digits, radix).map_err(
+            #[cold]
+            |err| {
+                if *err.kind() == IntErrorKind::PosOverflow {
+                    ParseError::new_integer_literal_too_large(self, ident)
+                } else {
+                    ParseError::new_malformed_number(self, ident)
+                }
+            },
+        )?;
+
+        Ok(Element::from_integer(int))
+    }
+
+    /// Returns the radix of the integer literal starting at the cursor
+    /// if it has a `0x`, `0b` or `0o` prefix followed by a valid digit.
+    fn radix_prefix(&self) -> Option<u32> {
+        if self.current() != Some(&b'0') {
+            return None;
+        }
+        let radix = match *self.next()? {
+            b'x' | b'X' => 16,
+            b'b' | b'B' => 2,
+            b'o' | b'O' => 8,
+            _ => return None,
+        };
+        char::from(*self.next_at(2)?)
+            .is_digit(radix)
+            .then_some(radix)
+    }
+
+    /// Parses the name of a named argument (`name = value`) if any.
+    /// Returns the name and its position.
+    fn argument_name(&mut self) -> Option<(&'input str, usize)> {
+        self.next_trim();
+        let start = self.cursor;
+        if !self.current().is_some_and(u8::is_ascii_alphabetic) {
+            return None;
+        }
+        let name = self.take_identifier_part();
+        if self.consume_if_eq(b'=') {
+            return Some((name, start));
+        }
+        // not a named argument, rewind
+        self.cursor = start;
+        None
+    }
+
     /// Parses a list of arguments in a function call.
-    fn parse_arguments(&mut self) -> Result<Vec<Element<'input>>, ParseError> {
+    fn parse_arguments(&mut self) -> Result<Vec<Argument<'input>>, ParseError> {
         let mut args = Vec::new();
 
+        // empty argument list, the number of arguments is checked by the caller
+        if self.next_trim() == Some(&b')') {
+            return Ok(args);
+        }
+
         loop {
-            let arg = self.argument()?;
-            args.push(arg);
+            let name = self.argument_name();
+            let start = self.cursor;
+            let value = self.argument()?;
+            args.push(Argument {
+                name,
+                span: (start, self.cursor - start),
+                value,
+            });
 
             // expect either a comma or a closing parenthesis
             match self.next_trim() {
@@ -344,15 +454,108 @@ impl<'input, 'ctx> ParserImpl<'input, 'ctx> {
                 ErrorKind::UnexpectedEndOfExpression
                 | ErrorKind::ExpectedToken(_)
                 | ErrorKind::MalformedNumber(_)
+                | ErrorKind::IntegerLiteralTooLarge(_)
                 | ErrorKind::IllegalCharacter(_)
                 | ErrorKind::VariableNotDeclared(_, _)
-                | ErrorKind::TooFewArguments(_, _)
-                | ErrorKind::TooManyArguments(_, _)
-                | ErrorKind::MissingArgument => err,
+                | ErrorKind::TooFewArguments(_, _, _)
+                | ErrorKind::TooManyArguments(_, _, _)
+                | ErrorKind::MissingArgument
+                | ErrorKind::UnknownArgumentName(_, _)
+                | ErrorKind::DuplicateArgumentName(_)
+                | ErrorKind::PositionalArgumentAfterNamed
+                | ErrorKind::UnknownNamespace(_, _)
+                | ErrorKind::UnknownNamespaceMember(_, _)
+                | ErrorKind::AmbiguousIdentifier(_, _)
+                | ErrorKind::FunctionNotPermitted(_)
+                | ErrorKind::TooManyParameters(_) => err,
             },
         )
     }
 
+    /// Orders the arguments of a function call by parameter (named arguments are moved to
+    /// their parameter's position) and appends the default values of the omitted arguments.
+    fn order_arguments(
+        &self,
+        func: &Function,
+        args: Vec<Argument<'input>>,
+        start: usize,
+    ) -> Result<Vec<Element<'input>>, ParseError> {
+        let nb_given = args.len();
+        let mut slots: Vec<Option<Element<'input>>> =
+            Vec::with_capacity(nb_given);
+        let mut has_named = false;
+        for arg in args {
+            let Some((name, name_start)) = arg.name else {
+                if has_named {
+                    yeet!(ParseError::new_positional_after_named_argument(
+                        self, arg.span
+                    ));
+                }
+                slots.push(Some(arg.value));
+                continue;
+            };
+            has_named = true;
+            let span = (name_start, name.len());
+            let Some(idx) = func.params.iter().position(|param| param == name)
+            else {
+                yeet!(ParseError::new_unknown_argument_name(
+                    self,
+                    name,
+                    &func.params,
+                    span
+                ));
+            };
+            if slots.len() <= idx {
+                slots.resize_with(idx + 1, || None);
+            }
+            if let Some(slot @ &mut None) = slots.get_mut(idx) {
+                *slot = Some(arg.value);
+            } else {
+                yeet!(ParseError::new_duplicate_argument_name(
+                    self, name, span
+                ));
+            }
+        }
+
+        if let Some(max) = func.arity.max.map(usize::from) {
+            if slots.len() > max {
+                yeet!(ParseError::new_too_many_arguments(
+                    self,
+                    func,
+                    slots.len(),
+                    start
+                ));
+            }
+            // defaults are the values of the last parameters
+            if !func.defaults.is_empty() {
+                let first_default = max.saturating_sub(func.defaults.len());
+                slots.resize_with(max, || None);
+                for (slot, &default) in
+                    slots.iter_mut().skip(first_default).zip(func.defaults)
+                {
+                    slot.get_or_insert(Element::Number(default));
+                }
+            }
+        }
+
+        let missing = slots.iter().any(Option::is_none)
+            || slots.len() < usize::from(func.arity.min);
+        if missing {
+            yeet!(ParseError::new_too_few_arguments(
+                self, func, &slots, nb_given, start
+            ));
+        }
+
+        Ok(slots.into_iter().flatten().collect())
+    }
+
+    /// Takes the characters of an identifier (or of a part of a qualified one).
+    fn take_identifier_part(&mut self) -> &'input str {
+        self.take_while(
+            |&ch| matches!(ch, b'_' | b'\'' | b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9'),
+        )
+    }
+
     /// Takes characters while the given predicate is true and returns the
     /// corresponding substring.
     fn take_while(&mut self, predicate: fn(&u8) -> bool) -> &'input str {
@@ -368,8 +571,8 @@ impl<'input, 'ctx> ParserImpl<'input, 'ctx> {
 
 impl ParserImpl<'_, '_> {
     /// Skips characters while the given predicate is true.
-    fn skip_while(&mut self, predicate: fn(&u8) -> bool) {
-        while self.current().is_some_and(predicate) {
+    fn skip_while(&mut self, predicate: impl Fn(&u8) -> bool) {
+        while self.current().is_some_and(&predicate) {
             self.cursor += 1;
         }
     }
@@ -410,17 +613,18 @@ impl ParserImpl<'_, '_> {
     ) -> Option<(Operator, usize)> {
         use precedence::IMPLICIT_MULTIPLICATION_INFO;
 
-        let current_byte = *self.next_trim()?;
         // check for binary operator
-        if let Ok(op) = Operator::try_from(current_byte) {
+        if let Some((op, len)) = self.peek_operator() {
             let op_p = precedence::get_for_op(op);
             if op_p <= precedence {
                 return None;
             }
-            self.cursor += 1;
+            self.cursor += len;
             return Some((op, op_p));
         }
 
+        let current_byte = *self.next_trim()?;
+
         match current_byte {
             // if multiplication precedence is lower than current precedence
             // we now we don't need implicit multiplication
@@ -437,6 +641,34 @@ impl ParserImpl<'_, '_> {
         }
     }
 
+    /// Returns the operator at the cursor (without consuming it) and its
+    /// length in bytes, if any.
+    fn peek_operator(&mut self) -> Option<(Operator, usize)> {
+        let current_byte = *self.next_trim()?;
+        match (current_byte, self.next()) {
+            (b'/', Some(&b'/')) => Some((Operator::IntDivide, 2)),
+            (b'<', Some(&b'<')) => Some((Operator::ShiftLeft, 2)),
+            (b'>', Some(&b'>')) => Some((Operator::ShiftRight, 2)),
+            // `xor` must not be the beginning of an identifier (e.g. `xorg`)
+            (b'x', Some(&b'o'))
+                if self.next_at(2) == Some(&b'r')
+                    && !matches!(
+                        self.next_at(3),
+                        Some(
+                            &(b'_'
+                            | b'\''
+                            | b'A'..=b'Z'
+                            | b'a'..=b'z'
+                            | b'0'..=b'9'),
+                        )
+                    ) =>
+            {
+                Some((Operator::BitXor, 3))
+            },
+            (byte, _) => Operator::try_from(byte).ok().map(|op| (op, 1)),
+        }
+    }
+
     /// Asserts that the next character is equal to the given one and consumes
     /// it if it is.
     fn assert_eq_consume(&mut self, tok: u8) -> Result<(), ParseError> {
@@ -461,6 +693,7 @@ pub struct ParseError {
 
 impl miette::Diagnostic for ParseError {
     #[inline]
+    #[allow(clippy::too_many_lines)]
     fn help(&self) -> Option<Box<dyn fmt::Display + '_>> {
         let message = match self.kind {
             ErrorKind::UnexpectedEndOfExpression => {
@@ -471,6 +704,9 @@ impl miette::Diagnostic for ParseError {
                 "Did you enter a number with multiple decimal points?"
                     .to_owned()
             },
+            ErrorKind::IntegerLiteralTooLarge(_) => {
+                "Integer literals must fit in a `u64`.".to_owned()
+            },
             ErrorKind::IllegalCharacter(_) => {
                 "Try removing this character.".to_owned()
             },
@@ -483,23 +719,80 @@ impl miette::Diagnostic for ParseError {
                     available_vars.join("`, `")
                 )
             },
-            ErrorKind::TooManyArguments(expected, got) => {
-                let excess = got - usize::from(expected);
-                format!(
+            ErrorKind::TooManyArguments(expected, got, ref params) => {
+                let max = expected.max.map_or(got, usize::from);
+                let excess = got - max;
+                let mut help = format!(
                     "Try removing {excess} argument{}.",
                     if excess > 1 { "s" } else { "" }
-                )
+                );
+                if !params.is_empty() {
+                    help = format!(
+                        "{help} The parameters are: `{}`.",
+                        params.join("`, `")
+                    );
+                }
+                help
             },
-            ErrorKind::TooFewArguments(expected, got) => {
-                let missing = usize::from(expected) - got;
-                format!(
-                    "Try adding {missing} argument{}.",
-                    if missing > 1 { "s" } else { "" }
-                )
+            ErrorKind::TooFewArguments(expected, got, ref missing_params) => {
+                if missing_params.is_empty() {
+                    let missing = usize::from(expected.min) - got;
+                    format!(
+                        "Try adding {missing} argument{}.",
+                        if missing > 1 { "s" } else { "" }
+                    )
+                } else {
+                    format!(
+                        "Try adding the following argument{}: `{}`.",
+                        if missing_params.len() > 1 { "s" } else { "" },
+                        missing_params.join("`, `")
+                    )
+                }
+            },
+            ErrorKind::UnknownArgumentName(_, ref params) => {
+                if params.is_empty() {
+                    "This function doesn't have named parameters.".to_owned()
+                } else {
+                    format!(
+                        "Try replacing it with one of the following: `{}`.",
+                        params.join("`, `")
+                    )
+                }
+            },
+            ErrorKind::DuplicateArgumentName(_) => {
+                "Try removing one of them.".to_owned()
+            },
+            ErrorKind::PositionalArgumentAfterNamed => {
+                "Try naming it or moving it before the named arguments."
+                    .to_owned()
             },
             ErrorKind::MissingArgument => {
                 "Either remove comma or add argument.".to_owned()
             },
+            ErrorKind::UnknownNamespace(_, ref suggestions)
+            | ErrorKind::UnknownNamespaceMember(_, ref suggestions) => {
+                if suggestions.is_empty() {
+                    "Nothing is defined there.".to_owned()
+                } else {
+                    format!(
+                        "Try replacing it with one of the following: `{}`.",
+                        suggestions.join("`, `")
+                    )
+                }
+            },
+            ErrorKind::TooManyParameters(_) => {
+                "Functions take at most 255 parameters.".to_owned()
+            },
+            ErrorKind::FunctionNotPermitted(_) => {
+                "This function has been disabled, try using another one."
+                    .to_owned()
+            },
+            ErrorKind::AmbiguousIdentifier(_, ref qualified_names) => {
+                format!(
+                    "Try qualifying it with one of the following: `{}`.",
+                    qualified_names.join("`, `")
+                )
+            },
         };
         Some(Box::new(message))
     }
@@ -535,27 +828,67 @@ pub enum ErrorKind {
     /// Unexpected token error with a specified character.
     #[error("Unexpected token: `{0}`")]
     UnexpectedToken(char),
+    /// Integer literal that doesn't fit in a `u64` error with a specified string.
+    #[error("Integer literal too large: `{0}`")]
+    IntegerLiteralTooLarge(String),
     /// Malformed number error with a specified string.
     #[error("Malformed number: `{0}`")]
     MalformedNumber(String),
     /// Illegal character error with a specified character.
     #[error("Illegal character: `{0}`")]
     IllegalCharacter(char),
+    /// Identifier defined by several imported namespaces error
+    /// with the identifier and its qualified names.
+    #[error("Ambiguous identifier: `{0}`")]
+    AmbiguousIdentifier(String, Vec<String>),
+    /// Argument provided more than once error with the argument name.
+    #[error("Duplicate argument: `{0}`")]
+    DuplicateArgumentName(String),
     /// Expected token error with a specified character.
     #[error("Expected token: `{0}`")]
     ExpectedToken(char),
     /// Variable not previously declared error with variable name and available suggestions.
     #[error("Variable not previously declared: `{0}`")]
     VariableNotDeclared(String, Vec<String>),
-    /// Too few arguments for function call error with expected and actual argument counts.
+    /// Built-in function forbidden by the context error with the function name.
+    #[error("Function not permitted in this context: `{0}`")]
+    FunctionNotPermitted(String),
+    /// Too few arguments for function call error with expected arity, actual argument count
+    /// and the names of the missing parameters (if the function has named parameters).
     #[error("Too few arguments for function call, expected {0} got {1}")]
-    TooFewArguments(u8, usize),
-    /// Too many arguments for function call error with expected and actual argument counts.
+    TooFewArguments(Arity, usize, Vec<String>),
+    /// Too many arguments for function call error with expected arity, actual argument count
+    /// and the names of the function's parameters (if any).
     #[error("Too many arguments for function call, expected {0} got {1}")]
-    TooManyArguments(u8, usize),
+    TooManyArguments(Arity, usize, Vec<String>),
     /// Missing argument for function call error.
     #[error("Missing argument for function call")]
     MissingArgument,
+    /// Positional argument following named arguments error.
+    #[error("Positional argument after named arguments")]
+    PositionalArgumentAfterNamed,
+    /// Function defined with more parameters than a function can take error with the number of parameters.
+    #[error("Too many parameters for function definition: {0}")]
+    TooManyParameters(usize),
+    /// Unknown named argument error with the argument name and the function's parameters names.
+    #[error("Unknown argument name: `{0}`")]
+    UnknownArgumentName(String, Vec<String>),
+    /// Unknown namespace error with the namespace path and the closest namespaces.
+    #[error("Unknown namespace: `{0}`")]
+    UnknownNamespace(String, Vec<String>),
+    /// Unknown symbol in a namespace error with the qualified name and the closest symbols.
+    #[error("Unknown symbol in namespace: `{0}`")]
+    UnknownNamespaceMember(String, Vec<String>),
+}
+
+/// An argument of a function call.
+struct Argument<'input> {
+    /// The name of the argument and its position, if it is a named argument.
+    name: Option<(&'input str, usize)>,
+    /// The position and length of the argument's value.
+    span: (usize, usize),
+    /// The value of the argument.
+    value: Element<'input>,
 }
 
 impl ParseError {
@@ -581,6 +914,17 @@ impl ParseError {
         }
     }
 
+    /// Creates a new [`ParseError`] for an integer literal that is too large.
+    #[cold]
+    fn new_integer_literal_too_large(parser: &ParserImpl, ident: &str) -> Self {
+        let num_len = ident.len();
+        Self {
+            kind: ErrorKind::IntegerLiteralTooLarge(ident.to_owned()),
+            span: (parser.cursor - num_len, num_len).into(),
+            src: trust_me!(str::from_utf8_unchecked(parser.input)).to_owned(),
+        }
+    }
+
     /// Creates a new [`ParseError`] for a malformed number.
     #[cold]
     fn new_malformed_number(parser: &ParserImpl, ident: &str) -> Self {
@@ -602,6 +946,20 @@ impl ParseError {
         }
     }
 
+    /// Creates a new [`ParseError`] for a duplicate argument name error.
+    #[cold]
+    fn new_duplicate_argument_name(
+        parser: &ParserImpl,
+        name: &str,
+        span: (usize, usize),
+    ) -> Self {
+        Self {
+            kind: ErrorKind::DuplicateArgumentName(name.to_owned()),
+            span: span.into(),
+            src: trust_me!(str::from_utf8_unchecked(parser.input)).to_owned(),
+        }
+    }
+
     /// Creates a new [`ParseError`] for an expected token error.
     #[cold]
     fn new_expected_token(parser: &ParserImpl, tok: u8) -> Self {
@@ -633,12 +991,23 @@ impl ParseError {
     #[cold]
     fn new_too_few_arguments(
         parser: &ParserImpl,
-        expected: u8,
+        func: &Function,
+        slots: &[Option<Element>],
         got: usize,
         start: usize,
     ) -> Self {
+        // named parameters that are neither given nor defaulted
+        let nb_params = slots.len().max(usize::from(func.arity.min));
+        let missing_params = func
+            .params
+            .iter()
+            .take(nb_params)
+            .enumerate()
+            .filter(|&(idx, _)| slots.get(idx).map_or(true, Option::is_none))
+            .map(|(_, param)| param.to_owned())
+            .collect();
         Self {
-            kind: ErrorKind::TooFewArguments(expected, got),
+            kind: ErrorKind::TooFewArguments(func.arity, got, missing_params),
             span: (start..parser.cursor).into(),
             src: trust_me!(str::from_utf8_unchecked(parser.input)).to_owned(),
         }
@@ -648,17 +1017,35 @@ impl ParseError {
     #[cold]
     fn new_too_many_arguments(
         parser: &ParserImpl,
-        expected: u8,
+        func: &Function,
         got: usize,
         start: usize,
     ) -> Self {
+        let params = func.params.iter().map(str::to_owned);
         Self {
-            kind: ErrorKind::TooManyArguments(expected, got),
+            kind: ErrorKind::TooManyArguments(
+                func.arity,
+                got,
+                params.collect(),
+            ),
             span: (start..parser.cursor).into(),
             src: trust_me!(str::from_utf8_unchecked(parser.input)).to_owned(),
         }
     }
 
+    /// Creates a new [`ParseError`] for a function definition with too many parameters.
+    #[cold]
+    pub(crate) fn new_too_many_parameters(
+        body: &str,
+        nb_params: usize,
+    ) -> Self {
+        Self {
+            kind: ErrorKind::TooManyParameters(nb_params),
+            span: (0, body.len()).into(),
+            src: body.to_owned(),
+        }
+    }
+
     /// Creates a new [`ParseError`] for a missing argument error.
     #[cold]
     fn new_missing_argument(parser: &ParserImpl) -> Self {
@@ -668,4 +1055,50 @@ impl ParseError {
             src: trust_me!(str::from_utf8_unchecked(parser.input)).to_owned(),
         }
     }
+
+    /// Creates a new [`ParseError`] for a positional argument after named arguments error.
+    #[cold]
+    fn new_positional_after_named_argument(
+        parser: &ParserImpl,
+        span: (usize, usize),
+    ) -> Self {
+        Self {
+            kind: ErrorKind::PositionalArgumentAfterNamed,
+            span: span.into(),
+            src: trust_me!(str::from_utf8_unchecked(parser.input)).to_owned(),
+        }
+    }
+
+    /// Creates a new [`ParseError`] for an unknown argument name error.
+    #[cold]
+    fn new_unknown_argument_name(
+        parser: &ParserImpl,
+        name: &str,
+        params: &Params,
+        span: (usize, usize),
+    ) -> Self {
+        let param_names = params.iter().map(str::to_owned);
+        Self {
+            kind: ErrorKind::UnknownArgumentName(
+                name.to_owned(),
+                param_names.collect(),
+            ),
+            span: span.into(),
+            src: trust_me!(str::from_utf8_unchecked(parser.input)).to_owned(),
+        }
+    }
+
+    /// Creates a new [`ParseError`] for an identifier that couldn't be resolved or isn't permitted.
+    #[cold]
+    fn new_unresolved_identifier(
+        parser: &ParserImpl,
+        kind: ErrorKind,
+        start: usize,
+    ) -> Self {
+        Self {
+            kind,
+            span: (start..parser.cursor).into(),
+            src: trust_me!(str::from_utf8_unchecked(parser.input)).to_owned(),
+        }
+    }
 }
@@ -0,0 +1,90 @@
+/* Clippy Config */
+#![allow(clippy::std_instead_of_core)]
+/* Built-in imports */
+extern crate alloc;
+use alloc::collections::BTreeMap;
+use core::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash},
+};
+use std::collections::HashMap;
+
+/// Provides the values of the variables of an expression during its evaluation,
+/// see [`Xprs::eval_with`](crate::Xprs::eval_with).
+///
+/// Variables are resolved lazily: [`VariableResolver::resolve`] is only called
+/// for the variables reached by the evaluation, every time they are reached.
+///
+/// Implemented for maps ([`HashMap`], [`BTreeMap`]), slices and arrays of `(name, value)` pairs,
+/// and closures taking a name and returning an [`Option<f64>`].
+///
+/// # Example
+///
+/// ```
+/// use xprs::{VariableResolver, Xprs};
+///
+/// struct Row {
+///     price: f64,
+///     quantity: f64,
+/// }
+///
+/// impl VariableResolver for Row {
+///     fn resolve(&self, name: &str) -> Option<f64> {
+///         match name {
+///             "price" => Some(self.price),
+///             "quantity" => Some(self.quantity),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let xprs = Xprs::try_from("price * quantity")?;
+/// let row = Row { price: 2.5, quantity: 4.0 };
+/// assert_eq!(xprs.eval_with(&row), Ok(10.0));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub trait VariableResolver {
+    /// Returns the value of the variable, or [`None`] if it is unknown.
+    fn resolve(&self, name: &str) -> Option<f64>;
+}
+
+impl<K, S> VariableResolver for HashMap<K, f64, S>
+where
+    K: Borrow<str> + Hash + Eq,
+    S: BuildHasher,
+{
+    #[inline]
+    fn resolve(&self, name: &str) -> Option<f64> {
+        self.get(name).copied()
+    }
+}
+
+impl<K: Borrow<str> + Ord> VariableResolver for BTreeMap<K, f64> {
+    #[inline]
+    fn resolve(&self, name: &str) -> Option<f64> {
+        self.get(name).copied()
+    }
+}
+
+impl<K: AsRef<str>> VariableResolver for [(K, f64)] {
+    #[inline]
+    fn resolve(&self, name: &str) -> Option<f64> {
+        self.iter()
+            .find(|pair| pair.0.as_ref() == name)
+            .map(|pair| pair.1)
+    }
+}
+
+impl<K: AsRef<str>, const N: usize> VariableResolver for [(K, f64); N] {
+    #[inline]
+    fn resolve(&self, name: &str) -> Option<f64> {
+        self.as_slice().resolve(name)
+    }
+}
+
+impl<F: Fn(&str) -> Option<f64>> VariableResolver for F {
+    #[inline]
+    fn resolve(&self, name: &str) -> Option<f64> {
+        self(name)
+    }
+}
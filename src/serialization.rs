@@ -0,0 +1,362 @@
+/* Built-in imports */
+extern crate alloc;
+use alloc::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+};
+use core::{fmt, marker::PhantomData};
+use std::collections::HashSet;
+/* Dependencies imports */
+use serde::{
+    de::{self, DeserializeSeed},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+/* Crate imports */
+use crate::{
+    context::Context,
+    element::{BinOp, Element, FunctionCall, UnOp},
+    formats::variable,
+    token::Operator,
+    xprs::Xprs,
+};
+
+/// Serialized form of a number.
+/// The non-finite numbers are strings (`"inf"`, `"-inf"` and `"nan"`) in human-readable formats,
+/// which usually can't represent them (`JSON` turns them into `null`).
+#[derive(Clone, Copy)]
+pub struct Number(pub f64);
+
+/// Deserializes the finite and non-finite forms of a [`Number`].
+struct NumberVisitor;
+
+/// Serialized form of an [`Element`], functions are referenced by name.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ElementRepr<'a> {
+    /// A binary operation.
+    BinOp {
+        /// The left-hand side.
+        #[serde(borrow)]
+        lhs: Box<Self>,
+        /// The operator.
+        op: Operator,
+        /// The right-hand side.
+        #[serde(borrow)]
+        rhs: Box<Self>,
+    },
+    /// A function call.
+    Function {
+        /// The arguments.
+        #[serde(borrow)]
+        args: Vec<Self>,
+        /// The name of the function.
+        #[serde(borrow)]
+        name: Cow<'a, str>,
+    },
+    /// An integer too large for a number.
+    Integer(u64),
+    /// A number.
+    Number(Number),
+    /// A unary operation.
+    UnOp {
+        /// The operator.
+        op: Operator,
+        /// The operand.
+        #[serde(borrow)]
+        operand: Box<Self>,
+    },
+    /// A variable.
+    Variable(&'a str),
+}
+
+impl<'a> From<&'a Element<'_>> for ElementRepr<'a> {
+    fn from(element: &'a Element<'_>) -> Self {
+        match *element {
+            Element::Number(num) => Self::Number(Number(num)),
+            Element::Integer(int) => Self::Integer(int),
+            Element::BinOp(ref binop) => Self::BinOp {
+                op: binop.op,
+                lhs: Box::new((&binop.lhs).into()),
+                rhs: Box::new((&binop.rhs).into()),
+            },
+            Element::UnOp(ref unop) => Self::UnOp {
+                op: unop.op,
+                operand: Box::new((&unop.operand).into()),
+            },
+            Element::Function(ref func) => Self::Function {
+                name: Cow::Borrowed(&func.desc.name),
+                args: func.args.iter().map(Into::into).collect(),
+            },
+            Element::Variable(var) => Self::Variable(var),
+        }
+    }
+}
+
+impl<'a> ElementRepr<'a> {
+    /// Converts the serialized form into an [`Element`],
+    /// resolving the functions and checking the variables against the context.
+    fn resolve(self, ctx: &Context) -> Result<Element<'a>, String> {
+        let element = match self {
+            Self::Number(num) => Element::Number(num.0),
+            Self::Integer(int) => Element::Integer(int),
+            Self::BinOp { op, lhs, rhs } => {
+                BinOp::new_element(op, lhs.resolve(ctx)?, rhs.resolve(ctx)?)
+            },
+            Self::UnOp { op, operand } => {
+                UnOp::new_element(op, operand.resolve(ctx)?)
+            },
+            Self::Function { name, args } => {
+                let func = ctx
+                    .find_function(&name)
+                    .ok_or_else(|| format!("unknown function `{name}`"))?;
+                if !func.arity.contains(args.len()) {
+                    return Err(format!(
+                        "function `{name}` called with {} arguments, expected {}",
+                        args.len(),
+                        func.arity
+                    ));
+                }
+                let resolved = args
+                    .into_iter()
+                    .map(|arg| arg.resolve(ctx))
+                    .collect::<Result<_, _>>()?;
+                FunctionCall::new_element(func, resolved)
+            },
+            Self::Variable(var) => {
+                variable(var, ctx).map_err(|err| err.to_string())?
+            },
+        };
+        Ok(element)
+    }
+}
+
+/// Serialized form of a [`Context`], functions are referenced by name.
+#[derive(Serialize, Deserialize)]
+pub struct ContextRepr<'a> {
+    /// The only permitted built-in functions.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub allowed_functions: Option<BTreeSet<&'a str>>,
+    /// Whether the built-in constants and functions are available.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub built_ins: Option<bool>,
+    /// Whether constants are looked up case-insensitively.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub case_insensitive_constants: Option<bool>,
+    /// The added constants, by name.
+    #[serde(borrow, default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub constants: BTreeMap<&'a str, Number>,
+    /// The forbidden built-in functions.
+    #[serde(borrow, default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub denied_functions: BTreeSet<&'a str>,
+    /// The expected variables.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub expected_vars: Option<BTreeSet<&'a str>>,
+    /// The names of the functions, by symbol name.
+    #[serde(borrow, default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub functions: BTreeMap<Cow<'a, str>, Cow<'a, str>>,
+    /// The hidden constants.
+    #[serde(borrow, default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub hidden_constants: BTreeSet<&'a str>,
+    /// The imported namespaces.
+    #[serde(borrow, default, skip_serializing_if = "Vec::is_empty")]
+    pub imports: Vec<&'a str>,
+    /// The namespaces, by name.
+    #[serde(borrow, default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub namespaces: BTreeMap<&'a str, Self>,
+    /// The names explicitly shadowed.
+    #[serde(borrow, default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub shadowed: BTreeSet<&'a str>,
+    /// The variables, by name.
+    #[serde(borrow, default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<Cow<'a, str>, Number>,
+}
+
+impl Serialize for Number {
+    #[inline]
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if self.0.is_finite() || !serializer.is_human_readable() {
+            serializer.serialize_f64(self.0)
+        } else if self.0.is_nan() {
+            serializer.serialize_str("nan")
+        } else if self.0.is_sign_positive() {
+            serializer.serialize_str("inf")
+        } else {
+            serializer.serialize_str("-inf")
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Number {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(NumberVisitor)
+        } else {
+            f64::deserialize(deserializer).map(Number)
+        }
+    }
+}
+
+impl de::Visitor<'_> for NumberVisitor {
+    type Value = Number;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(r#"a number, "inf", "-inf" or "nan""#)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Number, E> {
+        Ok(Number(v))
+    }
+
+    #[allow(clippy::cast_precision_loss, clippy::as_conversions)]
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Number, E> {
+        Ok(Number(v as f64))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Number, E> {
+        match v {
+            "inf" => Ok(Number(f64::INFINITY)),
+            "-inf" => Ok(Number(f64::NEG_INFINITY)),
+            "nan" => Ok(Number(f64::NAN)),
+            _ => Err(de::Error::invalid_value(de::Unexpected::Str(v), &self)),
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss, clippy::as_conversions)]
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Number, E> {
+        Ok(Number(v as f64))
+    }
+}
+
+impl Serialize for Element<'_> {
+    #[inline]
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        ElementRepr::from(self).serialize(serializer)
+    }
+}
+
+impl Serialize for Xprs<'_> {
+    #[inline]
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.root.serialize(serializer)
+    }
+}
+
+impl Serialize for Context<'_> {
+    #[inline]
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.to_repr().serialize(serializer)
+    }
+}
+
+/// Deserializes the element, resolving its functions against the built-in ones,
+/// see [`ContextSeed`] for other functions.
+impl<'de: 'a, 'a> Deserialize<'de> for Element<'a> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Context::default().seed::<Self>().deserialize(deserializer)
+    }
+}
+
+/// Deserializes the expression, resolving its functions against the built-in ones,
+/// see [`ContextSeed`] for other functions.
+impl<'de: 'a, 'a> Deserialize<'de> for Xprs<'a> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Context::default().seed::<Self>().deserialize(deserializer)
+    }
+}
+
+/// Deserializes the context, resolving its functions against the built-in ones,
+/// see [`ContextSeed`] for other functions.
+impl<'de: 'a, 'a> Deserialize<'de> for Context<'a> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Context::default().seed::<Self>().deserialize(deserializer)
+    }
+}
+
+/// A [`DeserializeSeed`] resolving the functions referenced by name against a context
+/// (its functions, those of its namespaces and parents, and the permitted built-ins),
+/// created with [`Context::seed`].
+///
+/// Names are borrowed from the input, which must outlive the deserialized value.
+#[derive(Debug)]
+pub struct ContextSeed<'ctx, 'names, T> {
+    /// The context functions are resolved against.
+    ctx: &'ctx Context<'names>,
+    /// The deserialized type.
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'ctx, 'names, T> ContextSeed<'ctx, 'names, T> {
+    /// Creates a new [`ContextSeed`] resolving the functions against the context.
+    pub(crate) const fn new(ctx: &'ctx Context<'names>) -> Self {
+        Self {
+            ctx,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de: 'a, 'a> DeserializeSeed<'de> for ContextSeed<'_, '_, Element<'a>> {
+    type Value = Element<'a>;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        ElementRepr::deserialize(deserializer)?
+            .resolve(self.ctx)
+            .map_err(de::Error::custom)
+    }
+}
+
+impl<'de: 'a, 'a> DeserializeSeed<'de> for ContextSeed<'_, '_, Xprs<'a>> {
+    type Value = Xprs<'a>;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        let root = self.ctx.seed::<Element>().deserialize(deserializer)?;
+        let mut vars = HashSet::new();
+        root.find_variables(&mut vars);
+        Ok(Xprs { root, vars })
+    }
+}
+
+impl<'de: 'a, 'a> DeserializeSeed<'de> for ContextSeed<'_, '_, Context<'a>> {
+    type Value = Context<'a>;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        Context::from_repr(ContextRepr::deserialize(deserializer)?, self.ctx)
+            .map_err(de::Error::custom)
+    }
+}
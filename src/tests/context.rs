@@ -0,0 +1,461 @@
+/* Built-in imports */
+extern crate alloc;
+use alloc::sync::Arc;
+/* Crate imports */
+use crate::{
+    context, context::Symbol, xprs::EvalError, xprs_fn, Context, ContextError,
+    Parser,
+};
+
+fn double(x: f64) -> f64 {
+    x * 2.0
+}
+
+fn add(x: f64, y: f64) -> f64 {
+    x + y
+}
+
+mod consts {
+    pub const TWO: f64 = 2.0;
+
+    pub fn double(x: f64) -> f64 {
+        x * 2.0
+    }
+}
+
+#[test]
+fn test_context_macro_entries() {
+    let ctx = context! {
+        x = 1.0,
+        y = consts::TWO,
+        z = -3,
+        add = add/2,
+        double = consts::double/1,
+        expect [a, b]
+    };
+
+    assert_eq!(ctx.get("x"), Some(&Symbol::Variable(1.0)));
+    assert_eq!(ctx.get("y"), Some(&Symbol::Variable(2.0)));
+    assert_eq!(ctx.get("z"), Some(&Symbol::Variable(-3.0)));
+    for (name, nb_args) in [("add", 2), ("double", 1)] {
+        assert!(
+            matches!(
+                ctx.get(name),
+                Some(&Symbol::Function(ref func))
+                    if func.name == name && func.arity.contains(nb_args)
+            ),
+            "{name}"
+        );
+    }
+    assert_eq!(ctx.get_expected_vars(), Some(&["a", "b"].into()));
+
+    assert_eq!(context! {}, Context::default());
+    assert_eq!(
+        context! { x = 1.0, },
+        Context::default().with_var("x", 1.0).unwrap()
+    );
+}
+
+#[test]
+fn test_context_macro_inline_functions() {
+    let ctx = context! {
+        k = 10.0,
+        f(a, b) = a * b + 1,
+        g(a) = f(a, k) - sin(0),
+        h() = 42,
+    };
+    let parser = Parser::new_with_ctx(ctx);
+
+    let test_cases = [
+        ("f(2, 3)", 7.0),
+        ("f(b = 2, a = 4)", 9.0),
+        ("g(2)", 21.0),
+        ("h()", 42.0),
+    ];
+    for (expr, expected) in test_cases {
+        let xprs = parser.parse(expr).unwrap();
+        assert_eq!(xprs.eval_no_vars(), Ok(expected), "{expr}");
+    }
+
+    let xprs = parser.parse("f(x, 2)").unwrap();
+    assert_eq!(xprs.eval(&[("x", 0.5)].into()), Ok(2.0));
+    assert_eq!(
+        xprs.eval_no_vars(),
+        Err(EvalError::MissingVariable("x".to_owned()))
+    );
+}
+
+#[test]
+fn test_context_macro_expected_vars() {
+    let parser = Parser::new_with_ctx(context! { f(a) = 2a, expect [x] });
+
+    assert!(parser.parse("f(x)").is_ok());
+    assert!(parser.parse("f(y)").is_err());
+}
+
+#[test]
+#[deny(unused_mut)]
+fn test_context_macro_empty() {
+    assert_eq!(context! {}, Context::default());
+}
+
+#[test]
+#[should_panic(expected = "invalid body for `f`")]
+fn test_context_macro_unknown_variable() {
+    let _ctx = context! { f(a) = a + b };
+}
+
+#[test]
+#[should_panic(expected = "invalid body for `f`")]
+fn test_context_macro_invalid_body() {
+    let _ctx = context! { f(a) = a + (1, 2) };
+}
+
+#[test]
+fn test_try_inline_fn() {
+    let ctx = Context::default()
+        .with_var("k", 3.0)
+        .unwrap()
+        .with_expected_vars(["x"].into_iter().collect());
+
+    let scale = ctx.try_inline_fn("scale", &["a"], "k * a");
+    assert!(scale.is_ok());
+    // only the parameters are variables, even when others are expected
+    assert!(ctx.try_inline_fn("f", &["a"], "a + x").is_err());
+    assert!(ctx.try_inline_fn("f", &["a"], "a + b").is_err());
+    assert!(ctx.try_inline_fn("f", &["a"], "a + (1, 2)").is_err());
+
+    // the name and parameters don't have to be static
+    let (name, param) = (String::from("shift"), String::from("b"));
+    let shift = ctx.try_inline_fn(name, &[param.as_str()], "b + k").unwrap();
+    assert_eq!(shift.params.get(0), Some("b"));
+
+    let parser = Parser::new_with_ctx(
+        ctx.with_fn(scale.unwrap()).unwrap().with_fn(shift).unwrap(),
+    );
+    let xprs = parser.parse("scale(x) + shift(b = x)").unwrap();
+    assert_eq!(xprs.eval(&[("x", 2.0)].into()), Ok(11.0));
+}
+
+#[test]
+fn test_layered_contexts() {
+    let base = Arc::new(context! {
+        x = 1.0,
+        y = 2.0,
+        double = double/1,
+        expect [a]
+    });
+    let project = Arc::new(
+        Context::default()
+            .with_parent(Arc::clone(&base))
+            .unwrap()
+            .with_shadow("y", 3.0),
+    );
+    let request = Context::default()
+        .with_parent(Arc::clone(&project))
+        .unwrap()
+        .with_var("z", 4.0)
+        .unwrap();
+
+    assert_eq!(request.get("x"), Some(&Symbol::Variable(1.0)));
+    assert_eq!(request.get("y"), Some(&Symbol::Variable(3.0)));
+    assert_eq!(request.get("z"), Some(&Symbol::Variable(4.0)));
+    assert_eq!(request.get_local("y"), None);
+    assert_eq!(base.get("y"), Some(&Symbol::Variable(2.0)));
+    assert!(project.shadows("y"));
+    assert!(!project.shadows("x"));
+    assert!(!request.shadows("z"));
+    assert_eq!(request.get_expected_vars(), Some(&["a"].into()));
+    assert_eq!(request.parent(), Some(&project));
+
+    let parser = Parser::new_with_ctx(request);
+    let xprs = parser.parse("double(x) + y * z + a").unwrap();
+    assert_eq!(xprs.eval(&[("a", 1.0)].into()), Ok(15.0));
+    assert!(parser.parse("b").is_err());
+}
+
+#[test]
+fn test_shadowing() {
+    let base = Arc::new(context! { x = 1.0, double = double/1 });
+    let mut layer = Context::default().with_parent(Arc::clone(&base)).unwrap();
+
+    assert_eq!(layer.set_var("y", 2.0), Ok(None));
+    assert_eq!(layer.set_var("y", 3.0), Ok(Some(Symbol::Variable(2.0))));
+    // overriding a parent's symbol has to be explicit
+    assert_eq!(
+        layer.set_var("x", 4.0),
+        Err(ContextError::ImplicitShadowing("x".to_owned()))
+    );
+    assert_eq!(
+        layer.set_fn(xprs_fn!("double", |x| 3.0 * x, 1)),
+        Err(ContextError::ImplicitShadowing("double".to_owned()))
+    );
+    assert_eq!(layer.shadow("x", 4.0), Some(Symbol::Variable(1.0)));
+    assert!(layer.shadows("x"));
+    // once shadowed, the symbol can be set
+    assert_eq!(layer.set_var("x", 5.0), Ok(Some(Symbol::Variable(4.0))));
+    assert!(matches!(
+        layer.shadow("double", xprs_fn!("double", |x| 3.0 * x, 1)),
+        Some(Symbol::Function(func)) if func.name == "double"
+    ));
+    assert_eq!(base.get("x"), Some(&Symbol::Variable(1.0)));
+
+    let parser = Parser::new_with_ctx(layer);
+    assert_eq!(
+        parser.parse("double(x) + y").unwrap().eval_no_vars(),
+        Ok(18.0)
+    );
+}
+
+#[test]
+fn test_implicit_shadowing() {
+    let base = Arc::new(context! { x = 1.0, y = 2.0 });
+    let err = Err(ContextError::ImplicitShadowing("x".to_owned()));
+
+    // whatever the order the builders are called in
+    let layer = Context::default().with_parent(Arc::clone(&base)).unwrap();
+    assert_eq!(layer.with_var("x", 3.0), err);
+    assert_eq!(
+        Context::default()
+            .with_var("x", 3.0)
+            .unwrap()
+            .with_parent(Arc::clone(&base)),
+        err
+    );
+    assert_eq!(
+        context! { x = 3.0, y = 4.0 }.with_parent(Arc::clone(&base)),
+        err
+    );
+    assert_eq!(
+        Context::default()
+            .with_symbols([("x", Symbol::Variable(3.0))].into())
+            .unwrap()
+            .with_parent(Arc::clone(&base)),
+        err
+    );
+
+    // explicit shadowing is kept when the parent is attached afterwards
+    let mut layer = Context::default()
+        .with_shadow("x", 3.0)
+        .with_parent(Arc::clone(&base))
+        .unwrap();
+    assert!(layer.shadows("x"));
+    assert_eq!(layer.set_var("x", 4.0), Ok(Some(Symbol::Variable(3.0))));
+    assert!(layer.set_var("y", 4.0).is_err());
+}
+
+#[test]
+fn test_namespaces() {
+    let units = context! { km = 1000.0 };
+    let phys = context! { c = 299_792_458.0, h = 6.626_070_15e-34 }
+        .with_namespace("units", units);
+    let math = context! { double = double/1, add = add/2 };
+    let stats = context! { add(a, b) = (a + b) / 2 };
+    let ctx = Context::default()
+        .with_var("c", 3.0)
+        .unwrap()
+        .with_namespace("phys", phys)
+        .with_namespace("math", math)
+        .with_namespace("stats", stats)
+        .with_import("math");
+    let parser = Parser::new_with_ctx(ctx);
+
+    let test_cases = [
+        ("phys.c", 299_792_458.0),
+        ("phys.units.km * 2", 2000.0),
+        // root symbols take precedence over imported ones
+        ("c", 3.0),
+        ("math.double(c)", 6.0),
+        ("double(c)", 6.0),
+        ("stats.add(1, 2)", 1.5),
+        ("math.add(1, 2)", 3.0),
+    ];
+    for (expr, expected) in test_cases {
+        let xprs = parser.parse(expr).unwrap();
+        assert_eq!(xprs.eval_no_vars(), Ok(expected), "{expr}");
+    }
+
+    let xprs = parser.parse("stats.add(x, 1)").unwrap();
+    assert_eq!(xprs.to_string(), "stats.add(x, 1)");
+    assert_eq!(
+        parser.ctx().get("phys.units.km"),
+        Some(&Symbol::Variable(1000.0))
+    );
+    assert_eq!(parser.ctx().get("phys.x"), None);
+}
+
+#[test]
+fn test_namespaces_errors() {
+    use miette::Diagnostic as _;
+
+    let ctx = Context::default()
+        .with_namespace("math", context! { double = double/1, add = add/2 })
+        .with_namespace("maths", Context::default())
+        .with_namespace("stats", context! { add(a, b) = (a + b) / 2 })
+        .with_import("math")
+        .with_import("stats");
+    let layer = Context::default().with_parent(Arc::new(ctx)).unwrap();
+    let parser = Parser::new_with_ctx(layer);
+
+    let error = |input| {
+        let err = parser.parse(input).unwrap_err();
+        let help = err.help().unwrap().to_string();
+        (err.to_string(), help)
+    };
+    assert_eq!(
+        error("mth.double(2)"),
+        (
+            "Unknown namespace: `mth`".to_owned(),
+            "Try replacing it with one of the following: `math`, `maths`."
+                .to_owned()
+        )
+    );
+    assert_eq!(
+        error("math.dble(2)"),
+        (
+            "Unknown symbol in namespace: `math.dble`".to_owned(),
+            "Try replacing it with one of the following: `math.double`."
+                .to_owned()
+        )
+    );
+    assert_eq!(
+        error("math.double.x"),
+        (
+            "Unknown namespace: `math.double`".to_owned(),
+            "Nothing is defined there.".to_owned()
+        )
+    );
+    assert_eq!(
+        error("add(1, 2)"),
+        (
+            "Ambiguous identifier: `add`".to_owned(),
+            "Try qualifying it with one of the following: `math.add`, `stats.add`."
+                .to_owned()
+        )
+    );
+    assert!(parser.parse("double(2) + math.add(1, 2)").is_ok());
+}
+
+#[test]
+fn test_constants() {
+    let parser = Parser::default();
+    let test_cases = [
+        ("pi", core::f64::consts::PI),
+        ("e", core::f64::consts::E),
+        ("tau", core::f64::consts::TAU),
+        ("phi", 1.618_033_988_749_895),
+        ("sqrt2", core::f64::consts::SQRT_2),
+        ("inf", f64::INFINITY),
+        ("-inf", f64::NEG_INFINITY),
+    ];
+    for (expr, expected) in test_cases {
+        let xprs = parser.parse(expr).unwrap();
+        assert_eq!(xprs.eval_no_vars(), Ok(expected), "{expr}");
+    }
+    assert!(parser
+        .parse("nan")
+        .unwrap()
+        .eval_no_vars()
+        .unwrap()
+        .is_nan());
+    // case-sensitive by default
+    assert_eq!(parser.parse("PI").unwrap().vars, ["PI"].into());
+}
+
+#[test]
+fn test_constants_registry() {
+    let ctx = Context::default()
+        .with_constant("g", 9.81)
+        .with_hidden_constant("e")
+        .with_case_insensitive_constants(true);
+    let parser = Parser::new_with_ctx(ctx);
+
+    let test_cases = [
+        ("PI", core::f64::consts::PI),
+        ("Tau", core::f64::consts::TAU),
+        ("G", 9.81),
+        ("2g", 19.62),
+    ];
+    for (expr, expected) in test_cases {
+        let xprs = parser.parse(expr).unwrap();
+        assert_eq!(xprs.eval_no_vars(), Ok(expected), "{expr}");
+    }
+    assert_eq!(parser.ctx().get_constant("pI"), Some(core::f64::consts::PI));
+    assert_eq!(parser.ctx().get_constant("E"), None);
+    let xprs = parser.parse("e * 2").unwrap();
+    assert_eq!(xprs.eval(&[("e", 3.0)].into()), Ok(6.0));
+
+    // layers inherit the settings and added constants
+    let layer = Context::default()
+        .with_parent(Arc::new(parser.ctx().clone()))
+        .unwrap()
+        .with_hidden_constant("g");
+    assert!(layer.case_insensitive_constants());
+    assert_eq!(layer.get_constant("Pi"), Some(core::f64::consts::PI));
+    assert_eq!(layer.get_constant("g"), None);
+    assert_eq!(parser.ctx().get_constant("g"), Some(9.81));
+}
+
+#[test]
+fn test_disabled_built_ins() {
+    let ctx = Context::default()
+        .with_built_ins(false)
+        .with_constant("g", 9.81);
+    let parser = Parser::new_with_ctx(ctx);
+
+    let xprs = parser.parse("e * g").unwrap();
+    assert_eq!(xprs.vars, ["e"].into());
+    assert_eq!(xprs.eval(&[("e", 2.0)].into()), Ok(19.62));
+    // `sin` is a variable, implicitly multiplied
+    let xprs = parser.parse("sin(x)").unwrap();
+    assert_eq!(xprs.vars, ["sin", "x"].into());
+
+    let layer = Context::default()
+        .with_parent(Arc::new(parser.ctx().clone()))
+        .unwrap();
+    assert!(!layer.built_ins_enabled());
+    assert!(layer.with_built_ins(true).get_constant("pi").is_some());
+}
+
+#[test]
+fn test_built_in_functions_filter() {
+    use miette::Diagnostic as _;
+
+    let denied = Context::default()
+        .with_denied_built_in_functions(["factorial", "gamma", "recip"].into());
+    let parser = Parser::new_with_ctx(denied);
+    assert!(parser.parse("sin(1) + ln(2)").is_ok());
+    for expr in ["factorial(3)", "1 + gamma(x)", "invert(2)", "x!", "3! + 1"] {
+        assert!(parser.parse(expr).is_err(), "{expr}");
+    }
+    let err = parser.parse("2 * factorial(3)").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Function not permitted in this context: `factorial`"
+    );
+    assert_eq!(
+        err.help().unwrap().to_string(),
+        "This function has been disabled, try using another one."
+    );
+
+    let allowed = Context::default()
+        .with_allowed_built_in_functions(["sin", "cos"].into());
+    let layer = Context::default()
+        .with_parent(Arc::new(allowed))
+        .unwrap()
+        .with_denied_built_in_functions(["cos"].into())
+        // context functions override the built-ins and are always permitted
+        .with_fn(xprs_fn!("ln", consts::double, 1))
+        .unwrap();
+    let parser = Parser::new_with_ctx(layer);
+    assert!(parser.parse("sin(x) + ln(x) + pi").is_ok());
+    assert!(parser.parse("cos(x)").is_err());
+    assert!(parser.parse("tan(x)").is_err());
+    // `!` is `factorial`, which isn't allowed
+    let err = parser.parse("x!").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Function not permitted in this context: `factorial`"
+    );
+    assert_eq!(parser.parse("ln(2)").unwrap().eval_no_vars(), Ok(4.0));
+}
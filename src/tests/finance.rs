@@ -0,0 +1,84 @@
+/* Crate imports */
+use crate::{Context, Parser};
+
+/// Reference values computed with 50 digits decimals (they match the spreadsheets' examples).
+const REFERENCES: [(&str, f64); 11] = [
+    ("fin.pmt(0.08 / 12, 10, 10000)", -1_037.032_089_359_152_2),
+    (
+        "fin.fv(0.06 / 12, 10, -200, -500, 1)",
+        2_581.403_374_060_179,
+    ),
+    ("fin.pv(0.08 / 12, 240, 500)", -59_777.145_851_188_02),
+    (
+        "fin.nper(0.01, -100, -1000, 10000, 1)",
+        59.673_865_674_294_63,
+    ),
+    ("fin.rate(48, -200, 8000)", 0.007_701_472_488_202_044),
+    (
+        "fin.npv(0.1, -10000, 3000, 4200, 6800)",
+        1_188.443_412_335_223,
+    ),
+    (
+        "fin.irr(-70000, 12000, 15000, 18000, 21000, 26000)",
+        0.086_630_948_036_531_61,
+    ),
+    ("fin.compound(1000, 0.05, 10, 12)", 1_647.009_497_690_283),
+    (
+        "fin.compound(principal = 100, rate = 0.1, periods = 2)",
+        121.0,
+    ),
+    // no interest
+    ("fin.pmt(0, 10, 1000)", -100.0),
+    ("fin.fv(rate = 0, nper = 10, pmt = -100)", 1000.0),
+];
+
+#[test]
+fn test_finance_functions_accuracy() {
+    let parser =
+        Parser::new_with_ctx(Context::default().with_finance_functions("fin"));
+    for (expr, expected) in REFERENCES {
+        let result = parser.parse(expr).unwrap().eval_no_vars().unwrap();
+        let error = ((result - expected) / expected).abs();
+        assert!(error < 1e-10, "{expr}: got {result}, expected {expected}");
+    }
+}
+
+#[test]
+fn test_finance_functions_consistency() {
+    let parser =
+        Parser::new_with_ctx(Context::default().with_finance_functions("fin"));
+    let eval = |expr, value| {
+        parser
+            .parse(expr)
+            .unwrap()
+            .eval(&[("x", value)].into())
+            .unwrap()
+    };
+
+    let payment = eval("fin.pmt(0.05, 20, 10000, 500, 1)", 0.0);
+    let rate = eval("fin.rate(20, x, 10000, 500, 1)", payment);
+    assert!((rate - 0.05).abs() < 1e-10, "{rate}");
+    let nper = eval("fin.nper(0.05, x, 10000, 500, 1)", payment);
+    assert!((nper - 20.0).abs() < 1e-9, "{nper}");
+    // the net present value at the internal rate of return is null
+    let irr = eval("fin.irr(-100, 60, 60)", 0.0);
+    assert!(eval("fin.npv(x, 60, 60) - 100", irr).abs() < 1e-9);
+    // no sign change, no solution
+    assert!(eval("fin.irr(100, 60, 60)", 0.0).is_nan());
+}
+
+#[test]
+fn test_finance_functions_names_as_variables() {
+    let parser =
+        Parser::new_with_ctx(Context::default().with_finance_functions("fin"));
+    let xprs = parser.parse("fin.pmt(rate, nper, pv) + fv").unwrap();
+    let result = xprs
+        .eval(
+            &[("rate", 0.0), ("nper", 10.0), ("pv", 1000.0), ("fv", 1.0)]
+                .into(),
+        )
+        .unwrap();
+    assert_eq!(result, -99.0);
+    // the names of the pack are only functions in its namespace
+    assert!(parser.parse("pmt(0, 10, 1000)").is_err());
+}
@@ -1,7 +1,16 @@
 /* Modules */
+mod context;
 mod factorial;
+#[cfg(feature = "finance")]
+mod finance;
 mod issues;
 mod macros;
 mod parser;
+#[cfg(feature = "physics")]
+mod physics;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "stats")]
+mod stats;
 mod thread_safety;
 mod xprs;
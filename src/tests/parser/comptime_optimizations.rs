@@ -19,12 +19,16 @@ use crate::{
 /// 2^2^(2^2 + 1)
 /// 2 * (3 + (4 - 1))
 /// sin(-cos(2))
+////// Integers beyond 2^53
+/// 2 ^ 53 + 1
+/// (2 ^ 53 + 1) / 3
+/// 1 << 63
 ////// With variables
 /// x - x
 ////// Commutativity
 /// (a + b) - (b + a)
 #[allow(clippy::too_many_lines)]
-fn get_valid_test_cases() -> [(&'static str, Xprs<'static>); 13] {
+fn get_valid_test_cases() -> [(&'static str, Xprs<'static>); 16] {
     [
         (
             "2 + pi",
@@ -107,6 +111,31 @@ fn get_valid_test_cases() -> [(&'static str, Xprs<'static>); 13] {
                 vars: [].into(),
             },
         ),
+        (
+            "2 ^ 53 + 1",
+            Xprs {
+                root: Element::Integer(9_007_199_254_740_993),
+                vars: [].into(),
+            },
+        ),
+        (
+            "(2 ^ 53 + 1) / 3",
+            Xprs {
+                root: Element::Number(3_002_399_751_580_331.),
+                vars: [].into(),
+            },
+        ),
+        (
+            "1 << 63",
+            Xprs {
+                root: BinOp::new_element(
+                    Operator::ShiftLeft,
+                    Element::Number(1.),
+                    Element::Number(63.),
+                ),
+                vars: [].into(),
+            },
+        ),
         (
             "x - x",
             Xprs {
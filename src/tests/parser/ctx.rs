@@ -22,16 +22,16 @@ const MEAN: Function = xprs_fn!("mean", mean);
 fn get_parser_with_ctx() -> Parser<'static> {
     let mut ctx = Context::default();
 
-    ctx.set_var("x", 2.0);
-    ctx.set_var("phi", 1.618_033_988_749_895);
+    ctx.set_var("x", 2.0).unwrap();
+    ctx.set_var("phi", 1.618_033_988_749_895).unwrap();
 
-    ctx.set_fn(DOUBLE);
-    ctx.set_fn(ADD);
+    ctx.set_fn(DOUBLE).unwrap();
+    ctx.set_fn(ADD).unwrap();
 
     let mut parser = Parser::new_with_ctx(ctx);
 
-    parser.ctx_mut().set_var("y", 1.0);
-    parser.ctx_mut().set_fn(MEAN);
+    parser.ctx_mut().set_var("y", 1.0).unwrap();
+    parser.ctx_mut().set_fn(MEAN).unwrap();
 
     parser
 }
@@ -104,3 +104,26 @@ fn test_ctx() {
         assert_eq!(res.unwrap().root, expected, "\n`{expr}`");
     }
 }
+
+#[test]
+fn test_owned_function_names() {
+    // e.g. read from a config file
+    let config = [("kilo".to_owned(), 1e3), ("mega".to_owned(), 1e6)];
+    let mut ctx = Context::default();
+    for (name, factor) in config {
+        ctx.set_fn(Function::new_dyn(
+            name,
+            move |args| args[0] * factor,
+            Some(1),
+        ))
+        .unwrap();
+    }
+    ctx.set_fn(DOUBLE.with_name("twice".to_owned())).unwrap();
+    let parser = Parser::new_with_ctx(ctx);
+
+    let xprs = parser.parse("kilo(x) + mega(x) + twice(x)").unwrap();
+    assert_eq!(xprs.to_string(), "((kilo(x) + mega(x)) + twice(x))");
+    assert_eq!(xprs.eval(&[("x", 3.0)].into()), Ok(3_003_006.0));
+    // the original name isn't registered
+    assert!(parser.ctx().get("double").is_none());
+}
@@ -0,0 +1,68 @@
+/* Crate imports */
+use crate::{element::Element, physics, Context, Parser};
+
+#[test]
+fn test_physical_constants() {
+    let ctx = Context::default().with_physical_constants("phys");
+    let parser = Parser::new_with_ctx(ctx);
+
+    let test_cases = [
+        ("phys.c", 299_792_458.0),
+        ("phys.k_B * phys.N_A", physics::get("R").unwrap().value),
+        ("phys.e_charge * phys.N_A", physics::get("F").unwrap().value),
+        ("phys.h / (2pi)", physics::get("hbar").unwrap().value),
+        ("phys.g_n * 2", 19.6133),
+    ];
+    for (expr, expected) in test_cases {
+        let result = parser.parse(expr).unwrap().eval_no_vars().unwrap();
+        let error = ((result - expected) / expected).abs();
+        assert!(error < 1e-9, "{expr}: got {result}, expected {expected}");
+    }
+
+    // namespace members are constants, not variables, and `c` alone is still a variable
+    let xprs = parser.parse("phys.m_e * c^2").unwrap();
+    assert_eq!(xprs.vars, ["c"].into());
+    // the built-in constants are not members of the namespace
+    assert!(parser.parse("phys.pi").is_err());
+    assert_eq!(parser.ctx().get("phys.c"), None);
+}
+
+#[cfg(feature = "compile-time-optimizations")]
+#[test]
+fn test_physical_constants_folding() {
+    let ctx = Context::default()
+        .with_physical_constants("phys")
+        .with_import("phys");
+    let parser = Parser::new_with_ctx(ctx);
+
+    let xprs = parser.parse("m_e * phys.c^2").unwrap();
+    let expected =
+        physics::get("m_e").unwrap().value * 299_792_458.0_f64.powi(2);
+    assert!(
+        matches!(xprs.root, Element::Number(value) if (value - expected).abs() < expected * 1e-15),
+        "{xprs}"
+    );
+    assert!(xprs.vars.is_empty());
+}
+
+#[test]
+fn test_physical_constants_registry() {
+    use miette::Diagnostic as _;
+
+    assert!(physics::get("c").unwrap().is_exact());
+    let gravitation = physics::get("G").unwrap();
+    assert!(!gravitation.is_exact());
+    assert!((gravitation.relative_uncertainty() - 2.2e-5).abs() < 1e-6);
+    assert!(physics::CONSTANTS
+        .iter()
+        .all(|constant| constant.value > 0.0 && constant.uncertainty >= 0.0));
+
+    let parser = Parser::new_with_ctx(
+        Context::default().with_physical_constants("phys"),
+    );
+    let err = parser.parse("phys.hbr").unwrap_err();
+    assert_eq!(
+        err.help().unwrap().to_string(),
+        "Try replacing it with one of the following: `phys.hbar`, `phys.h`."
+    );
+}
@@ -0,0 +1,186 @@
+/* Built-in imports */
+extern crate alloc;
+use alloc::sync::Arc;
+/* Dependencies imports */
+use serde::de::DeserializeSeed as _;
+/* Crate imports */
+use crate::{context, xprs::Xprs, Context, Parser, Symbol};
+
+fn double(x: f64) -> f64 {
+    x * 2.0
+}
+
+fn add(x: f64, y: f64) -> f64 {
+    x + y
+}
+
+#[test]
+fn test_xprs_round_trip() {
+    let test_cases = [
+        "2 * x",
+        "sin(x) * 2 + y!",
+        "-(a ^ b) % 3",
+        "max(x, y, 1) << 2",
+        "atan2(y, x) // 1",
+    ];
+    for expr in test_cases {
+        let xprs = Xprs::try_from(expr).unwrap();
+        let json = serde_json::to_string(&xprs).unwrap();
+        let restored: Xprs = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, xprs, "{expr}: {json}");
+    }
+}
+
+#[test]
+fn test_non_finite_numbers() {
+    let xprs = Xprs::try_from("x + inf").unwrap();
+    let json = serde_json::to_string(&xprs).unwrap();
+    assert!(json.contains(r#"{"number":"inf"}"#), "{json}");
+    let restored: Xprs = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, xprs);
+
+    for (expr, repr) in [("x * -inf", "-inf"), ("x * nan", "nan")] {
+        let xprs = Xprs::try_from(expr).unwrap();
+        let json = serde_json::to_string(&xprs).unwrap();
+        assert!(json.contains(&format!(r#""{repr}""#)), "{json}");
+        let restored: Xprs = serde_json::from_str(&json).unwrap();
+        // `NaN` isn't equal to itself
+        assert_eq!(restored.to_string(), xprs.to_string(), "{expr}");
+    }
+
+    let ctx = Context::default()
+        .with_var("x", f64::INFINITY)
+        .unwrap()
+        .with_constant("big", f64::NEG_INFINITY);
+    let json = serde_json::to_string(&ctx).unwrap();
+    let restored: Context = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, ctx);
+
+    let json = r#"{ "number": "infinity" }"#;
+    assert!(serde_json::from_str::<Xprs>(json).is_err());
+}
+
+#[test]
+fn test_xprs_structure() {
+    let xprs = Xprs::try_from("2 * x + sqrt(y)").unwrap();
+    assert_eq!(
+        serde_json::to_value(&xprs).unwrap(),
+        serde_json::json!({
+            "bin_op": {
+                "op": "plus",
+                "lhs": {
+                    "bin_op": {
+                        "op": "times",
+                        "lhs": { "number": 2.0 },
+                        "rhs": { "variable": "x" }
+                    }
+                },
+                "rhs": {
+                    "function": {
+                        "name": "sqrt",
+                        "args": [{ "variable": "y" }]
+                    }
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn test_xprs_custom_functions() {
+    let ctx = context! { double = double/1 }
+        .with_namespace("math", context! { add = add/2 });
+    let parser = Parser::new_with_ctx(ctx);
+    let xprs = parser.parse("double(x) + math.add(x, 1)").unwrap();
+    let json = serde_json::to_string(&xprs).unwrap();
+
+    let mut deserializer = serde_json::Deserializer::from_str(&json);
+    let restored = parser
+        .ctx()
+        .seed::<Xprs>()
+        .deserialize(&mut deserializer)
+        .unwrap();
+    assert_eq!(restored, xprs);
+    assert_eq!(restored.eval(&[("x", 2.0)].into()), Ok(7.0));
+
+    // only the built-in functions are known without a registry
+    let err = serde_json::from_str::<Xprs>(&json).unwrap_err();
+    assert!(err.to_string().starts_with("unknown function `double`"));
+    // arities are checked
+    let json = r#"{ "function": { "name": "sin", "args": [] } }"#;
+    let err = serde_json::from_str::<Xprs>(json).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("function `sin` called with 0 arguments, expected 1"));
+}
+
+#[test]
+fn test_xprs_variables() {
+    let xprs = Parser::default().parse("x + y").unwrap();
+    let json = serde_json::to_string(&xprs).unwrap();
+
+    // the variables are checked like when parsing
+    let ctx = Context::default().with_constant("y", 1.0);
+    let mut deserializer = serde_json::Deserializer::from_str(&json);
+    let err = ctx
+        .seed::<Xprs>()
+        .deserialize(&mut deserializer)
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("Import error: `y` is defined by the context"));
+    let ctx = Context::default().with_expected_vars(["x"].into());
+    let mut deserializer = serde_json::Deserializer::from_str(&json);
+    let err = ctx
+        .seed::<Xprs>()
+        .deserialize(&mut deserializer)
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("Import error: variable `y` is not declared"));
+    let json = r#"{ "variable": "pi" }"#;
+    let err = serde_json::from_str::<Xprs>(json).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("Import error: `pi` is defined by the context"));
+}
+
+#[test]
+fn test_context_round_trip() {
+    let registry = context! { double = double/1, add = add/2 };
+    let ctx = context! { x = 1.5, double = double/1, expect [x, y] }
+        .with_namespace("math", context! { add = add/2, half = 0.5 })
+        .with_import("math")
+        .with_constant("g", 9.81)
+        .with_hidden_constant("e")
+        .with_denied_built_in_functions(["sin"].into())
+        .with_shadow("z", 2.0);
+    let json = serde_json::to_string(&ctx).unwrap();
+
+    let mut deserializer = serde_json::Deserializer::from_str(&json);
+    let restored = registry
+        .seed::<Context>()
+        .deserialize(&mut deserializer)
+        .unwrap();
+    // functions are resolved against the registry, so they're compared by name
+    assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+    let xprs = Parser::new_with_ctx(restored.clone())
+        .parse("double(x) + add(half, y)")
+        .unwrap();
+    assert_eq!(xprs.eval(&[("y", 1.0)].into()), Ok(4.5));
+    // explicit shadows are kept
+    let base = Arc::new(context! { z = 1.0 });
+    let layer = restored.with_parent(base).unwrap();
+    assert_eq!(layer.get("z"), Some(&Symbol::Variable(2.0)));
+
+    // built-in functions are always resolvable
+    let json =
+        r#"{ "variables": { "x": 2 }, "functions": { "root": "sqrt" } }"#;
+    let restored: Context = serde_json::from_str(json).unwrap();
+    let xprs = Parser::new_with_ctx(restored).parse("root(8x)").unwrap();
+    assert_eq!(xprs.eval_no_vars(), Ok(4.0));
+    let err =
+        serde_json::from_str::<Context>(r#"{ "functions": { "f": "f" } }"#)
+            .unwrap_err();
+    assert!(err.to_string().starts_with("unknown function `f`"));
+}
@@ -0,0 +1,74 @@
+/* Crate imports */
+use crate::{Context, Parser};
+
+/// Reference values computed with Python's `math` and `statistics` modules.
+const REFERENCES: [(&str, f64); 30] = [
+    ("median(3, 1, 2)", 2.0),
+    ("median(4, 1, 3, 2)", 2.5),
+    ("median(7)", 7.0),
+    ("var(2, 4, 4, 4, 5, 5, 7, 9)", 4.571_428_571_428_571),
+    ("stddev(2, 4, 4, 4, 5, 5, 7, 9)", 2.138_089_935_299_395),
+    ("erf(0.5)", 0.520_499_877_813_046_5),
+    ("erf(1)", 0.842_700_792_949_714_9),
+    ("erf(-2)", -0.995_322_265_018_952_7),
+    ("erfc(1)", 0.157_299_207_050_285_13),
+    ("erf(0.1)", 0.112_462_916_018_284_9),
+    ("erfc(3)", 2.209_049_699_858_543_8e-5),
+    ("erfc(5)", 1.537_459_794_428_035_1e-12),
+    ("erfc(10)", 2.088_487_583_762_545e-45),
+    ("lgamma(0.5)", 0.572_364_942_924_700_4),
+    ("lgamma(10)", 12.801_827_480_081_467),
+    ("lgamma(200)", 857.933_669_825_857_5),
+    ("lgamma(100000)", 1_051_287.708_973_656_6),
+    ("lgamma(-0.5)", 1.265_512_123_484_645),
+    ("beta(2, 3)", 0.083_333_333_333_333_33),
+    ("beta(0.5, 0.5)", core::f64::consts::PI),
+    ("binomial(52, 5)", 2_598_960.0),
+    ("binomial(60, 30)", 118_264_581_564_861_424.0),
+    ("normal_pdf(0)", 0.398_942_280_401_432_7),
+    ("normal_pdf(1, sigma = 2)", 0.176_032_663_382_149_76),
+    ("normal_cdf(1.96)", 0.975_002_104_851_779_5),
+    ("normal_cdf(12, 10, 2)", 0.841_344_746_068_542_9),
+    ("gcd(12, -18, 30)", 6.0),
+    ("lcm(4, 6, 10)", 60.0),
+    ("deg(pi) + rad(180)", 180.0 + core::f64::consts::PI),
+    ("clamp(5, 0, 1) + sign(-3) + lerp(2, 4, 0.25)", 2.5),
+];
+
+#[test]
+fn test_stats_functions_accuracy() {
+    let parser = Parser::new_with_ctx(
+        Context::default().with_stats_functions().unwrap(),
+    );
+    for (expr, expected) in REFERENCES {
+        let result = parser.parse(expr).unwrap().eval_no_vars().unwrap();
+        let error = ((result - expected) / expected).abs();
+        assert!(error < 1e-13, "{expr}: got {result}, expected {expected}");
+    }
+}
+
+#[test]
+fn test_stats_functions_edge_cases() {
+    let parser = Parser::new_with_ctx(
+        Context::default().with_stats_functions().unwrap(),
+    );
+    let eval = |expr| parser.parse(expr).unwrap().eval_no_vars().unwrap();
+
+    for expr in [
+        "median(1, nan)",
+        "binomial(2.5, 1)",
+        "gcd(1.5, 3)",
+        "clamp(0, 1, -1)",
+        "erf(nan)",
+    ] {
+        assert!(eval(expr).is_nan(), "{expr}");
+    }
+    assert_eq!(eval("sign(0)"), 0.0);
+    assert_eq!(eval("lgamma(-2)"), f64::INFINITY);
+    assert_eq!(eval("lcm(0, 5)"), 0.0);
+    assert_eq!(eval("binomial(n = 5, k = 7)"), 0.0);
+    assert_eq!(eval("erf(-inf) + erfc(-inf)"), 1.0);
+    assert_eq!(eval("erfc(30)"), 0.0);
+    assert!(parser.parse("var(1)").is_err());
+    assert!(parser.parse("median()").is_err());
+}
@@ -1,10 +1,12 @@
 /* Crate imports */
 use crate::{
     context::{Context, Symbol},
+    dag::XprsDag,
     element::{BinOp, Element, FunctionCall, UnOp},
     parser::{ErrorKind, ParseError, Parser},
-    token::{FnPointer, Function, Identifier, Operator},
-    xprs::{BindError, EvalError, Xprs},
+    token::{Arity, Domain, FnPointer, Function, Identifier, Operator, Params},
+    trace::{Trace, TraceStep},
+    xprs::{BindError, EvalError, FailedNode, Xprs},
 };
 
 const fn is_sized_send_sync_unpin<T: Sized + Send + Sync + Unpin>() {}
@@ -14,6 +16,8 @@ const fn test_thread_safety() {
     // context module
     is_sized_send_sync_unpin::<Context>();
     is_sized_send_sync_unpin::<Symbol>();
+    // dag module
+    is_sized_send_sync_unpin::<XprsDag<'_>>();
     // element module
     is_sized_send_sync_unpin::<BinOp<'_>>();
     is_sized_send_sync_unpin::<Element<'_>>();
@@ -24,12 +28,19 @@ const fn test_thread_safety() {
     is_sized_send_sync_unpin::<ParseError>();
     is_sized_send_sync_unpin::<Parser>();
     // token module
+    is_sized_send_sync_unpin::<Arity>();
+    is_sized_send_sync_unpin::<Domain>();
     is_sized_send_sync_unpin::<FnPointer>();
     is_sized_send_sync_unpin::<Function>();
     is_sized_send_sync_unpin::<Identifier>();
     is_sized_send_sync_unpin::<Operator>();
+    is_sized_send_sync_unpin::<Params>();
+    // trace module
+    is_sized_send_sync_unpin::<Trace>();
+    is_sized_send_sync_unpin::<TraceStep>();
     // xprs module
     is_sized_send_sync_unpin::<BindError>();
     is_sized_send_sync_unpin::<EvalError>();
+    is_sized_send_sync_unpin::<FailedNode>();
     is_sized_send_sync_unpin::<Xprs<'_>>();
 }
@@ -0,0 +1,141 @@
+/* Built-in imports */
+use core::iter;
+/* Crate imports */
+use crate::{context, xprs::Xprs, Context, DecodeError, Parser};
+
+fn double(x: f64) -> f64 {
+    x * 2.0
+}
+
+#[test]
+fn test_binary_round_trip() {
+    let parser = Parser::new_with_ctx(
+        context! { double = double/1 }
+            .with_namespace("math", context! { twice = double/1 }),
+    );
+    let test_cases = [
+        "2",
+        "x",
+        "2 * x + sqrt(y)",
+        "-(a ^ b) % 3 + x! - x",
+        "max(x, y, 1) << 2 >> 1 & 7 | 1 xor 2",
+        "atan2(y, x) // 1 + 1 / x",
+        "double(x) + math.twice(x * x)",
+        "-inf + nan",
+        "0xFFFF_FFFF_FFFF_FFFF & x",
+    ];
+    for expr in test_cases {
+        let xprs = parser.parse(expr).unwrap();
+        let bytes = xprs.to_bytes();
+        let decoded = Xprs::from_bytes(&bytes, parser.ctx()).unwrap();
+        assert_eq!(decoded.to_string(), xprs.to_string(), "{expr}");
+        assert_eq!(decoded.vars, xprs.vars, "{expr}");
+        // the encoding is deterministic
+        assert_eq!(decoded.to_bytes(), bytes, "{expr}");
+    }
+}
+
+#[test]
+fn test_binary_layout() {
+    let xprs = Xprs::try_from("x * x + sin(x)").unwrap();
+    let bytes = xprs.to_bytes();
+    #[rustfmt::skip]
+    let expected: &[u8] = &[
+        b'X', b'P', b'R', b'S', 1,
+        // names table: `x` and `sin`, interned
+        2, 1, b'x', 3, b's', b'i', b'n',
+        // (x * x) + sin(x)
+        0x10, 0x12, 0x01, 0, 0x01, 0, 0x02, 1, 1, 0x01, 0,
+    ];
+    assert_eq!(bytes, expected);
+}
+
+#[test]
+fn test_binary_errors() {
+    let parser = Parser::new_with_ctx(context! { double = double/1 });
+    let bytes = parser.parse("double(x) + y").unwrap().to_bytes();
+    let decode = |bytes: &[u8], ctx: &Context| {
+        Xprs::from_bytes(bytes, ctx).map(|xprs| xprs.to_string())
+    };
+
+    // the context must know the functions and the variables must still be variables
+    assert_eq!(
+        decode(&bytes, &Context::default()),
+        Err(DecodeError::UnknownFunction("double".to_owned()))
+    );
+    let ctx = context! { double = double/1, y = 2 };
+    assert_eq!(
+        decode(&bytes, &ctx),
+        Err(DecodeError::NotAVariable("y".to_owned()))
+    );
+    let ctx = context! { double = double/1, expect [x] };
+    assert_eq!(
+        decode(&bytes, &ctx),
+        Err(DecodeError::UndeclaredVariable("y".to_owned()))
+    );
+    let ctx = Context::default().with_denied_built_in_functions(["sin"].into());
+    assert_eq!(
+        decode(&Xprs::try_from("sin(x)").unwrap().to_bytes(), &ctx),
+        Err(DecodeError::UnknownFunction("sin".to_owned()))
+    );
+
+    // malformed bytes
+    let ctx = parser.ctx();
+    assert_eq!(decode(b"XPR", ctx), Err(DecodeError::InvalidHeader));
+    assert_eq!(
+        decode(b"XPRS\x02", ctx),
+        Err(DecodeError::UnsupportedVersion(2))
+    );
+    for len in 5..bytes.len() {
+        assert_eq!(
+            decode(bytes.get(..len).unwrap(), ctx),
+            Err(DecodeError::UnexpectedEnd),
+            "{len}"
+        );
+    }
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(decode(&trailing, ctx), Err(DecodeError::TrailingBytes(1)));
+    assert_eq!(
+        decode(b"XPRS\x01\x00\x1F", ctx),
+        Err(DecodeError::InvalidOpcode(0x1F))
+    );
+    // operators applied to a number of operands they don't accept
+    assert_eq!(
+        decode(b"XPRS\x01\x00\x22\x00\x00\x00\x00\x00\x00\x00\x00\x00", ctx),
+        Err(DecodeError::InvalidOpcode(0x22))
+    );
+    assert_eq!(
+        decode(b"XPRS\x01\x00\x17", ctx),
+        Err(DecodeError::InvalidOpcode(0x17))
+    );
+    // deeply nested trees are rejected before overflowing the stack
+    let negations = |depth| {
+        let mut bytes = b"XPRS\x01\x00".to_vec();
+        bytes.extend(iter::repeat(0x21).take(depth));
+        bytes.extend([0; 9]);
+        bytes
+    };
+    assert!(decode(&negations(255), ctx).is_ok());
+    assert_eq!(decode(&negations(256), ctx), Err(DecodeError::TooDeep));
+    assert_eq!(
+        decode(&negations(2_000_000), ctx),
+        Err(DecodeError::TooDeep)
+    );
+    assert_eq!(
+        decode(b"XPRS\x01\x00\x01\x00", ctx),
+        Err(DecodeError::InvalidStringIndex(0))
+    );
+    assert_eq!(
+        decode(b"XPRS\x01\x01\x01\xFF\x01\x00", ctx),
+        Err(DecodeError::InvalidUtf8)
+    );
+    assert_eq!(
+        decode(b"XPRS\x01\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\x01", ctx),
+        Err(DecodeError::IntegerOverflow)
+    );
+    assert_eq!(
+        decode(b"XPRS\x01\x01\x03sin\x02\x00\x00", ctx),
+        Err(DecodeError::ArityMismatch("sin".to_owned(), 0))
+    );
+}
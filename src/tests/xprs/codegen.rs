@@ -0,0 +1,305 @@
+/* Crate imports */
+use crate::{context, xprs::Xprs, xprs_fn, CodegenError, Context, Parser};
+
+fn double(x: f64) -> f64 {
+    x * 2.0
+}
+
+#[test]
+fn test_rust() {
+    let parser = Parser::new_with_ctx(
+        context! { double = double/1 }
+            .with_fn(xprs_fn!("total", |args| args.iter().sum()))
+            .unwrap()
+            .with_namespace("math", context! { twice = double/1 }),
+    );
+    let test_cases = [
+        ("x", "x"),
+        ("-x! + 2", "((-::xprs::runtime::factorial(x)) + 2.0_f64)"),
+        ("x // 2 % 3 ^ x", "((x / 2.0_f64).trunc() % 3.0_f64.powf(x))"),
+        ("x xor x << 1", "::xprs::runtime::bit_xor(x, ::xprs::runtime::shift_left(x, 1.0_f64))"),
+        ("round(x) + round(x, 2)", "(x.round() + ::xprs::runtime::round(x, 2.0_f64))"),
+        ("log(x) - logn(x, 2)", "(x.log10() - x.log(2.0_f64))"),
+        ("atan2(x, 1) * gamma(x)", "(x.atan2(1.0_f64) * ::xprs::runtime::gamma(x))"),
+        ("min(x, 1) + max(x) + sum(x) + mean(x, x)", "(((f64::INFINITY.min(x).min(1.0_f64) + f64::NEG_INFINITY.max(x)) + (x)) + ((x + x) / 2.0_f64))"),
+        ("double(x) + math.twice(x)", "(double(x) + math::twice(x))"),
+        ("total(x, 1)", "total(&[x, 1.0_f64])"),
+        ("x * inf - nan + 1e300", "(((x * f64::INFINITY) - f64::NAN) + 1e300_f64)"),
+    ];
+    for (expr, body) in test_cases {
+        let xprs = parser.parse(expr).unwrap();
+        assert_eq!(
+            xprs.to_rust("f", &["x"]).unwrap(),
+            format!("#[allow(unused_parens, unused_variables)]\npub fn f(x: f64) -> f64 {{\n    {body}\n}}\n"),
+            "{expr}"
+        );
+    }
+
+    // keywords are raw identifiers, parameters are kept in order
+    let xprs = Xprs::try_from("type + y").unwrap();
+    assert_eq!(
+        xprs.to_rust("match", &["y", "unused", "type"]).unwrap(),
+        "#[allow(unused_parens, unused_variables)]\npub fn r#match(y: f64, unused: f64, r#type: f64) -> f64 {\n    (r#type + y)\n}\n"
+    );
+    // functions of the context shadowing a built-in are called by name
+    let parser = Parser::new_with_ctx(
+        Context::default()
+            .with_fn(xprs_fn!("sin", double, 1))
+            .unwrap(),
+    );
+    assert_eq!(
+        parser.parse("sin(x)").unwrap().to_rust("f", &["x"]).unwrap(),
+        "#[allow(unused_parens, unused_variables)]\npub fn f(x: f64) -> f64 {\n    sin(x)\n}\n"
+    );
+    // calls without arguments, imported since they're folded when parsing
+    let xprs =
+        Xprs::from_sexpr("(+ (sum) (mean))", &Context::default()).unwrap();
+    assert_eq!(
+        xprs.to_rust("f", &[]).unwrap(),
+        "#[allow(unused_parens, unused_variables)]\npub fn f() -> f64 {\n    (0.0_f64 + f64::NAN)\n}\n"
+    );
+}
+
+#[test]
+fn test_rust_errors() {
+    let xprs = Xprs::try_from("x + y").unwrap();
+    assert_eq!(
+        xprs.to_rust("f", &["x"]),
+        Err(CodegenError::UnboundVariable("y".to_owned()))
+    );
+    assert_eq!(
+        xprs.to_rust("f", &["x", "y", "x"]),
+        Err(CodegenError::DuplicateParameter("x".to_owned()))
+    );
+    assert_eq!(
+        xprs.to_rust("self", &["x", "y"]),
+        Err(CodegenError::InvalidIdentifier("self".to_owned()))
+    );
+    assert_eq!(
+        Xprs::try_from("x'").unwrap().to_rust("f", &["x'"]),
+        Err(CodegenError::InvalidIdentifier("x'".to_owned()))
+    );
+}
+
+#[test]
+fn test_c() {
+    let parser = Parser::new_with_ctx(
+        context! { scale = double/1 }
+            .with_namespace("math", context! { twice = double/1 }),
+    );
+    let test_cases = [
+        ("-x + 2", "((-x) + 2.0)"),
+        ("x // 2 % 3 ^ x", "fmod(trunc(x / 2.0), pow(3.0, x))"),
+        ("ln(x) - log(x) * logn(x, 2)", "(log(x) - (log10(x) * (log(x) / log(2.0))))"),
+        ("abs(x) + gamma(x) + atan2(x, 1)", "((fabs(x) + tgamma(x)) + atan2(x, 1.0))"),
+        ("round(x) + round(x, 2)", "(round(x) + (isfinite(pow(10.0, trunc(2.0))) && isfinite(x * pow(10.0, trunc(2.0))) ? round(x * pow(10.0, trunc(2.0))) / pow(10.0, trunc(2.0)) : x))"),
+        ("min(x, 1) - max(x, 2) * fract(x)", "(fmin(x, 1.0) - (fmax(x, 2.0) * (x - trunc(x))))"),
+        ("min(x, 1, 2) + max(x, 2, 3, 4)", "(fmin(fmin(x, 1.0), 2.0) + fmax(fmax(fmax(x, 2.0), 3.0), 4.0))"),
+        ("scale(x) + math.twice(x)", "(scale(x) + math_twice(x))"),
+        ("x * inf - nan", "((x * INFINITY) - NAN)"),
+    ];
+    for (expr, body) in test_cases {
+        let xprs = parser.parse(expr).unwrap();
+        assert_eq!(
+            xprs.to_c("f", &["x"]).unwrap(),
+            format!("double f(double x) {{\n    return {body};\n}}\n"),
+            "{expr}"
+        );
+    }
+
+    // unused parameters are discarded, keywords are rejected
+    let xprs = Xprs::try_from("y").unwrap();
+    assert_eq!(
+        xprs.to_c("g", &["unused", "y"]).unwrap(),
+        "double g(double unused, double y) {\n    (void)unused;\n    return y;\n}\n"
+    );
+    assert_eq!(
+        Xprs::try_from("2").unwrap().to_c("g", &[]).unwrap(),
+        "double g(void) {\n    return 2.0;\n}\n"
+    );
+    assert_eq!(
+        Xprs::try_from("int").unwrap().to_c("g", &["int"]),
+        Err(CodegenError::InvalidIdentifier("int".to_owned()))
+    );
+    assert_eq!(
+        Xprs::try_from("pow").unwrap().to_c("g", &["pow"]),
+        Err(CodegenError::InvalidIdentifier("pow".to_owned()))
+    );
+}
+
+#[test]
+fn test_glsl() {
+    let parser = Parser::new_with_ctx(context! { scale = double/1 });
+    let test_cases = [
+        ("-x + 2", "((-x) + 2.0)"),
+        (
+            "x // 2 % 3 ^ x",
+            "(trunc(x / 2.0) - pow(3.0, x) * trunc(trunc(x / 2.0) / pow(3.0, x)))",
+        ),
+        ("ln(x) - log(x)", "(log(x) - (log(x) / log(10.0)))"),
+        (
+            "atan2(x, 1) + hypot(x, 2)",
+            "(atan(x, 1.0) + sqrt((x * x) + (2.0 * 2.0)))",
+        ),
+        (
+            "min(x, 1) - max(x, 2) * fract(x)",
+            "(min(x, 1.0) - (max(x, 2.0) * (x - trunc(x))))",
+        ),
+        (
+            "min(x, 1, 2) + max(x, 2, 3)",
+            "(min(min(x, 1.0), 2.0) + max(max(x, 2.0), 3.0))",
+        ),
+        ("abs(x) + scale(x)", "(abs(x) + scale(x))"),
+        ("x * inf - nan", "((x * (1.0 / 0.0)) - (0.0 / 0.0))"),
+    ];
+    for (expr, body) in test_cases {
+        let xprs = parser.parse(expr).unwrap();
+        assert_eq!(
+            xprs.to_glsl("f", &["x"]).unwrap(),
+            format!("float f(float x) {{\n    return {body};\n}}\n"),
+            "{expr}"
+        );
+    }
+
+    let xprs = Xprs::try_from("y").unwrap();
+    assert_eq!(
+        xprs.to_glsl("g", &["unused", "y"]).unwrap(),
+        "float g(float unused, float y) {\n    return y;\n}\n"
+    );
+    // `%` takes the sign of `lhs`
+    assert_eq!(
+        Xprs::try_from("-7 % y")
+            .unwrap()
+            .to_glsl("g", &["y"])
+            .unwrap(),
+        "float g(float y) {\n    return ((-7.0) - y * trunc((-7.0) / y));\n}\n"
+    );
+    for name in [
+        "vec2",
+        "mod",
+        "gl_x",
+        "a__b",
+        "filter",
+        "input",
+        "mat2x3",
+        "sampler2D",
+        "usampler2DArray",
+        "sampler2DRectShadow",
+        "image3D",
+        "uimageBuffer",
+    ] {
+        assert_eq!(
+            xprs.to_glsl(name, &["y"]),
+            Err(CodegenError::InvalidIdentifier(name.to_owned()))
+        );
+    }
+    assert!(xprs.to_glsl("f", &["filter", "input"]).is_err());
+    // only the types are reserved
+    for name in ["sampler2", "images", "imageSize2D"] {
+        assert!(xprs.to_glsl(name, &["y"]).is_ok(), "{name}");
+    }
+}
+
+#[test]
+fn test_unsupported() {
+    let parser = Parser::new_with_ctx(
+        Context::default()
+            .with_fn(xprs_fn!("total", |args| args.iter().sum()))
+            .unwrap(),
+    );
+    let xprs = parser
+        .parse("x! + factorial(x) * sum(x, 1) - (x & 1) + total(x) + x!")
+        .unwrap();
+    let unsupported = ["!", "factorial", "sum", "&", "total"]
+        .map(str::to_owned)
+        .to_vec();
+    assert_eq!(
+        xprs.to_c("f", &["x"]),
+        Err(CodegenError::Unsupported(unsupported.clone()))
+    );
+    assert_eq!(
+        xprs.to_glsl("f", &["x"]),
+        Err(CodegenError::Unsupported(unsupported))
+    );
+    assert_eq!(
+        Xprs::try_from("gamma(x) + cbrt(x)")
+            .unwrap()
+            .to_glsl("f", &["x"]),
+        Err(CodegenError::Unsupported(vec![
+            "gamma".to_owned(),
+            "cbrt".to_owned()
+        ]))
+    );
+    assert_eq!(
+        CodegenError::Unsupported(vec!["!".to_owned(), "sum".to_owned()])
+            .to_string(),
+        "Codegen error: no equivalent in the target language for `!`, `sum`"
+    );
+    // everything has an equivalent in Rust
+    assert!(xprs.to_rust("f", &["x"]).is_ok());
+}
+
+#[test]
+fn test_numpy() {
+    let parser = Parser::new_with_ctx(
+        Context::default()
+            .with_fn(xprs_fn!("total", |args| args.iter().sum()))
+            .unwrap()
+            .with_namespace("math", context! { twice = double/1 }),
+    );
+    let test_cases = [
+        (
+            "-x! + factorial(2y)",
+            "((-np.where((x >= 0.0) & (x == np.trunc(x)), scipy.special.factorial(x), np.nan)) + np.where(((2.0 * y) >= 0.0) & ((2.0 * y) == np.trunc((2.0 * y))), scipy.special.factorial((2.0 * y)), np.nan))",
+        ),
+        (
+            "x // 2 % 3 ^ y",
+            "np.fmod(np.trunc(x / 2.0), np.power(3.0, y))",
+        ),
+        (
+            "ln(x) - log(x) * logn(x, 2)",
+            "(np.log(x) - (np.log10(x) * (np.log(x) / np.log(2.0))))",
+        ),
+        (
+            "asinh(x) + atan2(x, y) * gamma(y)",
+            "(np.arcsinh(x) + (np.arctan2(x, y) * scipy.special.gamma(y)))",
+        ),
+        (
+            "round(x) + abs(y)",
+            "(np.copysign(np.floor(np.abs(x) + 0.5), x) + np.abs(y))",
+        ),
+        (
+            "round(x, 2)",
+            "np.where(np.isfinite(np.power(10.0, np.trunc(2.0))) & np.isfinite(x * np.power(10.0, np.trunc(2.0))), np.copysign(np.floor(np.abs(x * np.power(10.0, np.trunc(2.0))) + 0.5), x) / np.power(10.0, np.trunc(2.0)), x)",
+        ),
+        (
+            "min(x, y) + sum(x, y) + mean(x, y)",
+            "((np.fmin(np.fmin(np.inf, x), y) + (x + y)) + ((x + y) / 2.0))",
+        ),
+        (
+            "math.twice(x) + total(x, y)",
+            "(math.twice(x) + total(x, y))",
+        ),
+        ("x * inf - nan", "((x * np.inf) - np.nan)"),
+    ];
+    for (expr, code) in test_cases {
+        let xprs = parser.parse(expr).unwrap();
+        assert_eq!(xprs.to_numpy().unwrap(), code, "{expr}");
+    }
+
+    assert_eq!(
+        Xprs::try_from("x & y + rand()").unwrap().to_numpy(),
+        Err(CodegenError::Unsupported(vec![
+            "rand".to_owned(),
+            "&".to_owned()
+        ]))
+    );
+    // calls without arguments, imported since they're folded when parsing
+    let xprs =
+        Xprs::from_sexpr("(+ (sum) (mean))", &Context::default()).unwrap();
+    assert_eq!(xprs.to_numpy().unwrap(), "(0.0 + np.nan)");
+    assert_eq!(
+        Xprs::try_from("np + lambda").unwrap().to_numpy(),
+        Err(CodegenError::InvalidIdentifier("lambda".to_owned()))
+    );
+}
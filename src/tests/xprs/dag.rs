@@ -0,0 +1,70 @@
+/* Built-in imports */
+extern crate alloc;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicUsize, Ordering};
+/* Crate imports */
+use super::super::macros::assert_f64_eq;
+use crate::{xprs_fn, Context, EvalError, Parser};
+
+/// Input, number of nodes of the DAG
+const SHARED: [(&str, usize); 6] = [
+    ("x + x", 2),
+    ("x + y + (y + x)", 4),
+    // non commutative
+    ("x - y + (y - x)", 5),
+    ("sin(x + 2y) * cos(x + 2y) + sin(2y + x)", 9),
+    ("-x * -x", 3),
+    ("max(x, y) + max(y, x)", 5),
+];
+
+#[test]
+fn test_dag_sharing() {
+    let parser = Parser::default();
+    let vars = [("x", 3.0), ("y", 2.0)].into();
+
+    for (input, nb_nodes) in SHARED {
+        let xprs = parser.parse(input).unwrap();
+        let dag = xprs.to_dag();
+        assert_eq!(dag.nb_nodes(), nb_nodes, "{input}");
+        assert_eq!(dag.vars(), &xprs.vars, "{input}");
+        let expected = xprs.eval(&vars).unwrap();
+        let result = dag.eval(&vars).unwrap();
+        assert_f64_eq!(
+            result,
+            expected,
+            "{input}\nExpected: {expected}, got: {result}"
+        );
+    }
+}
+
+#[test]
+fn test_dag_impure_functions() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    let counted = move |num: f64| {
+        counter.fetch_add(1, Ordering::Relaxed);
+        num
+    };
+    let ctx = Context::default()
+        .with_fn(xprs_fn!("counted", dyn counted, 1).impure())
+        .unwrap();
+    let parser = Parser::new_with_ctx(ctx);
+
+    let dag = parser.parse("counted(x) + counted(x)").unwrap().to_dag();
+    // `x` is still shared
+    assert_eq!(dag.nb_nodes(), 4);
+    assert_f64_eq!(dag.eval(&[("x", 1.0)].into()).unwrap(), 2.0);
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_dag_missing_variable() {
+    let parser = Parser::default();
+    let dag = parser.parse("x + y").unwrap().to_dag();
+
+    assert_eq!(
+        dag.eval(&[("x", 1.0)].into()),
+        Err(EvalError::MissingVariable("y".to_owned()))
+    );
+    assert!(dag.eval_no_vars().is_err());
+}
@@ -0,0 +1,66 @@
+/* Crate imports */
+use crate::xprs::Xprs;
+
+#[test]
+fn test_dot() {
+    let xprs = Xprs::try_from("-max(x, 1.5)!").unwrap();
+    assert_eq!(
+        xprs.to_dot(),
+        r#"digraph xprs {
+    ordering=out;
+    n0 [label="-", shape=circle];
+    n0 -> n1;
+    n1 [label="!", shape=circle];
+    n1 -> n2;
+    n2 [label="max", shape=box];
+    n2 -> n3;
+    n3 [label="x", shape=ellipse];
+    n2 -> n4;
+    n4 [label="1.5", shape=plaintext];
+}
+"#
+    );
+}
+
+#[test]
+fn test_dot_diff() {
+    let xprs = Xprs::try_from("(x * y + z) * 2").unwrap();
+    let simplified = xprs.clone().simplify_for(("y", 0.0));
+    assert_eq!(simplified.to_string(), "(z * 2)");
+
+    // the `+` became `z`, the rest is kept
+    assert_eq!(
+        xprs.to_dot_diff(&simplified),
+        r##"digraph xprs {
+    ordering=out;
+    n0 [label="*", shape=circle];
+    n0 -> n1;
+    n1 [label="+", shape=circle, style=filled, fillcolor="#ffd27f"];
+    n1 -> n2;
+    n2 [label="*", shape=circle, style=filled, fillcolor="#ffd27f"];
+    n2 -> n3;
+    n3 [label="x", shape=ellipse, style=filled, fillcolor="#ffd27f"];
+    n2 -> n4;
+    n4 [label="y", shape=ellipse, style=filled, fillcolor="#ffd27f"];
+    n1 -> n5;
+    n5 [label="z", shape=ellipse, style=filled, fillcolor="#ffd27f"];
+    n0 -> n6;
+    n6 [label="2", shape=plaintext];
+}
+"##
+    );
+    assert_eq!(
+        simplified.to_dot_diff(&xprs),
+        r##"digraph xprs {
+    ordering=out;
+    n0 [label="*", shape=circle];
+    n0 -> n1;
+    n1 [label="z", shape=ellipse, style=filled, fillcolor="#ffd27f"];
+    n0 -> n2;
+    n2 [label="2", shape=plaintext];
+}
+"##
+    );
+    // identical trees aren't highlighted
+    assert_eq!(xprs.to_dot_diff(&xprs), xprs.to_dot());
+}
@@ -2,7 +2,7 @@
 use std::collections::HashMap;
 /* Crate imports */
 use super::super::macros::assert_f64_eq;
-use crate::Parser;
+use crate::{xprs_fn, Context, EvalError, Parser};
 
 type InputVarsResult = (&'static str, &'static [(&'static str, f64)], f64);
 /// 2(3)
@@ -65,3 +65,292 @@ fn test_invalid_eval() {
         "Should have failed because `y` is not provided"
     );
 }
+
+type InputVarsError =
+    (&'static str, &'static [(&'static str, f64)], &'static str);
+/// Expected errors are checked against their `Display` implementation.
+const INVALID_STRICT: [InputVarsError; 7] = [
+    (
+        "sqrt(x)",
+        &[("x", -1.0)],
+        "Evaluation error: domain error in `sqrt(x)`",
+    ),
+    (
+        "ln(x) + 1",
+        &[("x", 0.0)],
+        "Evaluation error: domain error in `ln(x)`",
+    ),
+    (
+        "1 / x",
+        &[("x", 0.0)],
+        "Evaluation error: division by zero in `(1 / x)`",
+    ),
+    (
+        "0 / 0",
+        &[],
+        "Evaluation error: division by zero in `(0 / 0)`",
+    ),
+    (
+        "x % 0",
+        &[("x", 1.0)],
+        "Evaluation error: division by zero in `(x % 0)`",
+    ),
+    ("171!", &[], "Evaluation error: overflow in `(!171)`"),
+    (
+        "x!",
+        &[("x", -1.0)],
+        "Evaluation error: domain error in `(!x)`",
+    ),
+];
+
+#[test]
+fn test_strict_eval() {
+    let parser = Parser::default();
+
+    for (input, vars, expected) in VALID {
+        let var_map: HashMap<&str, f64> = vars.iter().copied().collect();
+        let result = parser.parse(input).unwrap().eval_strict(&var_map);
+        assert_f64_eq!(result.unwrap(), expected, "{input}");
+    }
+
+    for (input, vars, expected) in INVALID_STRICT {
+        let var_map: HashMap<&str, f64> = vars.iter().copied().collect();
+        let xprs = parser.parse(input).unwrap();
+        // the non-strict evaluation still goes through
+        assert!(!xprs.eval(&var_map).unwrap().is_finite(), "{input}");
+        let err = xprs.eval_strict(&var_map).unwrap_err();
+        assert_eq!(err.to_string(), expected, "{input}");
+    }
+
+    let Err(EvalError::Overflow(node)) = parser
+        .parse("2 + x ^ 2")
+        .unwrap()
+        .eval_strict(&[("x", 1e300)].into())
+    else {
+        panic!("`x ^ 2` should overflow");
+    };
+    assert_eq!(node.expr, "(x ^ 2)");
+    assert_eq!(node.name, "^");
+    assert_eq!(node.operands, [1e300, 2.0]);
+}
+
+#[test]
+fn test_zero_arg_calls() {
+    let ctx = Context::default()
+        .with_fn(xprs_fn!("answer", || 42.0, 0))
+        .unwrap();
+    let parser = Parser::new_with_ctx(ctx);
+
+    let answer = parser.parse("answer() + answer( )").unwrap();
+    assert_f64_eq!(answer.eval_no_vars().unwrap(), 84.0);
+
+    // variadics accept an empty list
+    let sum = parser.parse("sum()").unwrap().eval_no_vars().unwrap();
+    assert_f64_eq!(sum, 0.0);
+    let min = parser.parse("min()").unwrap().eval_no_vars().unwrap();
+    assert_eq!(min, f64::INFINITY);
+    let max = parser.parse("max()").unwrap().eval_no_vars().unwrap();
+    assert_eq!(max, f64::NEG_INFINITY);
+
+    // `rand` isn't folded at parse time
+    let rand = parser.parse("rand()").unwrap();
+    assert_eq!(rand.to_string(), "rand()");
+    let value = rand.eval_no_vars().unwrap();
+    assert!((0.0..1.0).contains(&value), "{value}");
+
+    let too_few = parser.parse("sin()").unwrap_err();
+    assert!(
+        too_few.to_string().contains("expected 1 got 0"),
+        "{too_few}"
+    );
+    let too_many = parser.parse("answer(1)").unwrap_err();
+    assert!(
+        too_many.to_string().contains("expected 0 got 1"),
+        "{too_many}"
+    );
+    assert!(parser.parse("sum(,)").is_err());
+}
+
+#[test]
+fn test_arity_bounds() {
+    let ctx = Context::default()
+        .with_fn(xprs_fn!("norm", |args: &[f64]| args.iter().sum(), 1..=3))
+        .unwrap()
+        .with_fn(xprs_fn!("sum2", |args: &[f64]| args.iter().sum(), 2..))
+        .unwrap()
+        .with_fn(
+            xprs_fn!("lerp", |a, b, t| a + (b - a) * t, 3)
+                .with_defaults(&[0.0, 1.0, 0.5]),
+        )
+        .unwrap();
+    let parser = Parser::new_with_ctx(ctx);
+
+    let valid = [
+        ("norm(1)", 1.0),
+        ("norm(1, 2, 3)", 6.0),
+        ("sum2(1, 2)", 3.0),
+        ("sum2(1, 2, 3, 4)", 10.0),
+        ("lerp()", 0.5),
+        ("lerp(2)", 1.5),
+        ("lerp(2, 4)", 3.0),
+        ("lerp(2, 4, 0)", 2.0),
+        ("round(2.5)", 3.0),
+        ("round(3.14159, 2)", 3.14),
+        ("round(1234, -2)", 1200.0),
+        ("round(1.5, 400)", 1.5),
+        ("round(1e300, 10)", 1e300),
+        ("logn(8, 2)", 3.0),
+        ("logn(e ^ 3)", 3.0),
+    ];
+    for (input, expected) in valid {
+        let result = parser.parse(input).unwrap().eval_no_vars().unwrap();
+        assert_f64_eq!(result, expected, "{input}");
+    }
+
+    let invalid = [
+        (
+            "norm()",
+            "Too few arguments for function call, expected 1 to 3 got 0",
+        ),
+        (
+            "norm(1, 2, 3, 4)",
+            "Too many arguments for function call, expected 1 to 3 got 4",
+        ),
+        (
+            "sum2(1)",
+            "Too few arguments for function call, expected at least 2 got 1",
+        ),
+        (
+            "lerp(1, 2, 3, 4)",
+            "Too many arguments for function call, expected 0 to 3 got 4",
+        ),
+    ];
+    for (input, expected) in invalid {
+        let err = parser.parse(input).unwrap_err();
+        assert!(err.to_string().contains(expected), "{input}: {err}");
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "the minimum number of arguments is greater than the maximum"
+)]
+fn test_invalid_arity() {
+    let _func = xprs_fn!("norm", |args: &[f64]| args.iter().sum(), 1..=3)
+        .with_arity(3, Some(1));
+}
+
+#[test]
+#[should_panic(expected = "defaults can't be set on a variadic function")]
+fn test_variadic_defaults() {
+    let _func = xprs_fn!("total", |args: &[f64]| args.iter().sum())
+        .with_defaults(&[0.0]);
+}
+
+#[test]
+#[should_panic(expected = "the defaults raise the minimum number of arguments")]
+fn test_defaults_raising_arity() {
+    let _func =
+        xprs_fn!("clamp", |args: &[f64]| args[0], 1..=3).with_defaults(&[0.0]);
+}
+
+#[test]
+fn test_named_arguments() {
+    let ctx = Context::default()
+        .with_fn(
+            xprs_fn!("lerp", |a, b, t| a + (b - a) * t, 3)
+                .with_params(&["start", "end", "t"])
+                .with_defaults(&[0.5]),
+        )
+        .unwrap();
+    let parser = Parser::new_with_ctx(ctx);
+
+    let valid = [
+        ("lerp(2, 4, 0.25)", 2.5),
+        ("lerp(2, 4, t = 0.25)", 2.5),
+        ("lerp(t = 0.25, end = 4, start = 2)", 2.5),
+        ("lerp(2, end = 4)", 3.0),
+        ("lerp(end=4, start=2)", 3.0),
+    ];
+    for (input, expected) in valid {
+        let result = parser.parse(input).unwrap().eval_no_vars().unwrap();
+        assert_f64_eq!(result, expected, "{input}");
+    }
+
+    let invalid = [
+        ("lerp(2, 4, rate = 0.25)", "Unknown argument name: `rate`"),
+        ("lerp(2, 4, start = 1)", "Duplicate argument: `start`"),
+        ("lerp(t = 1, t = 2, 3)", "Duplicate argument: `t`"),
+        (
+            "lerp(start = 2, 4)",
+            "Positional argument after named arguments",
+        ),
+        (
+            "lerp(end = 4)",
+            "Too few arguments for function call, expected 2 to 3 got 1",
+        ),
+        ("sin(x = 1)", "Unknown argument name: `x`"),
+    ];
+    for (input, expected) in invalid {
+        let err = parser.parse(input).unwrap_err();
+        assert!(err.to_string().contains(expected), "{input}: {err}");
+    }
+}
+
+#[test]
+fn test_named_arguments_help() {
+    use miette::Diagnostic as _;
+
+    let hof = Parser::default().parse("x * y + z").unwrap();
+    let hof = hof.bind3("x", "y", "z").unwrap();
+    let ctx = Context::default()
+        .with_fn(xprs_fn!("hof", dyn hof, 3).with_params(&["x", "y", "z"]))
+        .unwrap();
+    let parser = Parser::new_with_ctx(ctx);
+
+    let result = parser.parse("hof(z = 1, y = 2, x = 3)").unwrap();
+    assert_f64_eq!(result.eval_no_vars().unwrap(), 7.0);
+
+    let help =
+        |input| parser.parse(input).unwrap_err().help().unwrap().to_string();
+    assert_eq!(
+        help("hof(y = 2)"),
+        "Try adding the following arguments: `x`, `z`."
+    );
+    assert_eq!(
+        help("hof(1, 2, 3, 4)"),
+        "Try removing 1 argument. The parameters are: `x`, `y`, `z`."
+    );
+    assert_eq!(
+        help("hof(1, 2, w = 3)"),
+        "Try replacing it with one of the following: `x`, `y`, `z`."
+    );
+}
+
+#[test]
+fn test_owned_params_and_domain() {
+    // e.g. read from a config file
+    let config = String::from("start end");
+    let lerp = xprs_fn!("lerp", |a, b| (a + b) / 2.0, 2)
+        .with_owned_params(config.split(' ').map(str::to_owned))
+        .with_owned_domain(format!("{config} are finite"));
+    assert_eq!(lerp.domain.get(), Some("start end are finite"));
+    assert_eq!(lerp.params.iter().collect::<Vec<_>>(), ["start", "end"]);
+
+    let parser =
+        Parser::new_with_ctx(Context::default().with_fn(lerp).unwrap());
+    let result = parser.parse("lerp(end = 4, start = 2)").unwrap();
+    assert_f64_eq!(result.eval_no_vars().unwrap(), 3.0);
+
+    let sign = xprs_fn!("sign", f64::signum, 1).with_domain("any number");
+    assert_eq!(sign.domain.get(), Some("any number"));
+    assert!(sign.params.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "owned parameters can't be replaced by static ones")]
+fn test_owned_params_replaced() {
+    let _func = xprs_fn!("twice", |x| 2.0 * x, 1)
+        .with_owned_params(["x"])
+        .with_params(&["y"]);
+}
@@ -0,0 +1,195 @@
+/* Crate imports */
+use crate::{context, xprs::Xprs, Context, ImportError, Parser};
+
+fn double(x: f64) -> f64 {
+    x * 2.0
+}
+
+const TEST_CASES: [&str; 11] = [
+    "2",
+    "x",
+    "2 * x + sqrt(y)",
+    "-(a ^ b) % 3 + x! - x",
+    "max(x, y, 1) << 2 >> 1 & 7 | 1 xor 2",
+    "atan2(y, x) // 1 + 1 / x",
+    "double(x) + math.twice(x * x)",
+    "-inf + nan",
+    // names that `f64` would parse
+    "NaN + x",
+    "infinity * Inf - inf",
+    // integers beyond 2^53 are kept exact
+    "0xFFFF_FFFF_FFFF_FFFF & x",
+];
+
+fn parser() -> Parser<'static> {
+    Parser::new_with_ctx(
+        context! { double = double/1 }
+            .with_namespace("math", context! { twice = double/1 }),
+    )
+}
+
+#[test]
+fn test_sexpr_round_trip() {
+    let parser = parser();
+    for expr in TEST_CASES {
+        let xprs = parser.parse(expr).unwrap();
+        let sexpr = xprs.to_sexpr();
+        let imported = Xprs::from_sexpr(&sexpr, parser.ctx()).unwrap();
+        assert_eq!(imported.to_string(), xprs.to_string(), "{expr}");
+        assert_eq!(imported.vars, xprs.vars, "{expr}");
+        assert_eq!(imported.to_sexpr(), sexpr, "{expr}");
+    }
+}
+
+#[test]
+fn test_json_round_trip() {
+    let parser = parser();
+    for expr in TEST_CASES {
+        let xprs = parser.parse(expr).unwrap();
+        let json = xprs.to_json();
+        let imported = Xprs::from_json(&json, parser.ctx()).unwrap();
+        assert_eq!(imported.to_string(), xprs.to_string(), "{expr}");
+        assert_eq!(imported.vars, xprs.vars, "{expr}");
+        assert_eq!(imported.to_json(), json, "{expr}");
+    }
+}
+
+#[test]
+fn test_export() {
+    let xprs = Xprs::try_from("-x! * max(y, 1.5) xor 3").unwrap();
+    assert_eq!(xprs.to_sexpr(), "(xor (* (- (! x)) (max y 1.5)) 3)");
+    assert_eq!(
+        Xprs::try_from("NaN * nan - inf").unwrap().to_sexpr(),
+        "(- (* NaN +NaN) +inf)"
+    );
+    assert_eq!(
+        Xprs::try_from("sin(x) - inf").unwrap().to_json(),
+        r#"{"type":"binary","op":"-","lhs":{"type":"call","name":"sin","args":[{"type":"variable","name":"x"}]},"rhs":{"type":"number","value":Infinity}}"#
+    );
+}
+
+#[test]
+fn test_import() {
+    let ctx = Context::default();
+    // whitespaces are free, unknown JSON fields are ignored
+    let xprs = Xprs::from_sexpr("  ( +\n(sin x)\t( - 2 ))  ", &ctx).unwrap();
+    assert_eq!(xprs.to_string(), "(sin(x) + (-2))");
+    let xprs = Xprs::from_json(
+        r#" { "name" : "max", "args" : [ {"type": "number", "value": -1.5e1}, {"value": NaN, "type": "number", "comment": null} ], "type" : "call" } "#,
+        &ctx,
+    )
+    .unwrap();
+    assert_eq!(xprs.to_string(), "max(-15, NaN)");
+}
+
+#[test]
+fn test_import_errors() {
+    let ctx = context! { double = double/1, y = 2 };
+    let sexpr =
+        |input| Xprs::from_sexpr(input, &ctx).map(|xprs| xprs.to_string());
+    let json =
+        |input| Xprs::from_json(input, &ctx).map(|xprs| xprs.to_string());
+
+    // malformed inputs
+    assert_eq!(
+        sexpr("(+ 1 2"),
+        Err(ImportError::Syntax("unexpected end of input".to_owned(), 6))
+    );
+    assert_eq!(
+        sexpr("(+ 1 2))"),
+        Err(ImportError::Syntax(
+            "unexpected trailing input".to_owned(),
+            7
+        ))
+    );
+    assert_eq!(
+        sexpr("(2 1)"),
+        Err(ImportError::Syntax("`2` can't be applied".to_owned(), 1))
+    );
+    assert_eq!(
+        json(r#"{"type":"number"}"#),
+        Err(ImportError::Syntax("missing `value`".to_owned(), 0))
+    );
+    assert_eq!(
+        json(r#"{"type":"number","value":"2"}"#),
+        Err(ImportError::Syntax("invalid `value`".to_owned(), 0))
+    );
+    assert_eq!(
+        json(r#"{"type":"variable","name":"\u0078"}"#),
+        Err(ImportError::Syntax(
+            "escaped strings are not supported".to_owned(),
+            27
+        ))
+    );
+    assert_eq!(
+        json(r#"{"type":"number","value":2"#),
+        Err(ImportError::Syntax("expected `,`".to_owned(), 26))
+    );
+
+    // operators and functions
+    assert_eq!(
+        sexpr("(% 1)"),
+        Err(ImportError::OperatorArity("%".to_owned(), 1))
+    );
+    assert_eq!(
+        sexpr("(! 1 2)"),
+        Err(ImportError::OperatorArity("!".to_owned(), 2))
+    );
+    assert_eq!(
+        sexpr("(? 1 2)"),
+        Err(ImportError::UnknownOperator("?".to_owned()))
+    );
+    assert_eq!(
+        sexpr("(triple 1)"),
+        Err(ImportError::UnknownFunction("triple".to_owned()))
+    );
+    assert_eq!(
+        json(r#"{"type":"call","name":"double","args":[]}"#),
+        Err(ImportError::ArityMismatch("double".to_owned(), 0))
+    );
+
+    // variables
+    assert_eq!(
+        sexpr("(+ x y)"),
+        Err(ImportError::NotAVariable("y".to_owned()))
+    );
+    assert_eq!(sexpr("pi"), Err(ImportError::NotAVariable("pi".to_owned())));
+    assert_eq!(
+        sexpr("inf"),
+        Err(ImportError::NotAVariable("inf".to_owned()))
+    );
+    assert_eq!(sexpr("1x"), Err(ImportError::InvalidName("1x".to_owned())));
+    let ctx = context! { expect [x] };
+    assert_eq!(
+        Xprs::from_sexpr("(* x z)", &ctx),
+        Err(ImportError::UndeclaredVariable("z".to_owned()))
+    );
+}
+
+#[test]
+fn test_import_depth() {
+    let ctx = Context::default();
+    let negations =
+        |depth| format!("{}x{}", "(- ".repeat(depth), ")".repeat(depth));
+    let calls =
+        |depth| format!("{}x{}", "(abs ".repeat(depth), ")".repeat(depth));
+
+    assert!(Xprs::from_sexpr(&negations(255), &ctx).is_ok());
+    assert_eq!(
+        Xprs::from_sexpr(&negations(256), &ctx),
+        Err(ImportError::TooDeep)
+    );
+    assert_eq!(
+        Xprs::from_sexpr(&negations(2_000_000), &ctx),
+        Err(ImportError::TooDeep)
+    );
+
+    // the arguments of a call are nested in an array, the JSON AST is twice as deep
+    let json = Xprs::from_sexpr(&calls(255), &ctx).unwrap().to_json();
+    assert!(Xprs::from_json(&json, &ctx).is_ok());
+    let json = Xprs::from_sexpr(&negations(255), &ctx).unwrap().to_json();
+    let deeper = format!(r#"{{"type":"unary","op":"-","operand":{json}}}"#);
+    assert_eq!(Xprs::from_json(&deeper, &ctx), Err(ImportError::TooDeep));
+    let arrays = format!("{}{}", "[".repeat(2_000_000), "]".repeat(2_000_000));
+    assert_eq!(Xprs::from_json(&arrays, &ctx), Err(ImportError::TooDeep));
+}
@@ -7,7 +7,7 @@ fn test_higher_order_functions() {
     let xprs_hof = Xprs::try_from("2x + y").unwrap();
     let fn_hof = xprs_hof.bind2("x", "y").unwrap();
     let hof = xprs_fn!("hof", dyn fn_hof, 2);
-    let ctx = Context::default().with_fn(hof);
+    let ctx = Context::default().with_fn(hof).unwrap();
     let parser = Parser::new_with_ctx(ctx);
 
     let bare_use = parser.parse("hof(2, 3)").unwrap();
@@ -0,0 +1,181 @@
+/* Built-in imports */
+use std::collections::HashMap;
+/* Crate imports */
+use super::super::macros::assert_f64_eq;
+use crate::{EvalError, Parser};
+
+const VALID_LITERALS: [(&str, f64); 5] = [
+    ("0xFF", 255.0),
+    ("0xdead_BEEF", 3_735_928_559.0),
+    ("0b1010", 10.0),
+    ("0o17", 15.0),
+    ("0b1_0000_0000", 256.0),
+];
+
+const INVALID_LITERALS: [&str; 2] = [
+    // don't fit in a u64
+    "0x1_0000_0000_0000_0000",
+    "0b1_0000000000000000000000000000000000000000000000000000000000000000",
+];
+
+type InputVarsResult = (&'static str, &'static [(&'static str, i64)], i64);
+const VALID_INT: [InputVarsResult; 14] = [
+    ("0xF0 | 0x0F", &[], 0xFF),
+    ("0xF0 & 0x3C", &[], 0x30),
+    ("0xF0 xor 0xFF", &[], 0x0F),
+    ("1 << 4 + 1", &[], 32),
+    ("reg >> 4 & 0xF", &[("reg", 0xAB)], 0xA),
+    ("7 // 2", &[], 3),
+    ("-7 // 2", &[], -3),
+    ("x / 4", &[("x", 12)], 3),
+    ("2 ^ 62", &[], 4_611_686_018_427_387_904),
+    ("abs(x) + 5!", &[("x", -3)], 123),
+    // beyond 2^53, where floats lose precision
+    ("9007199254740993", &[], 9_007_199_254_740_993),
+    ("2 ^ 53 + 1", &[], 9_007_199_254_740_993),
+    ("3 ^ 39 - 3 ^ 39 // 2", &[], 2_026_277_576_509_488_134),
+    ("(-1) ^ (x - 1)", &[("x", i64::MIN + 2)], -1),
+];
+
+type InputVarsError =
+    (&'static str, &'static [(&'static str, i64)], &'static str);
+/// Expected errors are checked against their `Display` implementation.
+const INVALID_INT: [InputVarsError; 10] = [
+    (
+        "x // 0",
+        &[("x", 1)],
+        "Evaluation error: division by zero in `(x // 0)`",
+    ),
+    (
+        "x % 0",
+        &[("x", 1)],
+        "Evaluation error: division by zero in `(x % 0)`",
+    ),
+    (
+        "x * 2",
+        &[("x", i64::MAX)],
+        "Evaluation error: overflow in `(x * 2)`",
+    ),
+    (
+        "-x",
+        &[("x", i64::MIN)],
+        "Evaluation error: overflow in `(-x)`",
+    ),
+    (
+        "x << 1",
+        &[("x", i64::MAX)],
+        "Evaluation error: overflow in `(x << 1)`",
+    ),
+    (
+        "x / 2",
+        &[("x", 7)],
+        "Evaluation error: `3.5` is not an integer",
+    ),
+    (
+        "sqrt(x)",
+        &[("x", 2)],
+        "Evaluation error: `1.4142135623730951` is not an integer",
+    ),
+    (
+        "abs(x)",
+        &[("x", 9_007_199_254_740_993)],
+        "Evaluation error: precision loss in `abs(x)`",
+    ),
+    (
+        "0xFFFF_FFFF_FFFF_FFFF & x",
+        &[("x", 1)],
+        "Evaluation error: overflow in `18446744073709551615`",
+    ),
+    (
+        "x ^ y",
+        &[("x", 0), ("y", -1)],
+        "Evaluation error: division by zero in `(x ^ y)`",
+    ),
+];
+
+#[test]
+fn test_radix_literals() {
+    let parser = Parser::default();
+    for (input, expected) in VALID_LITERALS {
+        let result = parser.parse(input).unwrap().eval_no_vars().unwrap();
+        assert_f64_eq!(
+            result,
+            expected,
+            "{input}\nExpected: {expected}, got: {result}"
+        );
+    }
+
+    // not a prefix without a valid digit, `0 * x`
+    assert_eq!(parser.parse("0x"), parser.parse("0 * x"));
+    // the literal stops at the first digit outside of its radix
+    assert_eq!(parser.parse("0b1e"), parser.parse("0b1 * e"));
+    assert_eq!(parser.parse("0o7f"), parser.parse("0o7 * f"));
+    assert_eq!(parser.parse("0b1_1x"), parser.parse("3x"));
+    assert!(parser.parse("0b102").is_err());
+    assert!(parser.parse("0o78").is_err());
+
+    for input in INVALID_LITERALS {
+        let err = parser.parse(input).unwrap_err();
+        assert!(
+            err.to_string().starts_with("Integer literal too large"),
+            "Should have failed for `{input}`, got: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn test_float_bitwise() {
+    let parser = Parser::default();
+    let xprs = parser.parse("(x & 0xF) + (x // 16)").unwrap();
+    assert_f64_eq!(xprs.eval(&[("x", 0xAB.into())].into()).unwrap(), 21.0);
+    // bitwise operators need integer operands
+    assert!(xprs.eval(&[("x", 1.5)].into()).unwrap().is_nan());
+}
+
+#[test]
+fn test_valid_eval_int() {
+    let parser = Parser::default();
+    for (input, vars, expected) in VALID_INT {
+        let var_map: HashMap<&str, i64> = vars.iter().copied().collect();
+        let result = parser.parse(input).unwrap().eval_int(&var_map);
+        assert_eq!(result, Ok(expected), "{input}");
+    }
+
+    let mask = parser.parse("0xFFFF_FFFF_FFFF_FFFF xor x").unwrap();
+    assert_eq!(
+        mask.eval_int::<u64>(&[("x", 0xFF)].into()),
+        Ok(0xFFFF_FFFF_FFFF_FF00)
+    );
+    // floats round the integers beyond 2^53
+    assert_f64_eq!(
+        parser
+            .parse("9007199254740993")
+            .unwrap()
+            .eval_no_vars()
+            .unwrap(),
+        9_007_199_254_740_992.0
+    );
+
+    let unsigned = parser.parse("0xFFFF_0000_0000_0000 | x").unwrap();
+    assert_eq!(
+        unsigned.eval_int::<u64>(&[("x", 0xFF)].into()),
+        Ok(0xFFFF_0000_0000_00FF)
+    );
+    assert!(matches!(
+        unsigned.eval_int::<i64>(&[("x", 0xFF)].into()),
+        Err(EvalError::Overflow(_))
+    ));
+}
+
+#[test]
+fn test_invalid_eval_int() {
+    let parser = Parser::default();
+    for (input, vars, expected) in INVALID_INT {
+        let var_map: HashMap<&str, i64> = vars.iter().copied().collect();
+        let err = parser.parse(input).unwrap().eval_int(&var_map).unwrap_err();
+        assert_eq!(err.to_string(), expected, "{input}");
+    }
+
+    let missing = parser.parse("x + 1").unwrap().eval_int_no_vars::<u64>();
+    assert_eq!(missing, Err(EvalError::MissingVariable("x".to_owned())));
+}
@@ -1,4 +1,12 @@
 /* Modules */
+mod binary;
+mod codegen;
+mod dag;
+mod dot;
 mod eval;
+mod formats;
 mod hof;
+mod integer;
+mod resolver;
 mod simplify;
+mod trace;
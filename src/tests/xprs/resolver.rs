@@ -0,0 +1,66 @@
+/* Built-in imports */
+extern crate alloc;
+use alloc::collections::BTreeMap;
+use core::cell::RefCell;
+use std::collections::HashMap;
+/* Crate imports */
+use crate::{EvalError, Parser, VariableResolver};
+
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+impl VariableResolver for Point {
+    fn resolve(&self, name: &str) -> Option<f64> {
+        match name {
+            "x" => Some(self.x),
+            "y" => Some(self.y),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_resolvers() {
+    let xprs = Parser::default().parse("x * 2 + y").unwrap();
+
+    let hash_map: HashMap<String, f64> =
+        [("x".to_owned(), 3.0), ("y".to_owned(), 1.0)].into();
+    let btree_map: BTreeMap<&str, f64> = [("x", 3.0), ("y", 1.0)].into();
+    let pairs = vec![("y", 1.0), ("x", 3.0)];
+
+    assert_eq!(xprs.eval_with(&hash_map), Ok(7.0));
+    assert_eq!(xprs.eval_with(&btree_map), Ok(7.0));
+    assert_eq!(xprs.eval_with(pairs.as_slice()), Ok(7.0));
+    assert_eq!(xprs.eval_with(&[("x", 3.0), ("y", 1.0)]), Ok(7.0));
+    assert_eq!(xprs.eval_with(&Point { x: 3.0, y: 1.0 }), Ok(7.0));
+    assert_eq!(
+        xprs.eval_with(&|name: &str| (name == "x")
+            .then_some(3.0)
+            .or(Some(1.0))),
+        Ok(7.0)
+    );
+    // same result as the `HashMap` evaluation
+    assert_eq!(
+        xprs.eval_with(&[("x", 3.0), ("y", 1.0)]),
+        xprs.eval(&[("x", 3.0), ("y", 1.0)].into())
+    );
+}
+
+#[test]
+fn test_lazy_resolution() {
+    let xprs = Parser::default().parse("(x + y) * z").unwrap();
+    let resolved = RefCell::new(Vec::new());
+    let resolver = |name: &str| {
+        resolved.borrow_mut().push(name.to_owned());
+        (name != "y").then_some(1.0)
+    };
+
+    assert_eq!(
+        xprs.eval_with(&resolver),
+        Err(EvalError::MissingVariable("y".to_owned()))
+    );
+    // `z` is never reached
+    assert_eq!(*resolved.borrow(), ["x", "y"]);
+}
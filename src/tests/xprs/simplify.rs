@@ -1,5 +1,5 @@
 /* Crate imports */
-use crate::Parser;
+use crate::{xprs_fn, Context, Parser};
 
 #[test]
 fn test_simplify() {
@@ -20,3 +20,26 @@ fn test_simplify() {
     xprs.simplify_for_in_place(UNKNOWN_VAR);
     assert_eq!(xprs, parser.parse("21 + 5z").unwrap());
 }
+
+#[test]
+fn test_simplify_impure_functions() {
+    const X_VAR: (&str, f64) = ("x", 2.0);
+    let ctx = Context::default()
+        .with_fn(xprs_fn!("pure", |num| num + 1.0, 1))
+        .unwrap()
+        .with_fn(xprs_fn!("impure", |num| num + 1.0, 1).impure())
+        .unwrap()
+        .with_fn(xprs_fn!("sensor", |num| num + 1.0, 1).non_deterministic())
+        .unwrap();
+    let parser = Parser::new_with_ctx(ctx);
+
+    let xprs = parser.parse("pure(x) + impure(x) + sensor(x)").unwrap();
+    let simplified = xprs.simplify_for(X_VAR);
+    assert_eq!(simplified.to_string(), "((3 + impure(2)) + sensor(2))");
+
+    // `rand` isn't folded, it's called on every evaluation
+    let rand = Parser::default().parse("rand() * 2").unwrap();
+    assert_eq!(rand.to_string(), "(rand() * 2)");
+    let value = rand.eval_no_vars().unwrap();
+    assert!((0.0..2.0).contains(&value), "{value}");
+}
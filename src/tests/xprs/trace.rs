@@ -0,0 +1,59 @@
+/* Crate imports */
+use crate::{EvalError, Parser};
+
+#[test]
+fn test_trace_steps() {
+    let parser = Parser::default();
+    let xprs = parser.parse("max(x, 2) * -y").unwrap();
+    let trace = xprs.eval_trace(&[("x", 3.0), ("y", 2.0)].into()).unwrap();
+
+    let steps: Vec<_> = trace
+        .steps()
+        .iter()
+        .map(|step| (step.expr.as_str(), step.value, step.depth))
+        .collect();
+    assert_eq!(
+        steps,
+        [
+            ("x", 3.0, 2),
+            ("2", 2.0, 2),
+            ("max(x, 2)", 3.0, 1),
+            ("y", 2.0, 2),
+            ("(-y)", -2.0, 1),
+            ("(max(x, 2) * (-y))", -6.0, 0),
+        ]
+    );
+    assert_eq!(trace.steps()[2].children, [0, 1]);
+    assert_eq!(trace.steps()[5].children, [2, 4]);
+    assert_eq!(
+        trace.value(),
+        xprs.eval(&[("x", 3.0), ("y", 2.0)].into()).unwrap()
+    );
+}
+
+#[test]
+fn test_trace_display() {
+    let parser = Parser::default();
+    let xprs = parser.parse("1 + x / (x - 1)").unwrap();
+    let trace = xprs.eval_trace(&[("x", 1.0)].into()).unwrap();
+
+    let expected = "\
+(1 + (x / (x - 1))) = inf
+  1
+  (x / (x - 1)) = inf
+    x = 1
+    (x - 1) = 0
+      x = 1
+      1
+";
+    assert_eq!(trace.to_string(), expected);
+}
+
+#[test]
+fn test_trace_missing_variable() {
+    let parser = Parser::default();
+    let xprs = parser.parse("x + y").unwrap();
+    let trace = xprs.eval_trace(&[("x", 1.0)].into());
+
+    assert_eq!(trace, Err(EvalError::MissingVariable("y".to_owned())));
+}
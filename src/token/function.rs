@@ -1,23 +1,74 @@
 /* Built-in imports */
 extern crate alloc;
-use alloc::sync::Arc;
+use alloc::{borrow::Cow, sync::Arc};
 use core::{cmp::Ordering, fmt, ops::Deref};
 
 /// Represents a mathematical function core infos.
 #[derive(Debug, PartialEq, Clone)]
 #[non_exhaustive]
 pub struct Function {
-    /// The name of the function.
-    pub name: &'static str,
+    /// The number of arguments the function accepts.
+    pub arity: Arity,
+    /// The default values of the last parameters, used when they aren't provided.
+    pub defaults: &'static [f64],
+    /// Whether the function always returns the same result for the same arguments (`true` by default).
+    pub deterministic: bool,
+    /// An optional description of the values the function is defined for (e.g. `x >= 0`).
+    pub domain: Domain,
     /// The function's implementation.
     pub func: FnPointer,
-    /// The optional number of arguments the function accepts.
-    /// If [`None`], the function is variadic.
-    pub nb_args: Option<u8>,
+    /// The name of the function, either static or owned (e.g. loaded from a config file).
+    pub name: Cow<'static, str>,
+    /// The names of the parameters, allowing named arguments (`f(x = 1, rate = 0.3)`).
+    pub params: Params,
+    /// Whether the function is free of side effects (`true` by default).
+    pub pure: bool,
 }
 
 impl Function {
+    /// Marks the function as having side effects (e.g. mutating a counter), returning the function.
+    ///
+    /// Calls to impure functions are never folded at parse time nor shared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::sync::atomic::{AtomicUsize, Ordering};
+    /// use xprs::{xprs_fn, Context, Parser};
+    ///
+    /// static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    /// let next = xprs_fn!("next", |step: f64| {
+    ///     COUNTER.fetch_add(1, Ordering::Relaxed);
+    ///     step
+    /// }, 1)
+    /// .impure();
+    ///
+    /// let parser = Parser::new_with_ctx(Context::default().with_fn(next)?);
+    /// let xprs = parser.parse("next(1) + next(1)")?;
+    /// assert_eq!(COUNTER.load(Ordering::Relaxed), 0);
+    /// assert_eq!(xprs.eval_no_vars(), Ok(2.0));
+    /// assert_eq!(COUNTER.load(Ordering::Relaxed), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn impure(mut self) -> Self {
+        self.pure = false;
+        self
+    }
+
+    /// Whether calls with the same arguments can be evaluated once and reused,
+    /// i.e. the function is both pure and deterministic.
+    /// Used by constant folding and common sub-expression elimination.
+    #[inline]
+    #[must_use]
+    pub const fn is_cacheable(&self) -> bool {
+        self.pure && self.deterministic
+    }
+
     /// Creates a new [`Function`] from static function components.
+    /// The function takes exactly `nb_args` arguments, or any number of arguments if [`None`],
+    /// see [`Function::with_arity`] for other bounds.
     /// Note that the fn pointer must be a function that takes a slice of f64 as argument and returns a f64.
     /// So make sure to wrap your function in a closure if it doesn't match the signature.
     /// For convenience, you can use the [`crate::xprs_fn!`] macro.
@@ -30,35 +81,314 @@ impl Function {
         nb_args: Option<u8>,
     ) -> Self {
         Self {
-            name,
+            name: Cow::Borrowed(name),
             func: FnPointer::Static(func),
-            nb_args,
+            arity: Arity::from_nb_args(nb_args),
+            defaults: &[],
+            params: Params::EMPTY,
+            pure: true,
+            deterministic: true,
+            domain: Domain::NONE,
         }
     }
 
-    /// Creates a new [`Function`] from dynamic function components.
+    /// Creates a new [`Function`] from dynamic function components,
+    /// its name can be owned (e.g. a [`String`]).
+    /// The function takes exactly `nb_args` arguments, or any number of arguments if [`None`],
+    /// see [`Function::with_arity`] for other bounds.
     /// Note that the fn pointer must be a function that takes a slice of f64 as argument and returns a f64.
     /// So make sure to wrap your function in a closure if it doesn't match the signature.
     /// For convenience, you can use the [`crate::xprs_fn!`] macro.
     ///
     /// [`Function`] needs a fn taking a slice because Rust variadics are not available yet.
     #[inline]
-    pub fn new_dyn<T>(name: &'static str, func: T, nb_args: Option<u8>) -> Self
+    pub fn new_dyn<N, T>(name: N, func: T, nb_args: Option<u8>) -> Self
     where
+        N: Into<Cow<'static, str>>,
         T: Fn(&[f64]) -> f64 + Send + Sync + 'static,
     {
         Self {
-            name,
+            name: name.into(),
             func: FnPointer::Dyn(Arc::new(func)),
-            nb_args,
+            arity: Arity::from_nb_args(nb_args),
+            defaults: &[],
+            params: Params::EMPTY,
+            pure: true,
+            deterministic: true,
+            domain: Domain::NONE,
+        }
+    }
+
+    /// Marks the function as non-deterministic (e.g. reading a clock or a random source), returning the function.
+    ///
+    /// Calls to non-deterministic functions are never folded at parse time nor shared.
+    #[inline]
+    #[must_use]
+    pub const fn non_deterministic(mut self) -> Self {
+        self.deterministic = false;
+        self
+    }
+
+    /// Sets the minimum and maximum (if any) number of arguments of the function, returning the function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::{Context, Function, Parser};
+    ///
+    /// // at least 2 arguments
+    /// let gap = Function::new_static("gap", |args| {
+    ///     args.iter().fold(f64::NEG_INFINITY, |acc, &x| acc.max(x))
+    ///         - args.iter().fold(f64::INFINITY, |acc, &x| acc.min(x))
+    /// }, None)
+    /// .with_arity(2, None);
+    ///
+    /// let parser = Parser::new_with_ctx(Context::default().with_fn(gap)?);
+    /// assert_eq!(parser.parse("gap(1, 5, 3)")?.eval_no_vars(), Ok(4.0));
+    /// assert!(parser.parse("gap(1)").is_err());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`, the function couldn't be called
+    /// (at compile time for constant functions).
+    #[inline]
+    #[must_use]
+    pub const fn with_arity(mut self, min: u8, max: Option<u8>) -> Self {
+        if let Some(max_args) = max {
+            assert!(
+                min <= max_args,
+                "the minimum number of arguments is greater than the maximum"
+            );
         }
+        self.arity = Arity { max, min };
+        self
+    }
+
+    /// Sets the default values of the last parameters, returning the function.
+    /// Omitted arguments are replaced by their default value at parse time,
+    /// so the function always receives its maximum number of arguments.
+    ///
+    /// The minimum number of arguments is lowered accordingly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::{xprs_fn, Context, Parser};
+    ///
+    /// // `base` defaults to 2
+    /// let log = xprs_fn!("log", |x: f64, base| x.log(base), 2).with_defaults(&[2.0]);
+    ///
+    /// let parser = Parser::new_with_ctx(Context::default().with_fn(log)?);
+    /// assert_eq!(parser.parse("log(8)")?.eval_no_vars(), Ok(3.0));
+    /// assert_eq!(parser.parse("log(100, 10)")?.eval_no_vars(), Ok(2.0));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function has no maximum number of arguments,
+    /// if there are more defaults than parameters,
+    /// or if the defaults would raise the minimum number of arguments
+    /// (at compile time for constant functions).
+    #[inline]
+    #[must_use]
+    pub const fn with_defaults(mut self, defaults: &'static [f64]) -> Self {
+        assert!(
+            self.arity.max.is_some(),
+            "defaults can't be set on a variadic function"
+        );
+        if let Some(max) = self.arity.max {
+            #[allow(clippy::as_conversions)]
+            let fits = defaults.len() <= max as usize;
+            assert!(fits, "there are more defaults than parameters");
+            #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+            let min = max - defaults.len() as u8;
+            assert!(
+                min <= self.arity.min,
+                "the defaults raise the minimum number of arguments"
+            );
+            self.arity.min = min;
+        }
+        self.defaults = defaults;
+        self
+    }
+
+    /// Sets the description of the values the function is defined for, returning the function.
+    /// See [`Function::with_owned_domain`] for an owned one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an owned description was set before.
+    #[inline]
+    #[must_use]
+    pub const fn with_domain(mut self, domain: &'static str) -> Self {
+        assert!(
+            self.domain.owned.is_none(),
+            "an owned domain can't be replaced by a static one"
+        );
+        self.domain.borrowed = Some(domain);
+        self
+    }
+
+    /// Sets the name of the function, returning the function.
+    /// Useful to give a runtime name to a static function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::{xprs_fn, Context, Parser};
+    ///
+    /// // e.g. read from a config file
+    /// let name = String::from("twice");
+    /// let twice = xprs_fn!("", |x| 2.0 * x, 1).with_name(name);
+    ///
+    /// let parser = Parser::new_with_ctx(Context::default().with_fn(twice)?);
+    /// let xprs = parser.parse("twice(x)")?;
+    /// assert_eq!(xprs.to_string(), "twice(x)");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_name<N: Into<Cow<'static, str>>>(mut self, name: N) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets an owned description of the values the function is defined for, returning the function.
+    /// See [`Function::with_domain`] for a static one.
+    #[inline]
+    #[must_use]
+    pub fn with_owned_domain<D: Into<String>>(mut self, domain: D) -> Self {
+        self.domain = Domain {
+            borrowed: None,
+            owned: Some(domain.into()),
+        };
+        self
+    }
+
+    /// Sets owned names of the parameters (e.g. loaded from a config file), returning the function.
+    /// See [`Function::with_params`] for static ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::{xprs_fn, Context, Parser};
+    ///
+    /// // e.g. read from a config file
+    /// let params = vec![String::from("start"), String::from("end")];
+    /// let gap = xprs_fn!("gap", |start, end| end - start, 2).with_owned_params(params);
+    ///
+    /// let parser = Parser::new_with_ctx(Context::default().with_fn(gap)?);
+    /// assert_eq!(parser.parse("gap(end = 5, start = 1)")?.eval_no_vars(), Ok(4.0));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_owned_params<I>(mut self, params: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.params = Params {
+            borrowed: &[],
+            owned: Some(params.into_iter().map(Into::into).collect()),
+        };
+        self
+    }
+
+    /// Sets the names of the parameters, returning the function.
+    /// Arguments can then be given by name (after the positional ones),
+    /// they are reordered at parse time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::{xprs_fn, Context, Parser};
+    ///
+    /// fn interest(amount: f64, rate: f64, years: f64) -> f64 {
+    ///     amount * (1.0 + rate).powf(years)
+    /// }
+    ///
+    /// let interest = xprs_fn!("interest", interest, 3).with_params(&["amount", "rate", "years"]);
+    ///
+    /// let parser = Parser::new_with_ctx(Context::default().with_fn(interest)?);
+    /// assert_eq!(
+    ///     parser.parse("interest(100, years = 2, rate = 0.5)")?,
+    ///     parser.parse("interest(100, 0.5, 2)")?,
+    /// );
+    /// assert!(parser.parse("interest(100, 0.5, year = 2)").is_err());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// See [`Function::with_owned_params`] for owned names.
+    ///
+    /// # Panics
+    ///
+    /// Panics if owned names were set before.
+    #[inline]
+    #[must_use]
+    pub const fn with_params(
+        mut self,
+        params: &'static [&'static str],
+    ) -> Self {
+        assert!(
+            self.params.owned.is_none(),
+            "owned parameters can't be replaced by static ones"
+        );
+        self.params.borrowed = params;
+        self
     }
 }
 
 impl PartialOrd for Function {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.name.partial_cmp(other.name)
+        self.name.partial_cmp(&other.name)
+    }
+}
+
+/// The number of arguments a [`Function`] accepts.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub struct Arity {
+    /// The maximum number of arguments, if [`None`] the function is variadic.
+    pub max: Option<u8>,
+    /// The minimum number of arguments.
+    pub min: u8,
+}
+
+impl Arity {
+    /// Whether the given number of arguments is accepted.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, nb_args: usize) -> bool {
+        usize::from(self.min) <= nb_args
+            && self.max.map_or(true, |max| nb_args <= usize::from(max))
+    }
+
+    /// Creates the [`Arity`] corresponding to an exact number of arguments,
+    /// or to any number of arguments if [`None`].
+    const fn from_nb_args(nb_args: Option<u8>) -> Self {
+        match nb_args {
+            Some(exact) => Self {
+                min: exact,
+                max: Some(exact),
+            },
+            None => Self { min: 0, max: None },
+        }
+    }
+}
+
+impl fmt::Display for Arity {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.max {
+            Some(max) if max == self.min => write!(f, "{max}"),
+            Some(max) => write!(f, "{} to {max}", self.min),
+            None => write!(f, "at least {}", self.min),
+        }
     }
 }
 
@@ -111,12 +441,90 @@ impl PartialEq for FnPointer {
     }
 }
 
+/// The description of the values a [`Function`] is defined for,
+/// either static or owned (e.g. loaded from a config file).
+///
+/// Unlike a [`Cow`], a static description can be set in constant functions.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Domain {
+    /// The static description, if any.
+    borrowed: Option<&'static str>,
+    /// The owned description, if any, it takes precedence over the static one.
+    owned: Option<String>,
+}
+
+impl Domain {
+    /// No description.
+    const NONE: Self = Self {
+        borrowed: None,
+        owned: None,
+    };
+
+    /// Returns the description, if any.
+    #[inline]
+    #[must_use]
+    pub fn get(&self) -> Option<&str> {
+        self.owned.as_deref().or(self.borrowed)
+    }
+}
+
+/// The names of the parameters of a [`Function`],
+/// either static or owned (e.g. loaded from a config file).
+///
+/// Like a [`Domain`], static names can be set in constant functions.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Params {
+    /// The static names.
+    borrowed: &'static [&'static str],
+    /// The owned names, if any, they take precedence over the static ones.
+    owned: Option<Vec<String>>,
+}
+
+impl Params {
+    /// No named parameter.
+    const EMPTY: Self = Self {
+        borrowed: &[],
+        owned: None,
+    };
+
+    /// Returns the name of the parameter at `idx`, if any.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, idx: usize) -> Option<&str> {
+        self.owned.as_ref().map_or_else(
+            || self.borrowed.get(idx).copied(),
+            |owned| owned.get(idx).map(String::as_str),
+        )
+    }
+
+    /// Whether no parameter is named.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the names of the parameters, in order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        (0..self.len()).filter_map(|idx| self.get(idx))
+    }
+
+    /// The number of named parameters.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.owned.as_ref().map_or(self.borrowed.len(), Vec::len)
+    }
+}
+
 /// Macro for defining functions for xprs' context easily, with optional variadic support.
 /// This macro is provided for convenience, since [`crate::Function`] needs a fn taking a slice of [`f64`] as argument.
 /// The macro will wrap your function in a closure depending on the number of arguments you provide.
 /// Be aware that the provided function gets moved into the closure, so if you want to use it again, you'll have to clone it.
 ///
 /// Don't provide the number of arguments if your function is variadic (takes any number of arguments).
+/// You can also provide a range of arguments (`1..=3`) or a minimum (`2..`), no wrapping is done in that case.
 #[macro_export]
 macro_rules! xprs_fn {
     // variadics
@@ -162,6 +570,41 @@ macro_rules! xprs_fn {
         )
     };
 
+    // bounded args, the function takes the slice of arguments
+    ($name:expr, $function:expr, $min:literal..=$max:literal) => {
+        $crate::Function::new_static($name, $function, None)
+            .with_arity($min, Some($max))
+    };
+    ($name:expr, dyn $function:expr, $min:literal..=$max:literal) => {
+        $crate::Function::new_dyn($name, $function, None)
+            .with_arity($min, Some($max))
+    };
+    ($function:expr, $min:literal..=$max:literal) => {
+        $crate::Function::new_static(stringify!($function), $function, None)
+            .with_arity($min, Some($max))
+    };
+    (dyn $function:expr, $min:literal..=$max:literal) => {
+        $crate::Function::new_dyn(stringify!($function), $function, None)
+            .with_arity($min, Some($max))
+    };
+    // variadics with a minimum number of args
+    ($name:expr, $function:expr, $min:literal..) => {
+        $crate::Function::new_static($name, $function, None)
+            .with_arity($min, None)
+    };
+    ($name:expr, dyn $function:expr, $min:literal..) => {
+        $crate::Function::new_dyn($name, $function, None)
+            .with_arity($min, None)
+    };
+    ($function:expr, $min:literal..) => {
+        $crate::Function::new_static(stringify!($function), $function, None)
+            .with_arity($min, None)
+    };
+    (dyn $function:expr, $min:literal..) => {
+        $crate::Function::new_dyn(stringify!($function), $function, None)
+            .with_arity($min, None)
+    };
+
     //// closure wrapping ////
     (wrap $function:expr, 0) => {
         move |_| $function()
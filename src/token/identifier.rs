@@ -1,7 +1,9 @@
-/* Built-in imports */
-use core::f64;
 /* Crate imports */
-use crate::{context::Symbol, token::Function, utils::built_in_functions};
+use crate::{
+    context::{Context, Symbol},
+    token::Function,
+    utils::built_in_functions,
+};
 
 /// Represents a mathematical identifier, which can be a function, constant, or variable.
 #[derive(Debug, PartialEq, PartialOrd)]
@@ -15,13 +17,16 @@ pub enum Identifier<'a> {
 }
 
 impl<'a> Identifier<'a> {
-    /// Converts a string into an [`Identifier`].
+    /// Converts a string into an [`Identifier`] using the context's constants and built-ins.
     /// Cannot fail because unknown identifiers are treated as variables.
-    pub(crate) fn from_str(value: &'a str) -> Self {
+    pub(crate) fn from_str(value: &'a str, ctx: &Context) -> Self {
+        if let Some(constant) = ctx.get_constant(value) {
+            return constant.into();
+        }
+        if !ctx.built_ins_enabled() {
+            return Identifier::Variable(value);
+        }
         match value {
-            /* Constants */
-            "pi" => f64::consts::PI.into(),
-            "e" => f64::consts::E.into(),
             /* Functions */
             // sin
             "sin" => built_in_functions::SIN.into(),
@@ -62,6 +67,7 @@ impl<'a> Identifier<'a> {
             "fract" => built_in_functions::FRACT.into(),
             "gamma" => built_in_functions::GAMMA.into(),
             "factorial" => built_in_functions::FACTORIAL.into(),
+            "rand" => built_in_functions::RAND.into(),
             /* Variables */
             _ => Identifier::Variable(value),
         }
@@ -6,7 +6,7 @@ mod identifier;
 /// The operator module.
 mod operator;
 /* Exports */
-pub use function::Function;
+pub use function::{Arity, Domain, Function, Params};
 pub use identifier::Identifier;
 pub use operator::Operator;
 
@@ -1,17 +1,36 @@
 /* Built-in imports */
 use core::fmt;
+/* Crate imports */
+use crate::utils::{bitwise, factorial::factorial};
 
 /// Represents a mathematical operator.
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum Operator {
     /// Addition operator.
     Plus,
     /// Subtraction operator.
     Minus,
+    /// Left shift operator.
+    ShiftLeft,
+    /// Right shift operator.
+    ShiftRight,
     /// Multiplication operator.
     Times,
+    /// Bitwise AND operator.
+    BitAnd,
+    /// Bitwise OR operator.
+    BitOr,
+    /// Bitwise XOR operator.
+    BitXor,
     /// Division operator.
     Divide,
+    /// Integer division operator (truncates towards zero).
+    IntDivide,
     /// Exponentiation operator.
     Power,
     /// Modulo operator.
@@ -25,7 +44,8 @@ impl TryFrom<u8> for Operator {
 
     /// Attempts to convert a byte value into an [`Operator`].
     /// Returns an error if the byte value does not correspond to a valid operator.
-    /// Valid operators are: '+', '-', '*', '/', '^', '%'.
+    /// Valid operators are: '!', '+', '-', '*', '/', '^', '%', '&', '|'.
+    /// Multi-byte operators (`//`, `<<`, `>>` and `xor`) are handled by the parser.
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
             b'!' => Ok(Self::Factorial),
@@ -35,6 +55,8 @@ impl TryFrom<u8> for Operator {
             b'/' => Ok(Self::Divide),
             b'^' => Ok(Self::Power),
             b'%' => Ok(Self::Modulo),
+            b'&' => Ok(Self::BitAnd),
+            b'|' => Ok(Self::BitOr),
             _ => Err("Operator not found"),
         }
     }
@@ -48,8 +70,107 @@ impl fmt::Display for Operator {
             Self::Minus => write!(fmt, "-"),
             Self::Times => write!(fmt, "*"),
             Self::Divide => write!(fmt, "/"),
+            Self::IntDivide => write!(fmt, "//"),
             Self::Power => write!(fmt, "^"),
             Self::Modulo => write!(fmt, "%"),
+            Self::BitAnd => write!(fmt, "&"),
+            Self::BitOr => write!(fmt, "|"),
+            Self::BitXor => write!(fmt, "xor"),
+            Self::ShiftLeft => write!(fmt, "<<"),
+            Self::ShiftRight => write!(fmt, ">>"),
         }
     }
 }
+
+impl Operator {
+    /// All the operators.
+    pub(crate) const ALL: [Self; 13] = [
+        Self::Plus,
+        Self::Minus,
+        Self::Times,
+        Self::Divide,
+        Self::IntDivide,
+        Self::Power,
+        Self::Modulo,
+        Self::Factorial,
+        Self::BitAnd,
+        Self::BitOr,
+        Self::BitXor,
+        Self::ShiftLeft,
+        Self::ShiftRight,
+    ];
+
+    /// Applies the operator as a binary operator on its operands.
+    pub(crate) fn eval_binary(self, lhs: f64, rhs: f64) -> f64 {
+        #[allow(clippy::unreachable)]
+        match self {
+            Self::Plus => lhs + rhs,
+            Self::Minus => lhs - rhs,
+            Self::Times => lhs * rhs,
+            Self::Divide => lhs / rhs,
+            Self::IntDivide => (lhs / rhs).trunc(),
+            Self::Power => lhs.powf(rhs),
+            Self::Modulo => lhs % rhs,
+            Self::BitAnd => bitwise::bit_and(lhs, rhs),
+            Self::BitOr => bitwise::bit_or(lhs, rhs),
+            Self::BitXor => bitwise::bit_xor(lhs, rhs),
+            Self::ShiftLeft => bitwise::shift_left(lhs, rhs),
+            Self::ShiftRight => bitwise::shift_right(lhs, rhs),
+            Self::Factorial => unreachable!(),
+        }
+    }
+
+    /// Applies the operator as a unary operator on its operand.
+    pub(crate) fn eval_unary(self, operand: f64) -> f64 {
+        #[allow(clippy::unreachable)]
+        match self {
+            Self::Plus => operand,
+            Self::Minus => -operand,
+            Self::Factorial => factorial(operand),
+            Self::Times
+            | Self::Divide
+            | Self::IntDivide
+            | Self::Power
+            | Self::Modulo
+            | Self::BitAnd
+            | Self::BitOr
+            | Self::BitXor
+            | Self::ShiftLeft
+            | Self::ShiftRight => unreachable!(),
+        }
+    }
+
+    /// Returns the operator displayed as `symbol` (e.g. `//` or `xor`).
+    pub(crate) fn from_symbol(symbol: &str) -> Option<Self> {
+        match *symbol.as_bytes() {
+            [b'/', b'/'] => Some(Self::IntDivide),
+            [b'x', b'o', b'r'] => Some(Self::BitXor),
+            [b'<', b'<'] => Some(Self::ShiftLeft),
+            [b'>', b'>'] => Some(Self::ShiftRight),
+            [byte] => Self::try_from(byte).ok(),
+            _ => None,
+        }
+    }
+
+    /// Whether the operator can be applied as a binary operator.
+    pub(crate) const fn is_binary(self) -> bool {
+        !matches!(self, Self::Factorial)
+    }
+
+    /// Whether the operands of the (binary) operator can be swapped.
+    pub(crate) const fn is_commutative(self) -> bool {
+        matches!(
+            self,
+            Self::Plus
+                | Self::Times
+                | Self::BitAnd
+                | Self::BitOr
+                | Self::BitXor
+        )
+    }
+
+    /// Whether the operator can be applied as a unary operator (`+x`, `-x` and `x!`).
+    pub(crate) const fn is_unary(self) -> bool {
+        matches!(self, Self::Plus | Self::Minus | Self::Factorial)
+    }
+}
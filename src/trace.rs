@@ -0,0 +1,81 @@
+/* Built-in imports */
+use core::fmt;
+
+/// The recorded evaluation of an [`Xprs`](crate::Xprs),
+/// see [`Xprs::eval_trace`](crate::Xprs::eval_trace).
+///
+/// Displaying a [`Trace`] renders it as an indented tree,
+/// one node per line with its value.
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub struct Trace {
+    /// The steps of the evaluation, the root of the expression being the last one.
+    steps: Vec<TraceStep>,
+}
+
+impl Trace {
+    /// Writes a step and its children, depth-first.
+    fn fmt_step(
+        &self,
+        fmt: &mut fmt::Formatter<'_>,
+        idx: usize,
+    ) -> fmt::Result {
+        let Some(step) = self.steps.get(idx) else {
+            return Ok(());
+        };
+        write!(fmt, "{:indent$}{}", "", step.expr, indent = step.depth * 2)?;
+        // numbers are their own value
+        if step.expr != step.value.to_string() {
+            write!(fmt, " = {}", step.value)?;
+        }
+        writeln!(fmt)?;
+        for &child in &step.children {
+            self.fmt_step(fmt, child)?;
+        }
+        Ok(())
+    }
+
+    /// Creates a new [`Trace`] from its steps.
+    pub(crate) const fn new(steps: Vec<TraceStep>) -> Self {
+        Self { steps }
+    }
+
+    /// Returns the steps of the evaluation in evaluation order,
+    /// operands come before their operator and the root is the last step.
+    #[inline]
+    #[must_use]
+    pub fn steps(&self) -> &[TraceStep] {
+        &self.steps
+    }
+
+    /// Returns the result of the evaluation.
+    #[inline]
+    #[must_use]
+    pub fn value(&self) -> f64 {
+        self.steps.last().map_or(f64::NAN, |step| step.value)
+    }
+}
+
+impl fmt::Display for Trace {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.steps
+            .len()
+            .checked_sub(1)
+            .map_or(Ok(()), |root| self.fmt_step(f, root))
+    }
+}
+
+/// A node of the expression and its value, part of a [`Trace`].
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub struct TraceStep {
+    /// The indices of the node's operands (or arguments) steps.
+    pub children: Vec<usize>,
+    /// The depth of the node in the expression, the root being at depth `0`.
+    pub depth: usize,
+    /// The formatted sub-expression.
+    pub expr: String,
+    /// The value of the sub-expression.
+    pub value: f64,
+}
@@ -0,0 +1,56 @@
+/* Crate imports */
+use crate::utils::hidden_macros::trust_me;
+
+/// Converts a float to an [`i64`] if it holds an integer value in range.
+fn to_i64(num: f64) -> Option<i64> {
+    const ZERO: f64 = 0.0;
+    // `MAX` gets rounded up to 2^63, the exclusive upper bound of `i64`
+    #[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+    let range = (i64::MIN as f64)..(i64::MAX as f64);
+
+    // `fract` of NaN and infinities is NaN
+    if num.fract() != ZERO || !range.contains(&num) {
+        return None;
+    }
+
+    Some(trust_me!(num.to_int_unchecked::<i64>()))
+}
+
+/// Applies an integer operation on two floats.
+/// Results in `f64::NAN` if an operand isn't an integer or if the operation fails.
+fn on_integers(lhs: f64, rhs: f64, op: fn(i64, i64) -> Option<i64>) -> f64 {
+    #[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+    to_i64(lhs)
+        .zip(to_i64(rhs))
+        .and_then(|(lhs_int, rhs_int)| op(lhs_int, rhs_int))
+        .map_or(f64::NAN, |res| res as f64)
+}
+
+#[doc(hidden)]
+pub fn bit_and(lhs: f64, rhs: f64) -> f64 {
+    on_integers(lhs, rhs, |lhs_int, rhs_int| Some(lhs_int & rhs_int))
+}
+
+#[doc(hidden)]
+pub fn bit_or(lhs: f64, rhs: f64) -> f64 {
+    on_integers(lhs, rhs, |lhs_int, rhs_int| Some(lhs_int | rhs_int))
+}
+
+#[doc(hidden)]
+pub fn bit_xor(lhs: f64, rhs: f64) -> f64 {
+    on_integers(lhs, rhs, |lhs_int, rhs_int| Some(lhs_int ^ rhs_int))
+}
+
+#[doc(hidden)]
+pub fn shift_left(lhs: f64, rhs: f64) -> f64 {
+    on_integers(lhs, rhs, |lhs_int, rhs_int| {
+        lhs_int.checked_shl(u32::try_from(rhs_int).ok()?)
+    })
+}
+
+#[doc(hidden)]
+pub fn shift_right(lhs: f64, rhs: f64) -> f64 {
+    on_integers(lhs, rhs, |lhs_int, rhs_int| {
+        lhs_int.checked_shr(u32::try_from(rhs_int).ok()?)
+    })
+}
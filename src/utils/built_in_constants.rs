@@ -0,0 +1,30 @@
+/* Built-in imports */
+use core::f64;
+
+/// The golden ratio.
+const PHI: f64 = 1.618_033_988_749_895;
+
+/// The built-in constants, by lowercase name.
+pub const BUILT_IN_CONSTANTS: [(&str, f64); 7] = [
+    ("pi", f64::consts::PI),
+    ("e", f64::consts::E),
+    ("tau", f64::consts::TAU),
+    ("phi", PHI),
+    ("sqrt2", f64::consts::SQRT_2),
+    ("inf", f64::INFINITY),
+    ("nan", f64::NAN),
+];
+
+/// Returns the value of a built-in constant.
+pub fn get(name: &str, case_insensitive: bool) -> Option<f64> {
+    BUILT_IN_CONSTANTS
+        .iter()
+        .find(|constant| {
+            if case_insensitive {
+                constant.0.eq_ignore_ascii_case(name)
+            } else {
+                constant.0 == name
+            }
+        })
+        .map(|constant| constant.1)
+}
@@ -1,5 +1,10 @@
+/* Built-in imports */
+use core::f64::consts::E;
 /* Crate imports */
-use super::factorial::{factorial, gamma};
+use super::{
+    factorial::{factorial, gamma},
+    random::random,
+};
 use crate::{token::Function, xprs_fn};
 
 /// Sine builtin function.
@@ -9,7 +14,8 @@ pub const SIN: Function = xprs_fn!("sin", f64::sin, 1);
 pub const SINH: Function = xprs_fn!("sinh", f64::sinh, 1);
 
 /// Arcsine builtin function.
-pub const ASIN: Function = xprs_fn!("asin", f64::asin, 1);
+pub const ASIN: Function =
+    xprs_fn!("asin", f64::asin, 1).with_domain("-1 <= x <= 1");
 
 /// Inverse hyperbolic sine builtin function.
 pub const ASINH: Function = xprs_fn!("asinh", f64::asinh, 1);
@@ -21,10 +27,12 @@ pub const COS: Function = xprs_fn!("cos", f64::cos, 1);
 pub const COSH: Function = xprs_fn!("cosh", f64::cosh, 1);
 
 /// Arccosine builtin function.
-pub const ACOS: Function = xprs_fn!("acos", f64::acos, 1);
+pub const ACOS: Function =
+    xprs_fn!("acos", f64::acos, 1).with_domain("-1 <= x <= 1");
 
 /// Inverse hyperbolic cosine builtin function.
-pub const ACOSH: Function = xprs_fn!("acosh", f64::acosh, 1);
+pub const ACOSH: Function =
+    xprs_fn!("acosh", f64::acosh, 1).with_domain("x >= 1");
 
 /// Tangent builtin function.
 pub const TAN: Function = xprs_fn!("tan", f64::tan, 1);
@@ -39,19 +47,20 @@ pub const ATAN: Function = xprs_fn!("atan", f64::atan, 1);
 pub const ATAN2: Function = xprs_fn!("atan2", f64::atan2, 2);
 
 /// Inverse hyperbolic tangent builtin function.
-pub const ATANH: Function = xprs_fn!("atanh", f64::atanh, 1);
+pub const ATANH: Function =
+    xprs_fn!("atanh", f64::atanh, 1).with_domain("-1 < x < 1");
 
 /// Natural logarithm builtin function.
-pub const LN: Function = xprs_fn!("ln", f64::ln, 1);
+pub const LN: Function = xprs_fn!("ln", f64::ln, 1).with_domain("x > 0");
 
 /// Base-10 logarithm builtin function.
-pub const LOG: Function = xprs_fn!("log", f64::log10, 1);
+pub const LOG: Function = xprs_fn!("log", f64::log10, 1).with_domain("x > 0");
 
-/// Logarithm function with a specified base.
-pub const LOGN: Function = xprs_fn!("logn", f64::log, 2);
+/// Logarithm function with a specified base, `e` by default.
+pub const LOGN: Function = xprs_fn!("logn", f64::log, 2).with_defaults(&[E]);
 
 /// Square root builtin function.
-pub const SQRT: Function = xprs_fn!("sqrt", f64::sqrt, 1);
+pub const SQRT: Function = xprs_fn!("sqrt", f64::sqrt, 1).with_domain("x >= 0");
 
 /// Cube root builtin function.
 pub const CBRT: Function = xprs_fn!("cbrt", f64::cbrt, 1);
@@ -68,8 +77,9 @@ pub const FLOOR: Function = xprs_fn!("floor", f64::floor, 1);
 /// Ceiling builtin function.
 pub const CEIL: Function = xprs_fn!("ceil", f64::ceil, 1);
 
-/// Round to the nearest integer builtin function.
-pub const ROUND: Function = xprs_fn!("round", f64::round, 1);
+/// Round to the nearest integer (or number of decimal digits) builtin function.
+pub const ROUND: Function =
+    xprs_fn!("round", round, 2).with_defaults(&[0.0_f64]);
 
 /// Truncate decimal part builtin function.
 pub const TRUNC: Function = xprs_fn!("trunc", f64::trunc, 1);
@@ -107,3 +117,17 @@ pub const FACTORIAL: Function = xprs_fn!("factorial", factorial, 1);
 
 /// Gamma builtin function.
 pub const GAMMA: Function = xprs_fn!("gamma", gamma, 1);
+
+/// Random number in `[0, 1)` builtin function.
+pub const RAND: Function = xprs_fn!("rand", random, 0).non_deterministic();
+
+/// Rounds a number to the given number of decimal digits (negative digits round to tens, hundreds...).
+pub fn round(num: f64, digits: f64) -> f64 {
+    let factor = 10.0_f64.powf(digits.trunc());
+    let scaled = num * factor;
+    // the number has no digits beyond the range of `f64`, it's already rounded
+    if !factor.is_finite() || !scaled.is_finite() {
+        return num;
+    }
+    scaled.round() / factor
+}
@@ -1,4 +1,8 @@
 /* Modules */
+/// Module containing the floating point bitwise operations.
+pub mod bitwise;
+/// Module containing the built-in constants.
+pub mod built_in_constants;
 /// Module containing the built-in functions.
 pub mod built_in_functions;
 /// Module containing the factorial logic.
@@ -7,3 +11,7 @@ pub mod factorial;
 pub mod hidden_macros;
 /// Module containing the precedence logic for the operators.
 pub mod precedence;
+/// Module containing the pseudo-random number generator.
+pub mod random;
+/// Module containing the suggestions logic for error messages.
+pub mod suggestions;
@@ -4,12 +4,12 @@ use crate::token::Operator;
 /// Constant representing no precedence.
 pub const NO_PRECEDENCE: usize = 0;
 /// Constant representing unary operator precedence.
-pub const UNOP_PRECEDENCE: usize = 3;
+pub const UNOP_PRECEDENCE: usize = 7;
 
 /// Constant representing the precedence of implicit multiplication.
 /// The actual value depends on the feature configuration.
 #[cfg(feature = "pejmdas")]
-const IMPLICIT_MULTIPLICATION_PRECEDENCE: usize = 3;
+const IMPLICIT_MULTIPLICATION_PRECEDENCE: usize = 7;
 /// Constant representing the precedence of implicit multiplication.
 /// The actual value depends on the feature configuration.
 #[cfg(feature = "pemdas")]
@@ -22,10 +22,18 @@ pub const IMPLICIT_MULTIPLICATION_INFO: (Operator, usize) =
 /// Retrieves the precedence value for a given operator.
 pub const fn get_for_op(op: Operator) -> usize {
     match op {
-        Operator::Plus | Operator::Minus => 1,
-        Operator::Times | Operator::Divide | Operator::Modulo => 2,
-        // uses `4` because `pejmdas` feature uses `3`
-        Operator::Power => 4,
-        Operator::Factorial => 5,
+        // bitwise operators bind looser than arithmetic ones (like in C)
+        Operator::BitOr => 1,
+        Operator::BitXor => 2,
+        Operator::BitAnd => 3,
+        Operator::ShiftLeft | Operator::ShiftRight => 4,
+        Operator::Plus | Operator::Minus => 5,
+        Operator::Times
+        | Operator::Divide
+        | Operator::IntDivide
+        | Operator::Modulo => 6,
+        // uses `8` because `pejmdas` feature uses `7`
+        Operator::Power => 8,
+        Operator::Factorial => 9,
     }
 }
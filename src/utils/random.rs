@@ -0,0 +1,37 @@
+/* Clippy Config */
+#![allow(clippy::std_instead_of_core)]
+/* Built-in imports */
+use core::{
+    cell::Cell,
+    hash::{BuildHasher as _, Hasher as _},
+};
+use std::collections::hash_map::RandomState;
+
+std::thread_local! {
+    /// The state of the thread's random number generator.
+    static STATE: Cell<u64> = Cell::new(seed());
+}
+
+/// Returns a random seed, different for every thread.
+fn seed() -> u64 {
+    // `RandomState` keys are randomly generated, `| 1` avoids the all-zero state
+    RandomState::new().build_hasher().finish() | 1
+}
+
+/// Returns a pseudo-random number in `[0, 1)`.
+/// Uses a xorshift64* generator, which is fast but not cryptographically secure.
+pub fn random() -> f64 {
+    const MULTIPLIER: u64 = 0x2545_F491_4F6C_DD1D;
+    STATE.with(|state| {
+        let mut num = state.get();
+        num ^= num >> 12_u8;
+        num ^= num << 25_u8;
+        num ^= num >> 27_u8;
+        state.set(num);
+        // keeps the 53 high bits, the precision of a `f64`
+        #[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+        let res = (num.wrapping_mul(MULTIPLIER) >> 11_u8) as f64
+            / (1_u64 << 53_u8) as f64;
+        res
+    })
+}
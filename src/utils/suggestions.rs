@@ -0,0 +1,46 @@
+/// Returns the candidates sorted by similarity to the given name.
+/// If some candidates are close enough to be typos, only those are returned.
+pub fn closest<'a, I>(name: &str, candidates: I) -> Vec<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    // a third of the name's characters can be typos
+    #[allow(clippy::integer_division, clippy::integer_division_remainder_used)]
+    let max_distance = (name.len() / 3).max(2);
+    let mut sorted = candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .collect::<Vec<_>>();
+    sorted.sort_unstable();
+    sorted.dedup();
+    if sorted
+        .first()
+        .is_some_and(|&(distance, _)| distance <= max_distance)
+    {
+        sorted.retain(|&(distance, _)| distance <= max_distance);
+    }
+    sorted.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(lhs: &str, rhs: &str) -> usize {
+    let rhs_chars = rhs.chars().collect::<Vec<_>>();
+    // distances between the current prefix of `lhs` and every prefix of `rhs`
+    let mut row = (0..=rhs_chars.len()).collect::<Vec<_>>();
+    for (i, lhs_ch) in lhs.chars().enumerate() {
+        let mut diagonal = i;
+        let mut left = i + 1;
+        for (above, &rhs_ch) in row.iter_mut().skip(1).zip(&rhs_chars) {
+            let distance = (diagonal + usize::from(lhs_ch != rhs_ch))
+                .min(left + 1)
+                .min(*above + 1);
+            diagonal = *above;
+            *above = distance;
+            left = distance;
+        }
+        if let Some(first) = row.first_mut() {
+            *first = i + 1;
+        }
+    }
+    row.last().copied().unwrap_or_default()
+}
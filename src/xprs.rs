@@ -5,13 +5,21 @@ use core::{fmt, ptr};
 use std::collections::{HashMap, HashSet};
 /* Crate imports */
 use crate::{
+    binary::{self, DecodeError},
+    codegen::{
+        self, c99::C99, glsl::Glsl, numpy::NumPy, rust::Rust, CodegenError,
+    },
+    context::Context,
+    dag::XprsDag,
+    dot::Dot,
     element::Element,
     element::Simplify,
+    formats::{json, sexpr, ImportError},
+    integer::{IntXprsImpl, Integer},
+    resolver::VariableResolver,
     token::Operator,
-    utils::{
-        factorial::factorial,
-        hidden_macros::{trust_me, yeet},
-    },
+    trace::{Trace, TraceStep},
+    utils::hidden_macros::{trust_me, yeet},
 };
 
 /// Represents a mathematical expression and its variables.
@@ -42,10 +50,13 @@ impl fmt::Display for Xprs<'_> {
     }
 }
 
-impl Xprs<'_> {
+impl<'src> Xprs<'src> {
     /// Evaluates the expression using the provided variable values.
     /// Returns an [`f64`] if the evaluation is successful, or an [`EvalError`] if an error occurs.
     ///
+    /// Non-finite results (e.g. `sqrt(-1)` or `1 / 0`) are returned as is,
+    /// see [`Xprs::eval_strict`] to report them as errors.
+    ///
     /// # Errors
     ///
     /// An [`EvalError`] is returned if a variable is not provided.
@@ -76,7 +87,132 @@ impl Xprs<'_> {
         &self,
         variables: &HashMap<&str, f64>,
     ) -> Result<f64, EvalError> {
-        XprsImpl::new(variables).eval_element(&self.root)
+        XprsImpl::new(variables, false).eval_element(&self.root)
+    }
+
+    /// Evaluates the expression on integers using the provided variable values.
+    /// Returns a `T` if the evaluation is successful, or an [`EvalError`] if an error occurs.
+    ///
+    /// Every operation is checked, `/` must divide exactly while `//` truncates towards zero.
+    /// Functions are called on [`f64`] values and must return an integer.
+    ///
+    /// Integer literals are exact up to [`u64::MAX`] and constant sub-expressions
+    /// are only folded at parse time while their values are exact (up to `2^53`).
+    ///
+    /// # Errors
+    ///
+    /// An [`EvalError`] is returned if a variable is not provided,
+    /// on division by zero, on overflow, if a value isn't an integer
+    /// or if a function argument isn't exactly representable as an [`f64`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::{EvalError, Xprs};
+    ///
+    /// let xprs = Xprs::try_from("(reg >> 4) & 0xF | 0b1 << 7")?;
+    /// let result = xprs.eval_int::<u64>(&[("reg", 0xAB)].into());
+    /// assert_eq!(result, Ok(0x8A));
+    ///
+    /// let overflow = Xprs::try_from("x * 2")?.eval_int(&[("x", i64::MAX)].into());
+    /// assert!(matches!(overflow, Err(EvalError::Overflow(_))));
+    ///
+    /// let mask = Xprs::try_from("0xFFFF_FFFF_FFFF_FFFF xor (1 << 63)")?;
+    /// assert_eq!(mask.eval_int_no_vars::<u64>(), Ok(u64::MAX >> 1));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    pub fn eval_int<T: Integer>(
+        &self,
+        variables: &HashMap<&str, T>,
+    ) -> Result<T, EvalError> {
+        IntXprsImpl::new(variables).eval_element(&self.root)
+    }
+
+    /// Simple wrapper around [`Xprs::eval_int`] that doesn't require any variables.
+    /// This will obviously fail if the expression contains variables.
+    ///
+    /// # Errors
+    ///
+    /// An [`EvalError`] is returned if the expression contains variables
+    /// or if the integer evaluation fails.
+    #[inline]
+    pub fn eval_int_no_vars<T: Integer>(&self) -> Result<T, EvalError> {
+        self.eval_int(&[].into())
+    }
+
+    /// Evaluates the expression using the provided variable values,
+    /// failing on the first node producing a non-finite value (NaN or infinity).
+    /// Returns an [`f64`] if the evaluation is successful, or an [`EvalError`] if an error occurs.
+    ///
+    /// # Errors
+    ///
+    /// An [`EvalError`] is returned if a variable is not provided or if a node
+    /// produces a non-finite value, in which case the error describes the failing node
+    /// (see [`FailedNode`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::{EvalError, Xprs};
+    ///
+    /// let xprs = Xprs::try_from("1 + sqrt(x)")?;
+    ///
+    /// assert_eq!(xprs.eval_strict(&[("x", 4.0)].into()), Ok(3.0));
+    ///
+    /// let Err(EvalError::DomainError(node)) = xprs.eval_strict(&[("x", -1.0)].into()) else {
+    ///     panic!("`sqrt(-1)` should be a domain error");
+    /// };
+    /// assert_eq!(node.expr, "sqrt(x)");
+    /// assert_eq!(node.name, "sqrt");
+    /// assert_eq!(node.operands, [-1.0]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    pub fn eval_strict(
+        &self,
+        variables: &HashMap<&str, f64>,
+    ) -> Result<f64, EvalError> {
+        XprsImpl::new(variables, true).eval_element(&self.root)
+    }
+
+    /// Evaluates the expression using the provided variable values,
+    /// recording the value of every node of the expression.
+    /// Returns a [`Trace`] if the evaluation is successful, or an [`EvalError`] if an error occurs.
+    ///
+    /// The trace's steps are in evaluation order (operands before their operator),
+    /// its [`Display`](fmt::Display) implementation renders it as an indented tree.
+    ///
+    /// # Errors
+    ///
+    /// An [`EvalError`] is returned if a variable is not provided.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::Xprs;
+    ///
+    /// let xprs = Xprs::try_from("2 * x + y")?;
+    /// let trace = xprs.eval_trace(&[("x", 3.0), ("y", 2.0)].into())?;
+    ///
+    /// assert_eq!(trace.value(), 8.0);
+    /// assert_eq!(trace.steps()[2].expr, "(2 * x)");
+    /// assert_eq!(trace.steps()[2].value, 6.0);
+    /// assert_eq!(
+    ///     trace.to_string(),
+    ///     "((2 * x) + y) = 8\n  (2 * x) = 6\n    2\n    x = 3\n  y = 2\n"
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    pub fn eval_trace(
+        &self,
+        variables: &HashMap<&str, f64>,
+    ) -> Result<Trace, EvalError> {
+        let mut steps = Vec::new();
+        XprsImpl::new(variables, false)
+            .eval_element_traced(&self.root, 0, &mut steps)?;
+        Ok(Trace::new(steps))
     }
 
     /// Evaluates the expression using the provided variable values without error handling.
@@ -117,7 +253,37 @@ impl Xprs<'_> {
     #[inline]
     #[must_use]
     pub fn eval_unchecked(&self, variables: &HashMap<&str, f64>) -> f64 {
-        XprsImpl::new(variables).eval_element_unchecked(&self.root)
+        XprsImpl::new(variables, false).eval_element_unchecked(&self.root)
+    }
+
+    /// Evaluates the expression, resolving the variables with the provided [`VariableResolver`].
+    /// Returns an [`f64`] if the evaluation is successful, or an [`EvalError`] if an error occurs.
+    ///
+    /// Only the variables reached by the evaluation are resolved,
+    /// so values don't have to be collected in a map up front.
+    ///
+    /// # Errors
+    ///
+    /// An [`EvalError`] is returned if a variable cannot be resolved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::Xprs;
+    ///
+    /// let xprs = Xprs::try_from("2 * x + y")?;
+    ///
+    /// assert_eq!(xprs.eval_with(&[("x", 3.0), ("y", 2.0)]), Ok(8.0));
+    /// assert_eq!(xprs.eval_with(&|name: &str| Some(name.len() as f64)), Ok(3.0));
+    /// assert!(xprs.eval_with(&[("x", 3.0)]).is_err());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    pub fn eval_with<R: VariableResolver + ?Sized>(
+        &self,
+        resolver: &R,
+    ) -> Result<f64, EvalError> {
+        XprsImpl::new(resolver, false).eval_element(&self.root)
     }
 
     /// Simplifies the expression in-place for a single variable.
@@ -170,6 +336,60 @@ impl Xprs<'_> {
         self.eval_unchecked(&[].into())
     }
 
+    /// Decodes an expression encoded with [`Xprs::to_bytes`], its names are borrowed from the bytes.
+    ///
+    /// The expression is validated against the context: its functions must be known
+    /// (and permitted) with a valid number of arguments, and its variables must neither be defined
+    /// by the context nor be missing from its expected variables.
+    ///
+    /// # Errors
+    ///
+    /// A [`DecodeError`] is returned if the bytes are malformed, were encoded by another version,
+    /// don't match the context or nest the expression more than 256 levels deep.
+    #[inline]
+    pub fn from_bytes<'bytes>(
+        bytes: &'bytes [u8],
+        ctx: &Context,
+    ) -> Result<Xprs<'bytes>, DecodeError> {
+        binary::decode(bytes, ctx)
+    }
+
+    /// Imports an expression from a JSON AST (see [`Xprs::to_json`]),
+    /// its names are borrowed from the input so they can't contain escape sequences.
+    ///
+    /// Unknown fields are ignored. The expression is validated against the context
+    /// like [`Xprs::from_sexpr`] does.
+    ///
+    /// # Errors
+    ///
+    /// An [`ImportError`] is returned if the input is malformed, nested too deeply or doesn't match the context.
+    #[inline]
+    pub fn from_json<'input>(
+        input: &'input str,
+        ctx: &Context,
+    ) -> Result<Xprs<'input>, ImportError> {
+        json::parse(input, ctx)
+    }
+
+    /// Imports an expression from an S-expression (see [`Xprs::to_sexpr`]),
+    /// its names are borrowed from the input.
+    ///
+    /// The expression is validated against the context: its operators must be applied to a valid
+    /// number of operands, its functions must be known (and permitted) with a valid number
+    /// of arguments, and its variables must neither be defined by the context
+    /// nor be missing from its expected variables.
+    ///
+    /// # Errors
+    ///
+    /// An [`ImportError`] is returned if the input is malformed, nested too deeply or doesn't match the context.
+    #[inline]
+    pub fn from_sexpr<'input>(
+        input: &'input str,
+        ctx: &Context,
+    ) -> Result<Xprs<'input>, ImportError> {
+        sexpr::parse(input, ctx)
+    }
+
     /// Simplifies the expression in-place for a single variable and returns the expression.
     ///
     /// # Example
@@ -251,57 +471,384 @@ impl Xprs<'_> {
         self.simplify_for_multiple_in_place(vars);
         self
     }
+
+    /// Encodes the expression in a compact and versioned binary format,
+    /// to be cached (e.g. on disk) and loaded with [`Xprs::from_bytes`] without parsing it again.
+    ///
+    /// The bytes are the `XPRS` magic, the version of the format, the table of names
+    /// (variables and functions, each stored once) then the tree in pre-order,
+    /// each node being an opcode followed by its number (8 bytes, little-endian),
+    /// the index of its name or its operands. Integers are unsigned LEB128.
+    ///
+    /// Functions are referenced by name, so they must be known by the context given when decoding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::{Context, Parser, Xprs};
+    ///
+    /// let parser = Parser::default();
+    /// let xprs = parser.parse("2 * sin(x) + 2 * x")?;
+    /// let bytes = xprs.to_bytes();
+    ///
+    /// let decoded = Xprs::from_bytes(&bytes, parser.ctx())?;
+    /// assert_eq!(decoded, xprs);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        binary::encode(&self.root)
+    }
+
+    /// Generates the source of a C99 function computing the expression with [`f64`]s (`double`),
+    /// the operations being mapped to the functions of `math.h` (`%` is `fmod`, `^` is `pow`).
+    ///
+    /// Like [`Xprs::to_rust`], the function is named `name` and takes the `params`, in order.
+    /// Fixed arity functions of the context are called by name, namespaces being joined with `_` (`math.f` is `math_f`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::Xprs;
+    ///
+    /// let xprs = Xprs::try_from("2x + sin(y) ^ 2")?;
+    /// assert_eq!(
+    ///     xprs.to_c("f", &["x", "y"])?,
+    ///     "double f(double x, double y) {
+    ///     return ((2.0 * x) + pow(sin(y), 2.0));
+    /// }
+    /// "
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`Xprs::to_rust`], with C identifiers (keywords and the `math.h` functions are rejected),
+    /// and [`CodegenError::Unsupported`] listing the operators and functions without an equivalent
+    /// in C, like `!`, the bitwise operators, `factorial` or the variadic `sum`.
+    #[inline]
+    pub fn to_c(
+        &self,
+        name: &str,
+        params: &[&str],
+    ) -> Result<String, CodegenError> {
+        codegen::generate::<C99>(self, name, params)
+    }
+
+    /// Shares the structurally equal sub-expressions of the expression
+    /// (common sub-expression elimination), turning its tree into a directed acyclic graph.
+    ///
+    /// Operands of commutative operators are compared in any order (`x + 2y` is `2y + x`).
+    /// Calls to impure or non-deterministic functions are never shared (see [`Function::is_cacheable`](crate::Function::is_cacheable)).
+    ///
+    /// Useful when evaluating the same large expression many times.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::Xprs;
+    ///
+    /// let xprs = Xprs::try_from("sin(x + 2y) ^ 2 + sin(2y + x)")?;
+    /// let dag = xprs.to_dag();
+    ///
+    /// // x, 2, y, 2y, x + 2y, sin(x + 2y), ^ and + (`2` and `sin(x + 2y)` are shared)
+    /// assert_eq!(dag.nb_nodes(), 8);
+    /// let vars = [("x", 1.0), ("y", 2.0)].into();
+    /// assert_eq!(dag.eval(&vars), xprs.eval(&vars));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_dag(&self) -> XprsDag<'src> {
+        XprsDag::new(self)
+    }
+
+    /// Renders the expression tree as a [Graphviz](https://graphviz.org) DOT graph,
+    /// to draw it (e.g. with `dot -Tsvg`) when debugging precedence issues.
+    ///
+    /// Nodes are labeled with their operator (circles), function name (boxes),
+    /// variable (ellipses) or number, and the operands are drawn from left to right.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::Xprs;
+    ///
+    /// let xprs = Xprs::try_from("2 * x")?;
+    /// assert_eq!(
+    ///     xprs.to_dot(),
+    ///     r#"digraph xprs {
+    ///     ordering=out;
+    ///     n0 [label="*", shape=circle];
+    ///     n0 -> n1;
+    ///     n1 [label="2", shape=plaintext];
+    ///     n0 -> n2;
+    ///     n2 [label="x", shape=ellipse];
+    /// }
+    /// "#
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        Dot {
+            root: &self.root,
+            other: None,
+        }
+        .to_string()
+    }
+
+    /// Renders the expression tree as a [Graphviz](https://graphviz.org) DOT graph like
+    /// [`Xprs::to_dot`], highlighting the nodes that differ from the other expression.
+    ///
+    /// Both trees are walked together from their roots: a node with the same label and number
+    /// of operands as the node at the same place in the other tree is kept and its operands are compared,
+    /// otherwise it's highlighted with all of its operands.
+    /// Useful to render before/after pictures of a simplification.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::Xprs;
+    ///
+    /// let xprs = Xprs::try_from("sin(x * y) + z")?;
+    /// let simplified = xprs.clone().simplify_for(("y", 1.0));
+    ///
+    /// // `x * y` is highlighted before, and `x` after
+    /// let before = xprs.to_dot_diff(&simplified);
+    /// assert_eq!(before.matches("fillcolor").count(), 3);
+    /// let after = simplified.to_dot_diff(&xprs);
+    /// assert_eq!(after.matches("fillcolor").count(), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_dot_diff(&self, other: &Xprs) -> String {
+        Dot {
+            root: &self.root,
+            other: Some(&other.root),
+        }
+        .to_string()
+    }
+
+    /// Generates the source of a GLSL function computing the expression with scalar `float`s,
+    /// the operations being mapped to the built-in functions (`^` is `pow`, and `%` is computed with `trunc`
+    /// since GLSL's `mod` takes the sign of its divisor).
+    ///
+    /// Like [`Xprs::to_c`], the function is named `name` and takes the `params`, in order,
+    /// and fixed arity functions of the context are called by name, namespaces being joined with `_`.
+    /// Note that GLSL's `pow` is undefined for negative bases.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::Xprs;
+    ///
+    /// let xprs = Xprs::try_from("2x + sin(y) ^ 2")?;
+    /// assert_eq!(
+    ///     xprs.to_glsl("f", &["x", "y"])?,
+    ///     "float f(float x, float y) {
+    ///     return ((2.0 * x) + pow(sin(y), 2.0));
+    /// }
+    /// "
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`Xprs::to_rust`], with GLSL identifiers (keywords, reserved words, types, the built-in functions
+    /// and the `gl_` prefixed names are rejected), and [`CodegenError::Unsupported`] listing
+    /// the operators and functions without an equivalent in GLSL, like `!`, `gamma` or the variadic `sum`.
+    #[inline]
+    pub fn to_glsl(
+        &self,
+        name: &str,
+        params: &[&str],
+    ) -> Result<String, CodegenError> {
+        codegen::generate::<Glsl>(self, name, params)
+    }
+
+    /// Renders the expression tree as a JSON AST, for tools in other languages.
+    ///
+    /// Each node is an object with a `type` and its fields:
+    /// - `{"type":"number","value":2}`, non-finite numbers being `NaN`, `Infinity`
+    ///   and `-Infinity` (as Python's `json` module writes them, strict RFC 8259 parsers reject them),
+    /// - `{"type":"variable","name":"x"}`,
+    /// - `{"type":"unary","op":"-","operand":…}`,
+    /// - `{"type":"binary","op":"+","lhs":…,"rhs":…}`,
+    /// - `{"type":"call","name":"sin","args":[…]}`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::{Parser, Xprs};
+    ///
+    /// let parser = Parser::default();
+    /// let xprs = parser.parse("-x + 2")?;
+    /// let json = xprs.to_json();
+    /// assert_eq!(
+    ///     json,
+    ///     r#"{"type":"binary","op":"+","lhs":{"type":"unary","op":"-","operand":{"type":"variable","name":"x"}},"rhs":{"type":"number","value":2}}"#
+    /// );
+    ///
+    /// let imported = Xprs::from_json(&json, parser.ctx())?;
+    /// assert_eq!(imported, xprs);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        json::Json(&self.root).to_string()
+    }
+
+    /// Prints the expression as a Python expression vectorised with `numpy`,
+    /// the variables being array names, e.g. to cross-check the evaluation of batches in a notebook.
+    ///
+    /// The operations are mapped to `numpy`'s universal functions (`%` is `np.fmod`, `^` is `np.power`),
+    /// `gamma` and `!` to `scipy.special`, so `numpy` must be imported as `np` and `scipy.special` imported
+    /// (`!` is guarded with `np.where` to be `NaN` for negative and non-integer numbers, like when evaluated).
+    /// Functions of the context are called by name, namespaces being attributes (`math.f` stays `math.f`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::Xprs;
+    ///
+    /// let xprs = Xprs::try_from("2x + sin(y) ^ 2")?;
+    /// assert_eq!(xprs.to_numpy()?, "((2.0 * x) + np.power(np.sin(y), 2.0))");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`CodegenError::InvalidIdentifier`] if a variable isn't a valid Python identifier
+    /// (keywords, `np` and `scipy` are rejected), and [`CodegenError::Unsupported`] listing the operators
+    /// and functions without a vectorised equivalent: the bitwise operators and `rand`.
+    #[inline]
+    pub fn to_numpy(&self) -> Result<String, CodegenError> {
+        codegen::expression::<NumPy>(self)
+    }
+
+    /// Generates the source of a Rust function computing the expression,
+    /// e.g. to be written by a build script and [`include!`]d.
+    ///
+    /// The function is named `name` and takes the `params` as [`f64`]s, in order,
+    /// they must contain all the variables of the expression (Rust keywords are written as raw identifiers).
+    ///
+    /// Built-in functions are mapped to [`f64`] methods, the operations without
+    /// an equivalent method (like `!`, `gamma` or the bitwise operators) call the helpers
+    /// of the [`runtime`](crate::runtime) module, so the crate must be a dependency of the generated code.
+    /// Functions of the context are called by name (namespaces becoming modules, `math.f` is `math::f`)
+    /// and must be in scope: fixed arity functions take their arguments as [`f64`]s,
+    /// the others take a slice like the functions of [`xprs_fn!`](crate::xprs_fn).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::Xprs;
+    ///
+    /// let xprs = Xprs::try_from("2x + sin(y) ^ 2")?;
+    /// assert_eq!(
+    ///     xprs.to_rust("f", &["x", "y"])?,
+    ///     "#[allow(unused_parens, unused_variables)]
+    /// pub fn f(x: f64, y: f64) -> f64 {
+    ///     ((2.0_f64 * x) + y.sin().powf(2.0_f64))
+    /// }
+    /// "
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// A [`CodegenError`] is returned if a name isn't a valid Rust identifier,
+    /// if a parameter is duplicated or if a variable isn't a parameter.
+    #[inline]
+    pub fn to_rust(
+        &self,
+        name: &str,
+        params: &[&str],
+    ) -> Result<String, CodegenError> {
+        codegen::generate::<Rust>(self, name, params)
+    }
+
+    /// Renders the expression tree as an S-expression, e.g. `(+ (* 2 (sin x)) 1)`,
+    /// a fully parenthesized format that is easier to read than its [`Debug`](fmt::Debug) dump.
+    ///
+    /// Operators are written with their symbol (`-` is the negation with a single operand),
+    /// functions with their name and numbers as [`f64`]s are displayed,
+    /// with a sign for the non-finite ones (`+inf`, `-inf`, `+NaN`) since `inf` and `NaN` are names.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use xprs::{Parser, Xprs};
+    ///
+    /// let parser = Parser::default();
+    /// let xprs = parser.parse("2 * sin(x) + -y")?;
+    /// assert_eq!(xprs.to_sexpr(), "(+ (* 2 (sin x)) (- y))");
+    ///
+    /// let imported = Xprs::from_sexpr("(+ (* 2 (sin x)) (- y))", parser.ctx())?;
+    /// assert_eq!(imported, xprs);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_sexpr(&self) -> String {
+        sexpr::SExpr(&self.root).to_string()
+    }
 }
 
 /// An internal struct used for evaluating expressions.
 ///
 /// This struct is responsible for handling the evaluation of individual elements within an expression.
 /// It is used by the [`Xprs`] struct to perform evaluations with respect to a given set of variable values.
-struct XprsImpl<'a> {
-    /// A reference to the map of variables and their corresponding values.
-    variables: &'a HashMap<&'a str, f64>,
+struct XprsImpl<'a, R: ?Sized> {
+    /// Whether non-finite results are reported as errors.
+    strict: bool,
+    /// A reference to the resolver of the variables' values.
+    variables: &'a R,
 }
 
-impl XprsImpl<'_> {
+impl<'a, R: VariableResolver + ?Sized> XprsImpl<'a, R> {
     /// Creates a new [`XprsImpl`] instance.
-    const fn new<'a>(variables: &'a HashMap<&str, f64>) -> XprsImpl<'a> {
-        XprsImpl { variables }
+    const fn new(variables: &'a R, strict: bool) -> Self {
+        XprsImpl { strict, variables }
+    }
+
+    /// Checks the result of a node, in strict mode non-finite results are errors.
+    fn check(
+        &self,
+        element: &Element,
+        operands: &[f64],
+        res: f64,
+    ) -> Result<f64, EvalError> {
+        if self.strict && !res.is_finite() {
+            yeet!(EvalError::new_non_finite(element, operands, res));
+        }
+        Ok(res)
     }
 
     /// Evaluates an element within an expression and returns the result.
     fn eval_element(&self, element: &Element) -> Result<f64, EvalError> {
         let res = match *element {
             Element::Number(n) => n,
-            Element::Variable(name) => *self.variables.get(name).ok_or_else(
-                #[cold]
-                || EvalError(name.to_owned()),
-            )?,
+            Element::Integer(int) => int.to_f64(),
+            Element::Variable(name) => self.resolve(name)?,
             Element::UnOp(ref unop) => {
                 let operand = self.eval_element(&unop.operand)?;
-                #[allow(clippy::unreachable)]
-                match unop.op {
-                    Operator::Plus => operand,
-                    Operator::Minus => -operand,
-                    Operator::Factorial => factorial(operand),
-                    Operator::Times
-                    | Operator::Divide
-                    | Operator::Power
-                    | Operator::Modulo => unreachable!(),
-                }
+                let res = unop.op.eval_unary(operand);
+                self.check(element, &[operand], res)?
             },
             Element::BinOp(ref binop) => {
                 let left = self.eval_element(&binop.lhs)?;
                 let right = self.eval_element(&binop.rhs)?;
-                #[allow(clippy::unreachable)]
-                match binop.op {
-                    Operator::Plus => left + right,
-                    Operator::Minus => left - right,
-                    Operator::Times => left * right,
-                    Operator::Divide => left / right,
-                    Operator::Power => left.powf(right),
-                    Operator::Modulo => left % right,
-                    Operator::Factorial => unreachable!(),
-                }
+                let res = binop.op.eval_binary(left, right);
+                self.check(element, &[left, right], res)?
             },
             Element::Function(ref func) => {
                 let args = func
@@ -309,45 +856,76 @@ impl XprsImpl<'_> {
                     .iter()
                     .map(|arg| self.eval_element(arg))
                     .collect::<Result<Vec<_>, EvalError>>()?;
-                func.call(&args)
+                self.check(element, &args, func.call(&args))?
             },
         };
 
         Ok(res)
     }
 
+    /// Evaluates an element and records every node result in evaluation order.
+    /// Returns the result and the index of the node's step.
+    fn eval_element_traced(
+        &self,
+        element: &Element,
+        depth: usize,
+        steps: &mut Vec<TraceStep>,
+    ) -> Result<(f64, usize), EvalError> {
+        let mut children = Vec::new();
+        let mut eval_child = |child: &Element| {
+            let (res, idx) =
+                self.eval_element_traced(child, depth + 1, steps)?;
+            children.push(idx);
+            Ok::<_, EvalError>(res)
+        };
+
+        let value = match *element {
+            Element::Number(n) => n,
+            Element::Integer(int) => int.to_f64(),
+            Element::Variable(name) => self.resolve(name)?,
+            Element::UnOp(ref unop) => {
+                let operand = eval_child(&unop.operand)?;
+                unop.op.eval_unary(operand)
+            },
+            Element::BinOp(ref binop) => {
+                let left = eval_child(&binop.lhs)?;
+                let right = eval_child(&binop.rhs)?;
+                binop.op.eval_binary(left, right)
+            },
+            Element::Function(ref func) => {
+                let args = func
+                    .args
+                    .iter()
+                    .map(&mut eval_child)
+                    .collect::<Result<Vec<_>, EvalError>>()?;
+                func.call(&args)
+            },
+        };
+
+        steps.push(TraceStep {
+            expr: element.to_string(),
+            value,
+            depth,
+            children,
+        });
+        Ok((value, steps.len() - 1))
+    }
+
     /// Evaluates an element within an expression without checking for errors.
     fn eval_element_unchecked(&self, element: &Element) -> f64 {
         match *element {
             Element::Number(n) => n,
+            Element::Integer(int) => int.to_f64(),
             #[allow(clippy::unwrap_used)]
-            Element::Variable(name) => *self.variables.get(name).unwrap(),
+            Element::Variable(name) => self.variables.resolve(name).unwrap(),
             Element::UnOp(ref unop) => {
                 let operand = self.eval_element_unchecked(&unop.operand);
-                #[allow(clippy::unreachable)]
-                match unop.op {
-                    Operator::Plus => operand,
-                    Operator::Minus => -operand,
-                    Operator::Factorial => factorial(operand),
-                    Operator::Times
-                    | Operator::Divide
-                    | Operator::Power
-                    | Operator::Modulo => unreachable!(),
-                }
+                unop.op.eval_unary(operand)
             },
             Element::BinOp(ref binop) => {
                 let left = self.eval_element_unchecked(&binop.lhs);
                 let right = self.eval_element_unchecked(&binop.rhs);
-                #[allow(clippy::unreachable)]
-                match binop.op {
-                    Operator::Plus => left + right,
-                    Operator::Minus => left - right,
-                    Operator::Times => left * right,
-                    Operator::Divide => left / right,
-                    Operator::Power => left.powf(right),
-                    Operator::Modulo => left % right,
-                    Operator::Factorial => unreachable!(),
-                }
+                binop.op.eval_binary(left, right)
             },
             Element::Function(ref func) => {
                 let args = func
@@ -359,14 +937,117 @@ impl XprsImpl<'_> {
             },
         }
     }
+
+    /// Resolves the value of a variable.
+    fn resolve(&self, name: &str) -> Result<f64, EvalError> {
+        self.variables.resolve(name).ok_or_else(
+            #[cold]
+            || EvalError::MissingVariable(name.to_owned()),
+        )
+    }
 }
 
-/// Represents an error that occurs during expression evaluation, indicating that a variable was not provided.
-#[derive(
-    Debug, Eq, PartialEq, thiserror::Error, Clone, Hash, Ord, PartialOrd,
-)]
-#[error("Evaluation error: '{0}' was not provided")]
-pub struct EvalError(String);
+/// Represents an error that occurs during expression evaluation.
+#[derive(Debug, PartialEq, thiserror::Error, Clone)]
+#[non_exhaustive]
+pub enum EvalError {
+    /// A division or a modulo by zero.
+    #[error("Evaluation error: division by zero in `{0}`")]
+    DivisionByZero(FailedNode),
+    /// A value is outside of the domain of an operator or a function (e.g. `sqrt(-1)`).
+    #[error("Evaluation error: domain error in `{0}`")]
+    DomainError(FailedNode),
+    /// A variable was not provided.
+    #[error("Evaluation error: '{0}' was not provided")]
+    MissingVariable(String),
+    /// A value isn't an integer (integer evaluation only).
+    #[error("Evaluation error: `{0}` is not an integer")]
+    NotAnInteger(f64),
+    /// A result is too large to be represented.
+    #[error("Evaluation error: overflow in `{0}`")]
+    Overflow(FailedNode),
+    /// An integer argument of a function isn't exactly representable as an [`f64`]
+    /// (integer evaluation only).
+    #[error("Evaluation error: precision loss in `{0}`")]
+    PrecisionLoss(FailedNode),
+}
+
+impl EvalError {
+    /// Creates the [`EvalError`] corresponding to a node producing a non-finite value.
+    #[cold]
+    fn new_non_finite(element: &Element, operands: &[f64], res: f64) -> Self {
+        const ZERO: f64 = 0.0;
+        let node = FailedNode::new(element, operands);
+        let is_division_by_zero = match *element {
+            Element::BinOp(ref binop) => match *operands {
+                [lhs, rhs] => {
+                    (matches!(
+                        binop.op,
+                        Operator::Divide
+                            | Operator::IntDivide
+                            | Operator::Modulo
+                    ) && rhs == ZERO)
+                        || (binop.op == Operator::Power
+                            && lhs == ZERO
+                            && rhs < ZERO)
+                },
+                _ => false,
+            },
+            Element::UnOp(_)
+            | Element::Function(_)
+            | Element::Number(_)
+            | Element::Integer(_)
+            | Element::Variable(_) => false,
+        };
+
+        if is_division_by_zero {
+            Self::DivisionByZero(node)
+        // functions can't tell a pole from an overflow
+        } else if res.is_nan() || matches!(*element, Element::Function(_)) {
+            Self::DomainError(node)
+        } else {
+            Self::Overflow(node)
+        }
+    }
+}
+
+/// Describes the node of the expression an [`EvalError`] originates from.
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub struct FailedNode {
+    /// The formatted sub-expression.
+    pub expr: String,
+    /// The operator or the function name.
+    pub name: String,
+    /// The values of the operands (or arguments).
+    pub operands: Vec<f64>,
+}
+
+impl FailedNode {
+    /// Creates a new [`FailedNode`] from an element and its operands values.
+    pub(crate) fn new(element: &Element, operands: &[f64]) -> Self {
+        let name = match *element {
+            Element::BinOp(ref binop) => binop.op.to_string(),
+            Element::UnOp(ref unop) => unop.op.to_string(),
+            Element::Function(ref func) => func.desc.name.to_string(),
+            Element::Number(_) | Element::Integer(_) | Element::Variable(_) => {
+                String::new()
+            },
+        };
+        Self {
+            expr: element.to_string(),
+            name,
+            operands: operands.to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for FailedNode {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.expr)
+    }
+}
 
 //////////////////////////////////////////////////////////////////////////////
 //  TODO: replace this with variadic generics when it's available & stable  //
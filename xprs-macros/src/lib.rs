@@ -0,0 +1,195 @@
+//! Procedural macro compiling [Xprs](https://docs.rs/xprs) expressions into native Rust functions.
+//!
+//! ```
+//! use xprs_macros::xprs;
+//!
+//! let f = xprs!("2x + y", x, y);
+//! assert_eq!(f(1.0, 3.0), 5.0);
+//! ```
+//!
+//! The generated code calls the `xprs::runtime` helpers for the operations without an [`f64`] method
+//! (`!`, `gamma`, bitwise operators…), so `xprs` must be a dependency too.
+
+/* Built-in imports */
+extern crate proc_macro;
+use proc_macro::{
+    Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream,
+    TokenTree,
+};
+/* Dependencies imports */
+use xprs::Parser;
+
+/// Name of the generated function.
+const FN_NAME: &str = "__xprs";
+
+/// Compiles an expression into a native Rust function taking the listed variables as [`f64`]s, in order.
+///
+/// The expression is parsed (and simplified) at compile time with the default context,
+/// so only the built-in constants and functions are available.
+/// Use `Xprs::to_rust` from a build script for the functions of your own context.
+///
+/// # Example
+///
+/// ```
+/// use xprs_macros::xprs;
+///
+/// let hypot = xprs!("sqrt(a^2 + b^2)", a, b);
+/// assert_eq!(hypot(3.0, 4.0), 5.0);
+///
+/// let pi = xprs!("2 * asin(1)");
+/// assert_eq!(pi(), core::f64::consts::PI);
+/// ```
+///
+/// Parsing errors, and variables missing from the list, are reported as compilation errors:
+///
+/// ```compile_fail
+/// use xprs_macros::xprs;
+///
+/// let f = xprs!("2x + y", x);
+/// ```
+#[proc_macro]
+#[inline]
+pub fn xprs(input: TokenStream) -> TokenStream {
+    expand(input)
+        .unwrap_or_else(|(message, span)| compile_error(&message, span))
+}
+
+/// Expands the macro, or returns an error message and its location.
+fn expand(input: TokenStream) -> Result<TokenStream, (String, Span)> {
+    let mut tokens = input.into_iter().map(|token| match token {
+        // fragments forwarded by declarative macros are wrapped in invisible groups
+        TokenTree::Group(ref group) if group.delimiter() == Delimiter::None => {
+            let mut inner = group.stream().into_iter();
+            match (inner.next(), inner.next()) {
+                (Some(single), None) => single,
+                (Some(_) | None, _) => token,
+            }
+        },
+        TokenTree::Group(_)
+        | TokenTree::Ident(_)
+        | TokenTree::Punct(_)
+        | TokenTree::Literal(_) => token,
+    });
+    let (expr, expr_span) = match tokens.next() {
+        Some(TokenTree::Literal(lit)) => (
+            string_value(&lit.to_string()).ok_or_else(|| {
+                ("expected a string literal".to_owned(), lit.span())
+            })?,
+            lit.span(),
+        ),
+        Some(token) => {
+            return Err(("expected a string literal".to_owned(), token.span()))
+        },
+        None => {
+            return Err((
+                "expected an expression".to_owned(),
+                Span::call_site(),
+            ))
+        },
+    };
+
+    let mut params = Vec::new();
+    while let Some(separator) = tokens.next() {
+        if !matches!(separator, TokenTree::Punct(ref punct) if punct.as_char() == ',')
+        {
+            return Err(("expected `,`".to_owned(), separator.span()));
+        }
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) => params.push(ident.to_string()),
+            // trailing comma
+            None => break,
+            Some(token) => {
+                return Err((
+                    "expected a variable name".to_owned(),
+                    token.span(),
+                ))
+            },
+        }
+    }
+    let names = params
+        .iter()
+        .map(|param| param.trim_start_matches("r#"))
+        .collect::<Vec<_>>();
+
+    let xprs = Parser::default()
+        .parse(&expr)
+        .map_err(|err| (err.to_string(), expr_span))?;
+    let code = xprs
+        .to_rust(FN_NAME, &names)
+        .map_err(|err| (err.to_string(), expr_span))?;
+    let expanded = format!("{{ {code} {FN_NAME} }}")
+        .parse()
+        .map_err(|err| (format!("{err:?}"), expr_span))?;
+    Ok(with_mixed_site(expanded))
+}
+
+/// Sets the spans of the generated code to the mixed site,
+/// so its parameters don't collide with the caller's variables.
+fn with_mixed_site(stream: TokenStream) -> TokenStream {
+    stream
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(ref mut group) = token {
+                let mut respanned = Group::new(
+                    group.delimiter(),
+                    with_mixed_site(group.stream()),
+                );
+                respanned.set_span(Span::mixed_site());
+                *group = respanned;
+            } else {
+                token.set_span(Span::mixed_site());
+            }
+            token
+        })
+        .collect()
+}
+
+/// Returns the value of a string literal, handling raw strings and the common escapes.
+fn string_value(lit: &str) -> Option<String> {
+    if let Some(raw) = lit.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        return raw
+            .get(hashes + 1..raw.len().checked_sub(hashes + 1)?)
+            .map(str::to_owned);
+    }
+    let inner = lit.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(chr) = chars.next() {
+        if chr != '\\' {
+            value.push(chr);
+            continue;
+        }
+        match chars.next()? {
+            'n' => value.push('\n'),
+            'r' => value.push('\r'),
+            't' => value.push('\t'),
+            '0' => value.push('\0'),
+            escaped @ ('\\' | '"' | '\'') => value.push(escaped),
+            // line continuation
+            '\n' => {
+                chars = chars.as_str().trim_start().chars();
+            },
+            _ => return None,
+        }
+    }
+    Some(value)
+}
+
+/// Creates a `compile_error!` invocation located at `span`.
+fn compile_error(message: &str, span: Span) -> TokenStream {
+    let mut lit = Literal::string(message);
+    lit.set_span(span);
+    let mut args = Group::new(
+        Delimiter::Parenthesis,
+        TokenStream::from(TokenTree::Literal(lit)),
+    );
+    args.set_span(span);
+    [
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Group(args),
+    ]
+    .into_iter()
+    .collect()
+}
@@ -0,0 +1,85 @@
+/* Clippy config */
+#![allow(
+    clippy::tests_outside_test_module,
+    clippy::redundant_test_prefix,
+    clippy::default_numeric_fallback,
+    clippy::float_cmp,
+    clippy::min_ident_chars,
+    clippy::suboptimal_flops,
+    clippy::approx_constant,
+    clippy::absolute_paths
+)]
+/* Dependencies imports */
+use xprs::Xprs;
+use xprs_macros::xprs;
+
+/// Asserts that the compiled expression matches its evaluation, for every set of values.
+macro_rules! assert_compiled {
+    ($expr:literal, [$($var:ident),*], $values:expr) => {
+        let compiled = xprs!($expr, $($var),*);
+        let xprs = Xprs::try_from($expr).unwrap();
+        for &[$($var),*] in $values {
+            let expected = xprs
+                .eval(&[$((stringify!($var), $var)),*].into())
+                .unwrap();
+            let result = compiled($($var),*);
+            assert!(
+                result == expected || (result.is_nan() && expected.is_nan()),
+                "{}: {result} != {expected}",
+                $expr
+            );
+        }
+    };
+}
+
+#[test]
+fn test_operators() {
+    let values = &[[1.0, 2.0], [-3.5, 0.5], [12.0, 3.0], [0.0, -2.0]];
+    assert_compiled!("x + y - x * y / 2", [x, y], values);
+    assert_compiled!("-x ^ y + x // y", [x, y], values);
+    assert_compiled!("x % y + (x + y)!", [x, y], values);
+    assert_compiled!("x & y | 1 xor 4 << 2 >> 1", [x, y], values);
+    assert_compiled!("+x - -y", [x, y], values);
+}
+
+#[test]
+fn test_functions() {
+    let values = &[[0.5, 2.0], [1.5, -0.25], [8.0, 3.0]];
+    assert_compiled!("sin(x) + cos(y) * tan(x) - atan2(y, x)", [x, y], values);
+    assert_compiled!("ln(x) + log(x) + logn(x, y) + exp(-y)", [x, y], values);
+    assert_compiled!("sqrt(x) + cbrt(y) + abs(y) + recip(x)", [x, y], values);
+    assert_compiled!(
+        "floor(y) + ceil(x) + trunc(y) + fract(x)",
+        [x, y],
+        values
+    );
+    assert_compiled!(
+        "round(x) + round(y * 100, 1) + hypot(x, y)",
+        [x, y],
+        values
+    );
+    assert_compiled!(
+        "sum(x, y, 1) + mean(x, y) + min(x, y) + max(x, y, 0)",
+        [x, y],
+        values
+    );
+    assert_compiled!(
+        "gamma(x) + factorial(3) + sinh(x) * acosh(x + 1)",
+        [x, y],
+        values
+    );
+}
+
+#[test]
+fn test_names() {
+    // keywords are raw identifiers
+    let keywords = xprs!("match * 2 + type", r#match, r#type);
+    assert_eq!(keywords(1.0, 2.0), 4.0);
+    // unused parameters
+    let unused = xprs!("x", x, y,);
+    assert_eq!(unused(1.0, 2.0), 1.0);
+    let tau = xprs!(r"2 * pi");
+    assert_eq!(tau(), core::f64::consts::TAU);
+    let random = xprs!("rand()")();
+    assert!((0.0..1.0).contains(&random));
+}